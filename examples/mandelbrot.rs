@@ -7,6 +7,7 @@ use zi::{
 
 type Position = euclid::default::Point2D<f64>;
 
+#[allow(dead_code)]
 #[derive(Clone, Debug)]
 struct Theme {
     logo: Style,
@@ -71,7 +72,7 @@ impl Mandelbrot {
                             break;
                         }
                     }
-                    let conv = (num_steps as f64 / 1000.0).max(0.0).min(1.0);
+                    let conv = (num_steps as f64 / 1000.0).clamp(0.0, 1.0);
                     // let conv2 = 1.0 - (z.norm_sqr() / target).max(0.0).min(1.0);
                     // let conv = conv1 * conv2;
                     // let xx = (conv * 255.0).floor() as u8;
@@ -152,6 +153,7 @@ enum Message {
 
 #[derive(Debug)]
 struct Viewer {
+    #[allow(dead_code)]
     theme: Theme,
     position: Position,
     scale: f64,
@@ -156,13 +156,16 @@ impl Component for Todo {
             let style = InputStyle {
                 content: content_style,
                 cursor: cursor_style,
+                ..InputStyle::default()
             };
             let cursor = self.cursor.clone();
             Input::with(InputProperties {
                 style,
-                content: Rope::from_str(&content),
+                content: Rope::from_str(content),
                 cursor,
+                placeholder: None,
                 on_change: self.handle_input_change.clone().into(),
+                on_submit: None,
                 focused: true,
             })
         } else {
@@ -390,6 +393,7 @@ impl Component for TodoMvc {
                 background: theme.unchecked,
                 direction: FlexDirection::Column,
                 num_items: todos.len(),
+                on_toggle: None,
                 selected: focus_index,
                 item_at: {
                     let todos = todos.clone();
@@ -4,7 +4,8 @@
 use futures::{self, stream::StreamExt};
 use smallvec::SmallVec;
 use std::{
-    collections::HashMap,
+    cmp,
+    collections::{HashMap, VecDeque},
     time::{Duration, Instant},
 };
 use tokio::{
@@ -12,16 +13,22 @@ use tokio::{
     runtime::{Builder as RuntimeBuilder, Runtime},
     sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     backend::{Backend, Event},
     component::{
-        layout::{LaidCanvas, LaidComponent, Layout},
+        layout::{Clip, LaidCanvas, LaidComponent, LaidOverlay, Layout},
         template::{ComponentId, DynamicMessage, DynamicProperties, Renderable, Template},
         BindingMatch, BindingTransition, LinkMessage, ShouldRender,
     },
     error::Result,
-    terminal::{Canvas, Key, Position, Rect, Size},
+    notification::Notifications,
+    terminal::{
+        Canvas, Colour, CursorStyle, Key, MouseButton, MouseEvent, MouseEventKind, Position, Rect,
+        Size, Style,
+    },
+    theme::{SharedTheme, Theme},
 };
 
 /// The `App` application runtime, which runs the event loop and draws your
@@ -33,6 +40,16 @@ pub struct App {
     subscriptions: ComponentSubscriptions,
     controller: InputController,
     link: LinkChannel,
+    redraw_latency: Duration,
+    performance_overlay: Option<PerformanceOverlay>,
+    notifications: Notifications,
+    mouse_selection: bool,
+    selection: Option<Selection>,
+    /// The topmost modal overlay layer, 0 if there is none.
+    modal_layer: usize,
+    /// The components which got the last mouse press. They get the following
+    /// drags and the release wherever the pointer is.
+    pointer_capture: Vec<ComponentId>,
 }
 
 impl App {
@@ -54,10 +71,107 @@ impl App {
             subscriptions: ComponentSubscriptions::new(),
             controller: InputController::new(),
             link: LinkChannel::new(),
+            redraw_latency: DEFAULT_REDRAW_LATENCY,
+            performance_overlay: None,
+            notifications: Notifications::default(),
+            mouse_selection: false,
+            selection: None,
+            modal_layer: 0,
+            pointer_capture: Vec::new(),
             root,
         }
     }
 
+    /// Sets the maximum number of frames drawn per second, 100 by default.
+    ///
+    /// Frames are only drawn when something changed, e.g. in response to user
+    /// input or a message sent to a component. When nothing changes and no
+    /// component needs ticking, the event loop sleeps until the next event.
+    ///
+    /// ```
+    /// # use zi::prelude::*;
+    /// # use zi::components::text::{Text, TextProperties};
+    /// let mut app = App::new(layout::component::<Text>(
+    ///    TextProperties::new().content("Hello, world!"),
+    /// ));
+    /// app.set_max_fps(30);
+    /// ```
+    pub fn set_max_fps(&mut self, max_fps: u32) -> &mut Self {
+        self.redraw_latency = Duration::from_secs(1) / max_fps.max(1);
+        self
+    }
+
+    /// Shows or hides an overlay in the top right corner of the screen with
+    /// the frame rate, draw and present times and component statistics.
+    /// Hidden by default, it can also be toggled at runtime using
+    /// [`ComponentLink::toggle_performance_overlay`](struct.ComponentLink.html#method.toggle_performance_overlay).
+    pub fn set_performance_overlay(&mut self, enabled: bool) -> &mut Self {
+        self.performance_overlay = if enabled {
+            Some(self.performance_overlay.take().unwrap_or_default())
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Sets how long to wait for the next key of a chord (e.g. `C-x C-s`)
+    /// before the pending keys are discarded. By default there is no
+    /// timeout and a pending chord waits indefinitely.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use zi::prelude::*;
+    /// # use zi::components::text::{Text, TextProperties};
+    /// let mut app = App::new(layout::component::<Text>(
+    ///    TextProperties::new().content("Hello, world!"),
+    /// ));
+    /// app.set_chord_timeout(Duration::from_secs(1));
+    /// ```
+    pub fn set_chord_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.controller.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the theme components read with
+    /// [`ComponentLink::theme`](struct.ComponentLink.html#method.theme), the
+    /// dark theme by default. It can also be switched at runtime using
+    /// [`ComponentLink::set_theme`](struct.ComponentLink.html#method.set_theme).
+    ///
+    /// ```
+    /// # use zi::prelude::*;
+    /// # use zi::{components::text::{Text, TextProperties}, Theme};
+    /// let mut app = App::new(layout::component::<Text>(
+    ///    TextProperties::new().content("Hello, world!"),
+    /// ));
+    /// app.set_theme(Theme::light());
+    /// ```
+    pub fn set_theme(&mut self, theme: Theme) -> &mut Self {
+        *self
+            .link
+            .theme
+            .write()
+            .expect("Theme lock shouldn't be poisoned") = theme;
+        // Everything is rendered again with the new theme
+        for component in self.components.values_mut() {
+            component.should_render = true;
+        }
+        self
+    }
+
+    /// Enables selecting text on the screen by dragging with the left mouse
+    /// button. The selection is highlighted and its text is copied to the
+    /// clipboard when the button is released. Disabled by default.
+    ///
+    /// Components still receive mouse presses and releases, but not the drag
+    /// events that make up a selection.
+    pub fn set_mouse_selection(&mut self, enabled: bool) -> &mut Self {
+        self.mouse_selection = enabled;
+        if !enabled {
+            self.selection = None;
+        }
+        self
+    }
+
     /// Starts the event loop. This is the main entry point of a Zi application.
     /// It draws and presents the components to the backend, handles user input
     /// and delivers messages to components. This method returns either when
@@ -78,7 +192,7 @@ impl App {
     pub fn run_event_loop(&mut self, mut backend: impl Backend) -> Result<()> {
         let mut screen = Canvas::new(backend.size()?);
         let mut poll_state = PollState::Dirty(None);
-        let mut last_drawn = Instant::now() - REDRAW_LATENCY;
+        let mut last_drawn = Instant::now() - self.redraw_latency;
         let mut runtime = RuntimeBuilder::new_current_thread().enable_all().build()?;
         let mut num_frame = 0;
 
@@ -100,7 +214,12 @@ impl App {
 
                     let frame = Rect::new(Position::new(0, 0), screen.size());
                     let statistics = self.draw(&mut screen, frame, num_frame);
+                    self.notifications.draw(&mut screen, &self.link.theme());
                     let drawn_time = now.elapsed();
+                    if let Some(overlay) = self.performance_overlay.as_mut() {
+                        overlay.record_frame(drawn_time, self.components.len(), &statistics);
+                        overlay.draw(&mut screen);
+                    }
 
                     // Present
                     let now = Instant::now();
@@ -116,6 +235,9 @@ impl App {
                         presented_time.as_secs_f64() * 1000.0,
                         num_bytes_presented,
                     );
+                    if let Some(overlay) = self.performance_overlay.as_mut() {
+                        overlay.record_present(presented_time);
+                    }
                     last_drawn = Instant::now();
                     num_frame += 1;
                 }
@@ -142,15 +264,24 @@ impl App {
         } = *self;
 
         subscriptions.clear();
+        // Containers don't necessarily cover their whole frame, e.g. when
+        // their children are centred, so nothing from the previous frame
+        // should show in between
+        screen.clear(Style::default());
 
         let mut first = true;
         let mut pending = Vec::new();
+        let mut overlays: Vec<(LaidOverlay, bool)> = Vec::new();
+        let mut overlay_layout = None;
+        let mut z_index = i32::MIN;
+        let mut layer = 0;
+        let mut modal_layer = 0;
         let mut statistics = DrawStatistics::default();
         loop {
-            let (layout, frame2, position_hash, parent_changed) = if first {
+            let (layout, frame2, position_hash, parent_changed, clip) = if first {
                 first = false;
-                (&mut self.root, frame, 0, false)
-            } else if let Some((component_id, frame, position_hash)) = pending.pop() {
+                (&mut self.root, frame, 0, false, None)
+            } else if let Some((component_id, frame, position_hash, clip)) = pending.pop() {
                 let component = components
                     .get_mut(&component_id)
                     .expect("Layout is cached only for mounted components");
@@ -162,7 +293,37 @@ impl App {
                     *layout = component.view()
                 }
                 component.set_generation(generation);
-                (layout, frame, position_hash, changed)
+                (layout, frame, position_hash, changed, clip)
+            } else if let Some(index) = overlays
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (overlay, _))| overlay.z_index)
+                .map(|(index, _)| index)
+            {
+                // Everything below the layer has been drawn. Layers are drawn
+                // by z-index, in the order they were laid out for equal ones.
+                let (overlay, parent_changed) = overlays.remove(index);
+                let LaidOverlay {
+                    frame,
+                    position_hash,
+                    layout,
+                    modal,
+                    z_index: layer_z_index,
+                    ..
+                } = overlay;
+                z_index = layer_z_index;
+                layer += 1;
+                if modal {
+                    subscriptions.focused.clear();
+                    modal_layer = layer;
+                }
+                (
+                    overlay_layout.insert(layout),
+                    frame,
+                    position_hash,
+                    parent_changed,
+                    None,
+                )
             } else {
                 break;
             };
@@ -174,19 +335,30 @@ impl App {
                           frame,
                           position_hash,
                           template,
+                          clip: component_clip,
                       }| {
                     let component_id = template.generate_id(position_hash);
+                    let clip = Clip::nest(component_clip, clip);
                     let mut new_component = false;
                     let component = components.entry(component_id).or_insert_with(|| {
                         new_component = true;
-                        let renderable = template.create(component_id, frame, link.sender.clone());
+                        let renderable = template.create(
+                            component_id,
+                            frame,
+                            link.sender.clone(),
+                            link.theme.clone(),
+                        );
                         MountedComponent {
                             renderable,
                             frame,
                             should_render: ShouldRender::Yes.into(),
                             generation,
+                            layer,
+                            clip,
                         }
                     });
+                    component.layer = layer;
+                    component.clip = clip;
 
                     if !new_component {
                         let mut changed =
@@ -220,13 +392,59 @@ impl App {
                     //     frame,
                     // );
 
-                    pending.push((component_id, frame, position_hash));
+                    pending.push((component_id, frame, position_hash, clip));
                 },
-                &mut |LaidCanvas { frame, canvas, .. }| {
-                    screen.copy_region(&canvas, frame);
+                &mut |LaidCanvas {
+                          frame,
+                          canvas,
+                          clip: canvas_clip,
+                      }| match Clip::nest(canvas_clip, clip) {
+                    Some(clip) => clip.draw(screen, canvas, frame),
+                    None => screen.copy_region(canvas, frame),
+                },
+                &mut |mut overlay| {
+                    if let Some(clip) = clip.filter(|_| !overlay.absolute) {
+                        overlay.frame = clip.visible(overlay.frame);
+                    }
+                    // Layers inside a layer are drawn above it
+                    overlay.z_index = cmp::max(overlay.z_index, z_index);
+                    overlays.push((overlay, parent_changed))
                 },
             );
         }
+        self.modal_layer = modal_layer;
+
+        // Show the cursor of the outermost focused component that has one.
+        let cursor = subscriptions.focused.iter().find_map(|component_id| {
+            let component = components.get(component_id)?;
+            let position = component.cursor_position()?;
+            let frame = component.frame;
+            if position.x < frame.size.width && position.y < frame.size.height {
+                let position = frame.origin + position.to_vector();
+                let position = match component.clip {
+                    Some(clip) => clip.visible_position(position)?,
+                    None => position,
+                };
+                Some((position, component.cursor_style()))
+            } else {
+                None
+            }
+        });
+        if let Some((_, cursor_style)) = cursor {
+            screen.set_cursor_style(cursor_style);
+        }
+        screen.set_cursor(cursor.map(|(position, _)| position));
+
+        if let Some(selection) = self.selection.as_mut() {
+            if selection.released && !selection.copied {
+                link.sender
+                    .send(LinkMessage::SetClipboard(selection.text(screen)))
+                    .map_err(|_| ())
+                    .expect("App needs to outlive components");
+                selection.copied = true;
+            }
+            selection.draw(screen);
+        }
 
         // Drop components that are not part of the current layout tree, i.e. do
         // not appear on the screen.
@@ -249,7 +467,7 @@ impl App {
         statistics
     }
 
-    /// Poll as many events as we can respecting REDRAW_LATENCY and REDRAW_LATENCY_SUSTAINED_IO
+    /// Poll as many events as we can respecting `redraw_latency` and SUSTAINED_IO_REDRAW_LATENCY
     #[inline]
     fn poll_events_batch(
         &mut self,
@@ -262,46 +480,62 @@ impl App {
         let mut poll_state = PollState::Clean;
 
         while !force_redraw && !poll_state.exit() {
+            // When there's nothing to draw, no component to tick, no chord
+            // that could time out and no toast to dismiss, only wake up for
+            // input events or link messages.
+            let chord_remaining = self.controller.remaining();
+            let toast_remaining = self.notifications.remaining();
+            let idle = !poll_state.dirty()
+                && self.subscriptions.tickable.is_empty()
+                && chord_remaining.is_none()
+                && toast_remaining.is_none();
             let timeout_duration = {
                 let since_last_drawn = last_drawn.elapsed();
-                if poll_state.dirty() && since_last_drawn >= REDRAW_LATENCY {
-                    Duration::from_millis(0)
-                } else if poll_state.dirty() {
-                    REDRAW_LATENCY - since_last_drawn
+                let timeout_duration = if poll_state.dirty() {
+                    self.redraw_latency.saturating_sub(since_last_drawn)
                 } else {
-                    Duration::from_millis(if self.subscriptions.tickable.is_empty() {
-                        240
-                    } else {
-                        60
-                    })
-                }
+                    TICK_INTERVAL
+                };
+                chord_remaining
+                    .into_iter()
+                    .chain(toast_remaining)
+                    .fold(timeout_duration, Duration::min)
             };
             (runtime.block_on(async {
                 tokio::select! {
                     link_message = self.link.receiver.recv() => {
-                        poll_state = self.handle_link_message(
+                        poll_state = poll_state.merge(self.handle_link_message(
                             backend,
                             link_message.expect("At least one sender exists."),
-                        )?;
+                        )?);
                         Ok(())
                     }
                     input_event = backend.event_stream().next() => {
-                        poll_state = self.handle_input_event(input_event.expect(
+                        poll_state = poll_state.merge(self.handle_input_event(input_event.expect(
                             "At least one sender exists.",
-                        )?)?;
+                        )?)?);
                         force_redraw = poll_state.dirty()
                             && (first_event_time.get_or_insert_with(Instant::now).elapsed()
                                 >= SUSTAINED_IO_REDRAW_LATENCY
                                 || poll_state.resized());
                         Ok(())
                     }
-                    _ = tokio::time::sleep(timeout_duration) => {
+                    _ = tokio::time::sleep(timeout_duration), if !idle => {
+                        if self.controller.timed_out() {
+                            self.controller.keys.clear();
+                            if self.notify_pending_keys() {
+                                poll_state = poll_state.merge(PollState::Dirty(None));
+                            }
+                        }
+                        if self.notifications.expire() {
+                            poll_state = poll_state.merge(PollState::Dirty(None));
+                        }
                         for TickSubscription {
                             component_id,
                             message,
                         } in self.subscriptions.tickable.drain(..)
                         {
-                            poll_state = PollState::Dirty(None);
+                            poll_state = poll_state.merge(PollState::Dirty(None));
                             match self.components.get_mut(&component_id) {
                                 Some(component) => {
                                     component.update(message);
@@ -350,6 +584,27 @@ impl App {
                 }
             }
             LinkMessage::Exit => PollState::Exit,
+            LinkMessage::SetClipboard(contents) => {
+                backend.set_clipboard(&contents)?;
+                PollState::Clean
+            }
+            LinkMessage::SetTitle(title) => {
+                backend.set_title(&title)?;
+                PollState::Clean
+            }
+            LinkMessage::Notify(toast) => {
+                self.notifications.post(toast);
+                PollState::Dirty(None)
+            }
+            LinkMessage::SetTheme(theme) => {
+                self.set_theme(theme);
+                PollState::Dirty(None)
+            }
+            LinkMessage::TogglePerformanceOverlay => {
+                let enabled = self.performance_overlay.is_none();
+                self.set_performance_overlay(enabled);
+                PollState::Dirty(None)
+            }
             LinkMessage::RunExclusive(process) => {
                 backend.suspend()?;
                 let maybe_message = process();
@@ -375,14 +630,178 @@ impl App {
     #[inline]
     fn handle_input_event(&mut self, event: Event) -> Result<PollState> {
         Ok(match event {
-            Event::Key(key) => {
+            Event::Key(key) | Event::KeyRepeat(key) => {
+                let had_pending_keys = !self.controller.keys.is_empty();
                 self.handle_key(key)?;
+                if had_pending_keys || !self.controller.keys.is_empty() {
+                    self.notify_pending_keys();
+                }
                 PollState::Dirty(None) // handle_event should return whether we need to rerender
             }
+            Event::Paste(text) => {
+                self.handle_paste(&text);
+                PollState::Dirty(None)
+            }
+            Event::Mouse(event) => {
+                if self.handle_mouse(event) {
+                    PollState::Dirty(None)
+                } else {
+                    PollState::Clean
+                }
+            }
             Event::Resize(size) => PollState::Dirty(Some(size)),
+            Event::Focus(focused) => {
+                if self.handle_window_focus(focused) {
+                    PollState::Dirty(None)
+                } else {
+                    PollState::Clean
+                }
+            }
         })
     }
 
+    #[inline]
+    fn handle_paste(&mut self, text: &str) {
+        let Self {
+            ref mut components,
+            ref subscriptions,
+            ..
+        } = *self;
+        for component_id in subscriptions.focused.iter() {
+            let focused_component = components
+                .get_mut(component_id)
+                .expect("A focused component should be mounted.");
+            if let Some(message) = focused_component.paste_binding(text) {
+                focused_component.update(message);
+            }
+        }
+    }
+
+    /// Delivers a mouse event to all components whose frame contains the
+    /// pointer, or for drags and releases to those which got the press.
+    /// Returns whether any of them need to be re-rendered.
+    #[inline]
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        let mut should_render = false;
+        if self.mouse_selection {
+            match event.kind {
+                MouseEventKind::Press(MouseButton::Left) => {
+                    should_render = self.selection.is_some();
+                    self.selection = Some(Selection::new(event.position));
+                }
+                MouseEventKind::Drag(MouseButton::Left) => {
+                    if let Some(selection) = self.selection.as_mut() {
+                        selection.head = event.position;
+                        selection.dragged = true;
+                        return true;
+                    }
+                }
+                MouseEventKind::Release(MouseButton::Left) => match self.selection.as_mut() {
+                    Some(selection) if selection.dragged => {
+                        selection.head = event.position;
+                        selection.released = true;
+                        should_render = true;
+                    }
+                    _ => {
+                        self.selection = None;
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        // Drags and releases go to the components which got the press, even if
+        // the pointer left them, e.g. while dragging a scrollbar's thumb
+        if let MouseEventKind::Drag(_) | MouseEventKind::Release(_) = event.kind {
+            if !self.pointer_capture.is_empty() {
+                let Self {
+                    ref mut components,
+                    ref mut pointer_capture,
+                    ..
+                } = *self;
+                for id in pointer_capture.iter() {
+                    let component = match components.get_mut(id) {
+                        Some(component) => component,
+                        None => continue,
+                    };
+                    let position = component.captured_position(event.position);
+                    let local_event = MouseEvent { position, ..event };
+                    if let Some(message) = component.mouse_binding(&local_event) {
+                        should_render = component.update(message) || should_render;
+                    }
+                }
+                if let MouseEventKind::Release(_) = event.kind {
+                    pointer_capture.clear();
+                }
+                return should_render;
+            }
+        }
+
+        // Only components in the topmost layer under the pointer get the
+        // event, and nothing below a modal layer does.
+        let modal_layer = self.modal_layer;
+        let top_layer = self
+            .components
+            .values()
+            .filter(|component| {
+                component.layer >= modal_layer && component.local_position(event.position).is_some()
+            })
+            .map(|component| component.layer)
+            .max();
+        let is_press = matches!(event.kind, MouseEventKind::Press(_));
+        if is_press {
+            self.pointer_capture.clear();
+        }
+        for (id, component) in self.components.iter_mut() {
+            if Some(component.layer) != top_layer {
+                continue;
+            }
+            let position = match component.local_position(event.position) {
+                Some(position) => position,
+                None => continue,
+            };
+            if is_press {
+                self.pointer_capture.push(*id);
+            }
+            let local_event = MouseEvent { position, ..event };
+            if let Some(message) = component.mouse_binding(&local_event) {
+                should_render = component.update(message) || should_render;
+            }
+        }
+        should_render
+    }
+
+    /// Lets every component know the terminal window gained or lost focus.
+    /// Returns whether any of them need to be re-rendered.
+    #[inline]
+    fn handle_window_focus(&mut self, focused: bool) -> bool {
+        let mut should_render = false;
+        for component in self.components.values_mut() {
+            if let Some(message) = component.window_focus_changed(focused) {
+                should_render = component.update(message) || should_render;
+            }
+        }
+        should_render
+    }
+
+    /// Lets every component know the keys of the pending chord have
+    /// changed. Returns whether any of them need to be re-rendered.
+    #[inline]
+    fn notify_pending_keys(&mut self) -> bool {
+        let Self {
+            ref mut components,
+            ref controller,
+            ..
+        } = *self;
+        let mut should_render = false;
+        for component in components.values_mut() {
+            if let Some(message) = component.pending_keys_changed(&controller.keys) {
+                should_render = component.update(message) || should_render;
+            }
+        }
+        should_render
+    }
+
     #[inline]
     fn handle_key(&mut self, key: Key) -> Result<()> {
         let Self {
@@ -433,12 +852,21 @@ impl App {
 struct LinkChannel {
     sender: UnboundedSender<LinkMessage>,
     receiver: UnboundedReceiver<LinkMessage>,
+    theme: SharedTheme,
 }
 
 impl LinkChannel {
     fn new() -> Self {
         let (sender, receiver) = mpsc::unbounded_channel();
-        Self { sender, receiver }
+        Self {
+            sender,
+            receiver,
+            theme: SharedTheme::default(),
+        }
+    }
+
+    fn theme(&self) -> Theme {
+        *self.theme.read().expect("Theme lock shouldn't be poisoned")
     }
 }
 
@@ -478,7 +906,7 @@ struct TickSubscription {
     message: DynamicMessage,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum PollState {
     Clean,
     Dirty(Option<Size>),
@@ -497,6 +925,17 @@ impl PollState {
     fn exit(&self) -> bool {
         matches!(*self, Self::Exit)
     }
+
+    /// Combines the state after handling a new event with the current one. A
+    /// pending exit, resize or redraw is never dropped.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Exit, _) | (_, Self::Exit) => Self::Exit,
+            (Self::Dirty(Some(size)), Self::Dirty(None)) => Self::Dirty(Some(size)),
+            (Self::Dirty(size), Self::Clean) => Self::Dirty(size),
+            (_, other) => other,
+        }
+    }
 }
 
 type Generation = usize;
@@ -506,9 +945,49 @@ struct MountedComponent {
     frame: Rect,
     generation: Generation,
     should_render: bool,
+    /// The overlay layer the component was laid out in, 0 for the base layout.
+    layer: usize,
+    /// Set when the component is in a viewport, its frame is then in the
+    /// coordinates of the viewport's content.
+    clip: Option<Clip>,
 }
 
 impl MountedComponent {
+    /// The position relative to the component's frame, if the component is
+    /// shown there.
+    fn local_position(&self, position: Position) -> Option<Position> {
+        let position = match self.clip {
+            Some(clip) => clip.content_position(position)?,
+            None => position,
+        };
+        let frame = self.frame;
+        if frame.contains(position) {
+            Some(Position::new(
+                position.x - frame.origin.x,
+                position.y - frame.origin.y,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// The position relative to the component's frame, clamped to the frame,
+    /// for the events it captured wherever the pointer is.
+    fn captured_position(&self, position: Position) -> Position {
+        let (x, y) = match self.clip {
+            Some(clip) => clip.content_point(position),
+            None => (position.x as isize, position.y as isize),
+        };
+        let frame = self.frame;
+        let clamp = |value: isize, origin: usize, size: usize| {
+            (value - origin as isize).clamp(0, size.saturating_sub(1) as isize) as usize
+        };
+        Position::new(
+            clamp(x, frame.origin.x, frame.size.width),
+            clamp(y, frame.origin.y, frame.size.height),
+        )
+    }
+
     #[inline]
     fn change(&mut self, properties: DynamicProperties) -> bool {
         self.should_render = self.renderable.change(properties).into() || self.should_render;
@@ -544,6 +1023,36 @@ impl MountedComponent {
         self.renderable.input_binding(pressed)
     }
 
+    #[inline]
+    fn paste_binding(&self, text: &str) -> Option<DynamicMessage> {
+        self.renderable.paste_binding(text)
+    }
+
+    #[inline]
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<DynamicMessage> {
+        self.renderable.mouse_binding(event)
+    }
+
+    #[inline]
+    fn window_focus_changed(&self, focused: bool) -> Option<DynamicMessage> {
+        self.renderable.window_focus_changed(focused)
+    }
+
+    #[inline]
+    fn pending_keys_changed(&self, pending: &[Key]) -> Option<DynamicMessage> {
+        self.renderable.pending_keys_changed(pending)
+    }
+
+    #[inline]
+    fn cursor_position(&self) -> Option<Position> {
+        self.renderable.cursor_position()
+    }
+
+    #[inline]
+    fn cursor_style(&self) -> CursorStyle {
+        self.renderable.cursor_style()
+    }
+
     #[inline]
     fn tick(&self) -> Option<DynamicMessage> {
         self.renderable.tick()
@@ -557,40 +1066,168 @@ impl MountedComponent {
 
 struct InputController {
     keys: SmallVec<[Key; 8]>,
+    timeout: Option<Duration>,
+    last_key_time: Instant,
 }
 
 impl InputController {
     fn new() -> Self {
         Self {
             keys: SmallVec::new(),
+            timeout: None,
+            last_key_time: Instant::now(),
         }
     }
 
     fn push(&mut self, key: Key) {
+        if self.timed_out() {
+            self.keys.clear();
+        }
         self.keys.push(key);
+        self.last_key_time = Instant::now();
+    }
+
+    /// How long until the pending chord times out, if there is one.
+    fn remaining(&self) -> Option<Duration> {
+        let timeout = self.timeout?;
+        if self.keys.is_empty() {
+            return None;
+        }
+        Some(timeout.saturating_sub(self.last_key_time.elapsed()))
+    }
+
+    fn timed_out(&self) -> bool {
+        self.remaining() == Some(Duration::from_secs(0))
     }
 }
 
 impl std::fmt::Display for InputController {
     fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
         for key in self.keys.iter() {
-            match key {
-                Key::Char(' ') => write!(formatter, "SPC ")?,
-                Key::Char('\n') => write!(formatter, "RET ")?,
-                Key::Char('\t') => write!(formatter, "TAB ")?,
-                Key::Char(char) => write!(formatter, "{} ", char)?,
-                Key::Ctrl(char) => write!(formatter, "C-{} ", char)?,
-                Key::Alt(char) => write!(formatter, "A-{} ", char)?,
-                Key::F(number) => write!(formatter, "F{} ", number)?,
-                Key::Esc => write!(formatter, "ESC ")?,
-                key => write!(formatter, "{:?} ", key)?,
-            }
+            write!(formatter, "{} ", key)?;
         }
         Ok(())
     }
 }
 
-const REDRAW_LATENCY: Duration = Duration::from_millis(10);
+/// A linear range of cells selected with the mouse, from `anchor` (where the
+/// drag started) to `head` (where the pointer is), both inclusive.
+struct Selection {
+    anchor: Position,
+    head: Position,
+    dragged: bool,
+    released: bool,
+    copied: bool,
+}
+
+impl Selection {
+    fn new(position: Position) -> Self {
+        Self {
+            anchor: position,
+            head: position,
+            dragged: false,
+            released: false,
+            copied: false,
+        }
+    }
+
+    /// Iterates over the selected row indices and column ranges in the
+    /// screen, in reading order.
+    fn rows(&self, size: Size) -> impl Iterator<Item = (usize, std::ops::Range<usize>)> {
+        let (start, end) = if (self.anchor.y, self.anchor.x) <= (self.head.y, self.head.x) {
+            (self.anchor, self.head)
+        } else {
+            (self.head, self.anchor)
+        };
+        (start.y..=end.y.min(size.height.saturating_sub(1))).map(move |y| {
+            let x_start = if y == start.y { start.x } else { 0 };
+            let x_end = if y == end.y { end.x + 1 } else { size.width };
+            (y, x_start.min(size.width)..x_end.min(size.width))
+        })
+    }
+
+    /// The selected text, with trailing whitespace removed from every line.
+    fn text(&self, screen: &Canvas) -> String {
+        let mut text = String::new();
+        for (index, (y, x_range)) in self.rows(screen.size()).enumerate() {
+            if index > 0 {
+                text.push('\n');
+            }
+            let line_start = text.len();
+            for x in x_range {
+                if let Some(textel) = screen.textel(x, y) {
+                    text.push_str(&textel.grapheme);
+                }
+            }
+            let line_end = text[line_start..].trim_end().len();
+            text.truncate(line_start + line_end);
+        }
+        text
+    }
+
+    fn draw(&self, screen: &mut Canvas) {
+        for (y, x_range) in self.rows(screen.size()) {
+            for x in x_range {
+                if let Some(textel) = screen.textel_mut(x, y) {
+                    textel.style.reverse = true;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct PerformanceOverlay {
+    frame_times: VecDeque<Instant>,
+    drawn_time: Duration,
+    presented_time: Duration,
+    summary: String,
+}
+
+impl PerformanceOverlay {
+    fn record_frame(
+        &mut self,
+        drawn_time: Duration,
+        num_components: usize,
+        statistics: &DrawStatistics,
+    ) {
+        let now = Instant::now();
+        self.frame_times.push_back(now);
+        while let Some(frame_time) = self.frame_times.front() {
+            if now.duration_since(*frame_time) <= Duration::from_secs(1) {
+                break;
+            }
+            self.frame_times.pop_front();
+        }
+        self.drawn_time = drawn_time;
+        self.summary = format!(
+            " {} fps | draw {:.1}ms pres {:.1}ms | {} comps [{}] ",
+            self.frame_times.len(),
+            self.drawn_time.as_secs_f64() * 1000.0,
+            self.presented_time.as_secs_f64() * 1000.0,
+            num_components,
+            statistics,
+        );
+    }
+
+    /// Present time is only known after the frame was drawn, it is shown in
+    /// the following frame.
+    fn record_present(&mut self, presented_time: Duration) {
+        self.presented_time = presented_time;
+    }
+
+    fn draw(&self, screen: &mut Canvas) {
+        let width = UnicodeWidthStr::width(self.summary.as_str());
+        let x = screen.size().width.saturating_sub(width);
+        screen.draw_str(x, 0, PERFORMANCE_OVERLAY_STYLE, &self.summary);
+    }
+}
+
+const PERFORMANCE_OVERLAY_STYLE: Style =
+    Style::normal(Colour::rgb(204, 36, 29), Colour::rgb(251, 241, 199));
+
+const DEFAULT_REDRAW_LATENCY: Duration = Duration::from_millis(10);
+const TICK_INTERVAL: Duration = Duration::from_millis(60);
 const SUSTAINED_IO_REDRAW_LATENCY: Duration = Duration::from_millis(100);
 
 #[derive(Default)]
@@ -613,7 +1250,202 @@ impl std::fmt::Display for DrawStatistics {
 
 #[cfg(test)]
 mod tests {
-    use super::{ComponentId, DynamicMessage, LinkMessage};
+    use super::{
+        App, Canvas, ComponentId, DynamicMessage, Layout, LinkMessage, PollState, Position, Rect,
+        Selection, Size, Style,
+    };
+    use crate::layout::{self, Layer};
+
+    #[test]
+    fn merge_poll_states() {
+        let resized = PollState::Dirty(Some(Size::new(80, 24)));
+        assert_eq!(
+            PollState::Dirty(None).merge(PollState::Clean),
+            PollState::Dirty(None)
+        );
+        assert_eq!(resized.merge(PollState::Dirty(None)), resized);
+        assert_eq!(resized.merge(PollState::Clean), resized);
+        assert_eq!(PollState::Clean.merge(resized), resized);
+        assert_eq!(PollState::Exit.merge(resized), PollState::Exit);
+    }
+
+    #[test]
+    fn selection_text() {
+        let mut screen = Canvas::new(Size::new(6, 3));
+        screen.clear(Style::default());
+        screen.draw_str(0, 0, Style::default(), "one");
+        screen.draw_str(0, 1, Style::default(), "two 中");
+        screen.draw_str(0, 2, Style::default(), "three");
+
+        let mut selection = Selection::new(Position::new(1, 0));
+        selection.head = Position::new(1, 2);
+        assert_eq!(selection.text(&screen), "ne\ntwo 中\nth");
+
+        // Selecting backwards gives the same text
+        let mut selection = Selection::new(Position::new(4, 1));
+        selection.head = Position::new(0, 1);
+        assert_eq!(selection.text(&screen), "two 中");
+    }
+
+    #[test]
+    fn layers_by_z_index() {
+        let text = |content: &str| {
+            let mut canvas = Canvas::new(Size::new(content.len(), 1));
+            canvas.clear(Style::default());
+            canvas.draw_str(0, 0, Style::default(), content);
+            Layout::from(canvas)
+        };
+        let frame = |x| Rect::new(Position::new(x, 0), Size::new(3, 1));
+        let mut app = App::new(layout::overlay(
+            text("......"),
+            [
+                Layer::absolute(frame(0), text("top")).z_index(1),
+                Layer::absolute(frame(0), text("mid")),
+                Layer::at(frame(3), layout::overlay(text("low"), [])).z_index(-1),
+            ],
+        ));
+        let mut screen = Canvas::new(Size::new(6, 1));
+        let frame = Rect::new(Position::new(0, 0), screen.size());
+        app.draw(&mut screen, frame, 0);
+        let mut selection = Selection::new(Position::new(0, 0));
+        selection.head = Position::new(5, 0);
+        assert_eq!(selection.text(&screen), "toplow");
+    }
+
+    #[test]
+    fn viewport_clips_content() {
+        use crate::components::text::{Text, TextProperties};
+
+        let text = |key, content: &str| {
+            layout::component_with_key::<Text>(key, TextProperties::new().content(content))
+        };
+        let content = layout::column([
+            layout::fixed(1, text(0, "abcdef")),
+            layout::fixed(1, text(1, "ghijkl")),
+            layout::fixed(1, text(2, "mnopqr")),
+        ]);
+        let mut separator = Canvas::new(Size::new(1, 2));
+        separator.clear_with(Style::default(), "|");
+        let mut app = App::new(layout::row([
+            layout::fixed(1, separator.into()),
+            layout::auto(layout::viewport(
+                Size::new(6, 3),
+                Position::new(1, 1),
+                content,
+            )),
+        ]));
+        let mut screen = Canvas::new(Size::new(5, 2));
+        let frame = Rect::new(Position::new(0, 0), screen.size());
+        // Components are drawn once they were created
+        app.draw(&mut screen, frame, 0);
+        app.draw(&mut screen, frame, 1);
+        let mut selection = Selection::new(Position::new(0, 0));
+        selection.head = Position::new(4, 1);
+        assert_eq!(selection.text(&screen), "|hijk\n|nopq");
+
+        // The last row of the content is shown in the second row of the
+        // screen, starting with its second character
+        let positions: Vec<_> = app
+            .components
+            .values()
+            .filter_map(|component| component.local_position(Position::new(1, 1)))
+            .collect();
+        assert_eq!(positions, [Position::new(1, 0)]);
+    }
+
+    #[test]
+    fn drag_outside_of_frame() {
+        use std::{cell::RefCell, rc::Rc};
+
+        use crate::{
+            Component, ComponentLink, MouseButton, MouseEvent, MouseEventKind, ShouldRender,
+        };
+
+        type Events = Rc<RefCell<Vec<MouseEvent>>>;
+
+        struct Recorder {
+            events: Events,
+        }
+
+        impl Component for Recorder {
+            type Message = ();
+            type Properties = Events;
+
+            fn create(events: Events, _frame: Rect, _link: ComponentLink<Self>) -> Self {
+                Self { events }
+            }
+
+            fn view(&self) -> Layout {
+                Canvas::new(Size::new(1, 2)).into()
+            }
+
+            fn update(&mut self, _: ()) -> ShouldRender {
+                ShouldRender::No
+            }
+
+            fn mouse_binding(&self, event: &MouseEvent) -> Option<()> {
+                self.events.borrow_mut().push(*event);
+                None
+            }
+        }
+
+        let events = Events::default();
+        let mut app = App::new(layout::row([
+            layout::fixed(1, layout::component::<Recorder>(events.clone())),
+            layout::auto(Canvas::new(Size::new(4, 2)).into()),
+        ]));
+        let mut screen = Canvas::new(Size::new(5, 2));
+        let frame = Rect::new(Position::new(0, 0), screen.size());
+        app.draw(&mut screen, frame, 0);
+        app.draw(&mut screen, frame, 1);
+
+        let event = |kind, x, y| MouseEvent {
+            kind,
+            position: Position::new(x, y),
+            modifiers: Default::default(),
+        };
+        let left = MouseButton::Left;
+        app.handle_mouse(event(MouseEventKind::Press(left), 0, 1));
+        app.handle_mouse(event(MouseEventKind::Drag(left), 3, 1));
+        app.handle_mouse(event(MouseEventKind::Release(left), 4, 0));
+        // Once released, events outside of the frame aren't delivered
+        app.handle_mouse(event(MouseEventKind::Drag(left), 3, 0));
+        // The positions are clamped to the frame
+        assert_eq!(
+            *events.borrow(),
+            [
+                event(MouseEventKind::Press(left), 0, 1),
+                event(MouseEventKind::Drag(left), 0, 1),
+                event(MouseEventKind::Release(left), 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn switching_theme_renders_again() {
+        use crate::{
+            components::text::{Text, TextProperties},
+            Theme,
+        };
+
+        let mut app = App::new(layout::component::<Text>(
+            TextProperties::new().content("Hello"),
+        ));
+        let mut screen = Canvas::new(Size::new(5, 1));
+        let frame = Rect::new(Position::new(0, 0), screen.size());
+        app.draw(&mut screen, frame, 0);
+        assert!(app
+            .components
+            .values()
+            .all(|component| !component.should_render));
+
+        app.set_theme(Theme::light());
+        assert_eq!(app.link.theme(), Theme::light());
+        assert!(app
+            .components
+            .values()
+            .all(|component| component.should_render));
+    }
 
     #[test]
     fn sizes() {
@@ -1,18 +1,30 @@
 //! Terminal backend implementation using [crossterm](https://docs.rs/crossterm)
 
-use crossterm::{self, queue, QueueableCommand};
-use futures::stream::{Stream, StreamExt};
+use crossterm::{
+    self,
+    event::{KeyCode, KeyEventKind},
+    queue, QueueableCommand,
+};
+use futures::{
+    future,
+    stream::{Stream, StreamExt},
+};
 use std::{
+    collections::HashSet,
     io::{self, BufWriter, Stdout, Write},
     pin::Pin,
+    sync::{Arc, Mutex},
 };
 
 use super::{
     painter::{FullPainter, IncrementalPainter, PaintOperation, Painter},
-    utils::MeteredWriter,
-    Backend, Event, Result,
+    utils::{self, MeteredWriter},
+    Backend, Capabilities, Event, GraphicsProtocol, Result,
+};
+use crate::terminal::{
+    Blink, Canvas, Colour, CursorShape, CursorStyle, ImagePlacement, Key, MediaKey, Modifiers,
+    MouseButton, MouseEvent, MouseEventKind, Position, Size, Style, Underline,
 };
-use crate::terminal::{Canvas, Colour, Key, Size, Style};
 
 /// Creates a new backend with an incremental painter. It only draws those
 /// parts of the terminal that have changed since last drawn.
@@ -26,34 +38,56 @@ pub fn full() -> Result<Crossterm<FullPainter>> {
     Crossterm::<FullPainter>::new()
 }
 
-/// Crossterm error type
-pub type Error = crossterm::ErrorKind;
+/// Crossterm error type. Since crossterm 0.26 this is simply `io::Error`.
+pub type Error = io::Error;
 
 /// Backend based on [crossterm](https://docs.rs/crossterm)
 pub struct Crossterm<PainterT: Painter = IncrementalPainter> {
     target: MeteredWriter<BufWriter<Stdout>>,
     painter: PainterT,
     events: Option<Pin<Box<dyn Stream<Item = Result<Event>> + Send + 'static>>>,
+    cursor_visible: bool,
+    cursor_style: Option<CursorStyle>,
+    capabilities: Capabilities,
+    /// Keys with no zi equivalent which were already logged, shared with the
+    /// event stream.
+    unmapped_keys: Arc<Mutex<HashSet<KeyCode>>>,
 }
 
 impl<PainterT: Painter> Crossterm<PainterT> {
     /// Create a new backend instance.
     ///
-    /// This method initialises the underlying tty device, enables raw mode,
-    /// hides the cursor and enters alternative screen mode. Additionally, an
-    /// async event stream with input events from stdin is started.
+    /// This method initialises the underlying tty device, enables raw mode and
+    /// mouse capture, bracketed paste and focus reporting, hides the cursor and
+    /// enters alternative screen mode. Additionally, an async event stream with
+    /// input events from stdin is started.
+    ///
+    /// The terminal's capabilities are detected from the environment, see
+    /// [`with_capabilities`](#method.with_capabilities) to override them.
     pub fn new() -> Result<Self> {
+        let unmapped_keys = Arc::new(Mutex::new(HashSet::new()));
         let mut backend = Self {
             target: MeteredWriter::new(BufWriter::with_capacity(1 << 20, io::stdout())),
             painter: PainterT::create(
                 crossterm::terminal::size()
                     .map(|(width, height)| Size::new(width as usize, height as usize))?,
             ),
-            events: Some(new_event_stream()),
+            events: Some(new_event_stream(Arc::clone(&unmapped_keys))),
+            cursor_visible: false,
+            cursor_style: None,
+            capabilities: detect_capabilities(),
+            unmapped_keys,
         };
         initialise_tty::<PainterT, _>(&mut backend.target)?;
         Ok(backend)
     }
+
+    /// Overrides the detected capabilities of the terminal, e.g. to enable a
+    /// graphics protocol for a terminal that isn't recognised.
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
 }
 
 impl<PainterT: Painter> Backend for Crossterm<PainterT> {
@@ -70,6 +104,9 @@ impl<PainterT: Painter> Backend for Crossterm<PainterT> {
         let Self {
             ref mut target,
             ref mut painter,
+            ref mut cursor_visible,
+            ref mut cursor_style,
+            capabilities,
             ..
         } = *self;
         let initial_num_bytes_written = target.num_bytes_written();
@@ -79,13 +116,43 @@ impl<PainterT: Painter> Backend for Crossterm<PainterT> {
                     queue!(target, crossterm::style::Print(grapheme))?
                 }
                 PaintOperation::SetStyle(style) => queue_set_style(target, style)?,
+                PaintOperation::SetLink(url) => queue!(
+                    target,
+                    crossterm::style::Print(format!("\x1b]8;;{}\x1b\\", url.unwrap_or("")))
+                )?,
                 PaintOperation::MoveTo(position) => queue!(
                     target,
                     crossterm::cursor::MoveTo(position.x as u16, position.y as u16)
                 )?, // Go to the begining of line (`MoveTo` uses 0-based indexing)
+                PaintOperation::ClearImages => {
+                    if capabilities.graphics == Some(GraphicsProtocol::Kitty) {
+                        queue!(target, crossterm::style::Print(utils::KITTY_DELETE_IMAGES))?
+                    }
+                }
+                PaintOperation::DrawImage(placement) => {
+                    if let Some(protocol) = capabilities.graphics {
+                        queue_draw_image(target, protocol, placement)?
+                    }
+                }
             }
             Ok(())
         })?;
+
+        // The painter leaves the terminal's cursor at the canvas' cursor
+        // position, only its visibility needs updating
+        let show_cursor = canvas.cursor().is_some();
+        if show_cursor != *cursor_visible {
+            if show_cursor {
+                queue!(target, crossterm::cursor::Show)?;
+            } else {
+                queue!(target, crossterm::cursor::Hide)?;
+            }
+            *cursor_visible = show_cursor;
+        }
+        if show_cursor && *cursor_style != Some(canvas.cursor_style()) {
+            queue_set_cursor_style(target, canvas.cursor_style())?;
+            *cursor_style = Some(canvas.cursor_style());
+        }
         target.flush()?;
         Ok(target.num_bytes_written() - initial_num_bytes_written)
     }
@@ -98,24 +165,134 @@ impl<PainterT: Painter> Backend for Crossterm<PainterT> {
     #[inline]
     fn suspend(&mut self) -> Result<()> {
         self.events = None;
+        // The keyboard enhancement is pushed again on resume
+        #[cfg(not(windows))]
+        {
+            queue!(self.target, crossterm::event::PopKeyboardEnhancementFlags)?;
+            self.target.flush()?;
+        }
         Ok(())
     }
 
     #[inline]
     fn resume(&mut self) -> Result<()> {
         self.painter = PainterT::create(self.size()?);
-        self.events = Some(new_event_stream());
+        self.events = Some(new_event_stream(Arc::clone(&self.unmapped_keys)));
+        self.cursor_visible = false;
+        self.cursor_style = None;
         initialise_tty::<PainterT, _>(&mut self.target)
     }
+
+    #[inline]
+    fn set_clipboard(&mut self, contents: &str) -> Result<()> {
+        // OSC 52, supported by most modern terminal emulators (and tmux with
+        // `set-clipboard on`), even over ssh.
+        queue!(
+            self.target,
+            crossterm::style::Print(format_args!(
+                "\x1b]52;c;{}\x07",
+                utils::base64_encode(contents.as_bytes())
+            ))
+        )?;
+        self.target.flush()?;
+        Ok(())
+    }
+
+    #[inline]
+    fn set_title(&mut self, title: &str) -> Result<()> {
+        queue!(self.target, crossterm::terminal::SetTitle(title))?;
+        self.target.flush()?;
+        Ok(())
+    }
+
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+}
+
+/// Guesses the capabilities of the terminal from environment variables set by
+/// terminal emulators. Querying the terminal would be more reliable, but the
+/// response would race with the input event stream.
+fn detect_capabilities() -> Capabilities {
+    let var = |name| std::env::var(name).unwrap_or_default();
+    let term = var("TERM");
+    let program = var("TERM_PROGRAM");
+    // Multiplexers need the sequences wrapped to pass them through, and even
+    // then they don't keep track of the images
+    let graphics = if std::env::var_os("TMUX").is_some() || term.starts_with("screen") {
+        None
+    } else if term == "xterm-kitty"
+        || term == "xterm-ghostty"
+        || std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || program == "WezTerm"
+        || program == "ghostty"
+    {
+        Some(GraphicsProtocol::Kitty)
+    } else if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") {
+        Some(GraphicsProtocol::Sixel)
+    } else {
+        None
+    };
+    Capabilities { graphics }
+}
+
+#[inline]
+fn queue_draw_image(
+    target: &mut impl Write,
+    protocol: GraphicsProtocol,
+    placement: &ImagePlacement,
+) -> Result<()> {
+    let ImagePlacement { region, ref image } = *placement;
+    queue!(
+        target,
+        crossterm::cursor::MoveTo(region.origin.x as u16, region.origin.y as u16)
+    )?;
+    match protocol {
+        GraphicsProtocol::Kitty => queue!(
+            target,
+            crossterm::style::Print(utils::kitty_encode(image, region.size))
+        )?,
+        GraphicsProtocol::Sixel => {
+            // Sixel images are drawn at their size in pixels, so they're
+            // resized to cover the region. Assume cells of 10x20 pixels if the
+            // terminal doesn't report its size in pixels.
+            let cell_size = crossterm::terminal::window_size()
+                .ok()
+                .filter(|size| size.width > 0 && size.columns > 0 && size.rows > 0)
+                .map_or(Size::new(10, 20), |size| {
+                    Size::new(
+                        usize::from(size.width / size.columns),
+                        usize::from(size.height / size.rows),
+                    )
+                });
+            let pixels = image.resized(Size::new(
+                region.size.width * cell_size.width,
+                region.size.height * cell_size.height,
+            ));
+            queue!(
+                target,
+                crossterm::style::Print(utils::sixel_encode(&pixels))
+            )?
+        }
+    }
+    Ok(())
 }
 
 impl<PainterT: Painter> Drop for Crossterm<PainterT> {
     fn drop(&mut self) {
+        #[cfg(not(windows))]
+        queue!(self.target, crossterm::event::PopKeyboardEnhancementFlags)
+            .expect("Failed to reset the keyboard when closing `crossterm` backend.");
         queue!(
             self.target,
             crossterm::style::ResetColor,
             crossterm::terminal::Clear(crossterm::terminal::ClearType::All),
+            crossterm::style::Print(RESET_CURSOR_STYLE),
             crossterm::cursor::Show,
+            crossterm::event::DisableMouseCapture,
+            crossterm::event::DisableBracketedPaste,
+            crossterm::event::DisableFocusChange,
             crossterm::terminal::LeaveAlternateScreen
         )
         .expect("Failed to clear screen when closing `crossterm` backend.");
@@ -128,7 +305,20 @@ impl<PainterT: Painter> Drop for Crossterm<PainterT> {
 fn initialise_tty<PainterT: Painter, TargetT: Write>(target: &mut TargetT) -> Result<()> {
     target
         .queue(crossterm::terminal::EnterAlternateScreen)?
+        .queue(crossterm::event::EnableMouseCapture)?
+        .queue(crossterm::event::EnableBracketedPaste)?
+        .queue(crossterm::event::EnableFocusChange)?
         .queue(crossterm::cursor::Hide)?;
+    // Needed for keys without a legacy encoding, e.g. media keys and F13-F24,
+    // and to tell key repeats apart. Terminals which don't support the kitty
+    // keyboard protocol ignore it, the Windows console reports repeats anyway.
+    #[cfg(not(windows))]
+    {
+        use crossterm::event::KeyboardEnhancementFlags as Flags;
+        target.queue(crossterm::event::PushKeyboardEnhancementFlags(
+            Flags::DISAMBIGUATE_ESCAPE_CODES | Flags::REPORT_EVENT_TYPES,
+        ))?;
+    }
     crossterm::terminal::enable_raw_mode()?;
     queue_set_style(target, &PainterT::INITIAL_STYLE)?;
     target.flush()?;
@@ -141,9 +331,17 @@ fn queue_set_style(target: &mut impl Write, style: &Style) -> Result<()> {
         Attribute, Color, SetAttribute, SetBackgroundColor, SetForegroundColor,
     };
 
-    // Bold
-    if style.bold {
-        queue!(target, SetAttribute(Attribute::Bold))?;
+    // Bold and dim
+    if style.bold || style.dim {
+        // Bold and dim share the intensity attribute, clear whichever one
+        // was set previously before applying the new ones.
+        queue!(target, SetAttribute(Attribute::NormalIntensity))?;
+        if style.bold {
+            queue!(target, SetAttribute(Attribute::Bold))?;
+        }
+        if style.dim {
+            queue!(target, SetAttribute(Attribute::Dim))?;
+        }
     } else {
         // Using Reset is not ideal as it resets all style attributes. The correct thing to do
         // would be to use `NoBold`, but it seems this is not reliably supported (at least it
@@ -152,11 +350,44 @@ fn queue_set_style(target: &mut impl Write, style: &Style) -> Result<()> {
         queue!(target, SetAttribute(Attribute::Reset))?;
     }
 
+    // Italic
+    if style.italic {
+        queue!(target, SetAttribute(Attribute::Italic))?;
+    } else {
+        queue!(target, SetAttribute(Attribute::NoItalic))?;
+    }
+
     // Underline
-    if style.underline {
-        queue!(target, SetAttribute(Attribute::Underlined))?;
+    match style.underline {
+        Underline::None => queue!(target, SetAttribute(Attribute::NoUnderline))?,
+        Underline::Single => queue!(target, SetAttribute(Attribute::Underlined))?,
+        // There's no `crossterm` attribute for double underline. `SGR 21` is
+        // interpreted as "bold off" by some terminals, use the colon
+        // separated form first introduced by kitty instead.
+        Underline::Double => queue!(target, crossterm::style::Print("\x1b[4:2m"))?,
+        // Terminals without undercurl support fall back to a single underline.
+        Underline::Curly => queue!(target, crossterm::style::Print("\x1b[4:3m"))?,
+    }
+
+    // Strikethrough
+    if style.strikethrough {
+        queue!(target, SetAttribute(Attribute::CrossedOut))?;
     } else {
-        queue!(target, SetAttribute(Attribute::NoUnderline))?;
+        queue!(target, SetAttribute(Attribute::NotCrossedOut))?;
+    }
+
+    // Blink
+    match style.blink {
+        Blink::None => queue!(target, SetAttribute(Attribute::NoBlink))?,
+        Blink::Slow => queue!(target, SetAttribute(Attribute::SlowBlink))?,
+        Blink::Rapid => queue!(target, SetAttribute(Attribute::RapidBlink))?,
+    }
+
+    // Reverse
+    if style.reverse {
+        queue!(target, SetAttribute(Attribute::Reverse))?;
+    } else {
+        queue!(target, SetAttribute(Attribute::NoReverse))?;
     }
 
     // Background
@@ -188,30 +419,72 @@ fn queue_set_style(target: &mut impl Write, style: &Style) -> Result<()> {
     Ok(())
 }
 
+/// Sets the cursor style using `DECSCUSR`, "Set Cursor Style".
 #[inline]
-fn new_event_stream() -> <Crossterm as Backend>::EventStream {
+fn queue_set_cursor_style(target: &mut impl Write, cursor_style: CursorStyle) -> Result<()> {
+    let code = match (cursor_style.shape, cursor_style.blinking) {
+        (CursorShape::Block, true) => 1,
+        (CursorShape::Block, false) => 2,
+        (CursorShape::Underline, true) => 3,
+        (CursorShape::Underline, false) => 4,
+        (CursorShape::Bar, true) => 5,
+        (CursorShape::Bar, false) => 6,
+    };
+    queue!(
+        target,
+        crossterm::style::Print(format_args!("\x1b[{} q", code))
+    )?;
+    Ok(())
+}
+
+/// Restores the terminal's configured cursor style.
+const RESET_CURSOR_STYLE: &str = "\x1b[0 q";
+
+#[inline]
+fn new_event_stream(
+    unmapped_keys: Arc<Mutex<HashSet<KeyCode>>>,
+) -> <Crossterm as Backend>::EventStream {
     Box::pin(
         crossterm::event::EventStream::new()
-            .filter_map(|event| async move {
-                match event {
+            .filter_map(move |event| {
+                future::ready(match event {
                     Ok(crossterm::event::Event::Key(key_event)) => {
-                        Some(Ok(Event::Key(map_key(key_event))))
+                        map_key(key_event, &unmapped_keys).map(|key| {
+                            Ok(if key_event.kind == KeyEventKind::Repeat {
+                                Event::KeyRepeat(key)
+                            } else {
+                                Event::Key(key)
+                            })
+                        })
+                    }
+                    Ok(crossterm::event::Event::Mouse(mouse_event)) => {
+                        Some(Ok(Event::Mouse(map_mouse(mouse_event))))
                     }
                     Ok(crossterm::event::Event::Resize(width, height)) => Some(Ok(Event::Resize(
                         Size::new(width as usize, height as usize),
                     ))),
-                    Ok(_) => None,
+                    Ok(crossterm::event::Event::Paste(text)) => Some(Ok(Event::Paste(text))),
+                    Ok(crossterm::event::Event::FocusGained) => Some(Ok(Event::Focus(true))),
+                    Ok(crossterm::event::Event::FocusLost) => Some(Ok(Event::Focus(false))),
                     Err(error) => Some(Err(error.into())),
-                }
+                })
             })
             .fuse(),
     )
 }
 
 #[inline]
-fn map_key(key: crossterm::event::KeyEvent) -> Key {
-    use crossterm::event::{KeyCode, KeyModifiers};
-    match key.code {
+fn map_key(
+    key: crossterm::event::KeyEvent,
+    unmapped_keys: &Mutex<HashSet<KeyCode>>,
+) -> Option<Key> {
+    use crossterm::event::KeyModifiers;
+    // Key releases are only reported on Windows, or when the terminal
+    // supports the kitty keyboard protocol
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+    Some(match key.code {
         KeyCode::Backspace => Key::Backspace,
         KeyCode::Left => Key::Left,
         KeyCode::Right => Key::Right,
@@ -225,12 +498,86 @@ fn map_key(key: crossterm::event::KeyEvent) -> Key {
         KeyCode::Delete => Key::Delete,
         KeyCode::Insert => Key::Insert,
         KeyCode::F(u8) => Key::F(u8),
+        KeyCode::Media(code) => Key::Media(map_media_key(code)),
         KeyCode::Null => Key::Null,
         KeyCode::Esc => Key::Esc,
+        // On Windows, AltGr is reported as Ctrl+Alt. Characters typed with it,
+        // e.g. `@` or `{` on many European layouts, should not be
+        // interpreted as Ctrl shortcuts.
+        KeyCode::Char(char) if is_alt_gr(key.modifiers, char) => Key::Char(char),
         KeyCode::Char(char) if key.modifiers.contains(KeyModifiers::CONTROL) => Key::Ctrl(char),
         KeyCode::Char(char) if key.modifiers.contains(KeyModifiers::ALT) => Key::Alt(char),
         KeyCode::Char(char) => Key::Char(char),
         KeyCode::Enter => Key::Char('\n'),
         KeyCode::Tab => Key::Char('\t'),
+        code => {
+            // Only log every key once, some are reported on every key press
+            // (e.g. modifiers with the kitty keyboard protocol)
+            if unmapped_keys.lock().unwrap().insert(code) {
+                log::debug!("Ignoring key with no zi equivalent: {:?}", code);
+            }
+            return None;
+        }
+    })
+}
+
+#[inline]
+fn map_media_key(code: crossterm::event::MediaKeyCode) -> MediaKey {
+    use crossterm::event::MediaKeyCode;
+    match code {
+        MediaKeyCode::Play => MediaKey::Play,
+        MediaKeyCode::Pause => MediaKey::Pause,
+        MediaKeyCode::PlayPause => MediaKey::PlayPause,
+        MediaKeyCode::Reverse => MediaKey::Reverse,
+        MediaKeyCode::Stop => MediaKey::Stop,
+        MediaKeyCode::FastForward => MediaKey::FastForward,
+        MediaKeyCode::Rewind => MediaKey::Rewind,
+        MediaKeyCode::TrackNext => MediaKey::TrackNext,
+        MediaKeyCode::TrackPrevious => MediaKey::TrackPrevious,
+        MediaKeyCode::Record => MediaKey::Record,
+        MediaKeyCode::LowerVolume => MediaKey::LowerVolume,
+        MediaKeyCode::RaiseVolume => MediaKey::RaiseVolume,
+        MediaKeyCode::MuteVolume => MediaKey::MuteVolume,
+    }
+}
+
+#[inline]
+fn is_alt_gr(modifiers: crossterm::event::KeyModifiers, char: char) -> bool {
+    use crossterm::event::KeyModifiers;
+    cfg!(windows)
+        && modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        && !char.is_ascii_alphanumeric()
+}
+
+#[inline]
+fn map_mouse(event: crossterm::event::MouseEvent) -> MouseEvent {
+    use crossterm::event::{KeyModifiers, MouseEventKind as Kind};
+    let kind = match event.kind {
+        Kind::Down(button) => MouseEventKind::Press(map_mouse_button(button)),
+        Kind::Up(button) => MouseEventKind::Release(map_mouse_button(button)),
+        Kind::Drag(button) => MouseEventKind::Drag(map_mouse_button(button)),
+        Kind::Moved => MouseEventKind::Move,
+        Kind::ScrollUp => MouseEventKind::ScrollUp,
+        Kind::ScrollDown => MouseEventKind::ScrollDown,
+        Kind::ScrollLeft => MouseEventKind::ScrollLeft,
+        Kind::ScrollRight => MouseEventKind::ScrollRight,
+    };
+    MouseEvent {
+        kind,
+        position: Position::new(event.column as usize, event.row as usize),
+        modifiers: Modifiers {
+            shift: event.modifiers.contains(KeyModifiers::SHIFT),
+            ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+            alt: event.modifiers.contains(KeyModifiers::ALT),
+        },
+    }
+}
+
+#[inline]
+fn map_mouse_button(button: crossterm::event::MouseButton) -> MouseButton {
+    match button {
+        crossterm::event::MouseButton::Left => MouseButton::Left,
+        crossterm::event::MouseButton::Right => MouseButton::Right,
+        crossterm::event::MouseButton::Middle => MouseButton::Middle,
     }
 }
@@ -13,7 +13,7 @@ use futures::Stream;
 use std::io;
 use thiserror::Error;
 
-use crate::terminal::{Canvas, Key, Size};
+use crate::terminal::{Canvas, Key, MouseEvent, Size};
 
 /// A trait implemented by backends that draw a [`Canvas`](../terminal/struct.Canvas.html) to
 /// an underlying device (e.g an ANSI terminal).
@@ -51,6 +51,48 @@ pub trait Backend {
     /// cursor and saves the current screen content (i.e. "alternative screen mode") in order
     /// to restore the previous terminal content on exit.
     fn resume(&mut self) -> Result<()>;
+
+    /// Sets the contents of the system clipboard.
+    ///
+    /// The default implementation does nothing, for devices without a
+    /// clipboard.
+    fn set_clipboard(&mut self, _contents: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// Sets the title of the window the application is running in.
+    ///
+    /// The default implementation does nothing, for devices without one.
+    fn set_title(&mut self, _title: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns the optional features supported by the underlying device.
+    ///
+    /// The default implementation reports none of them, content relying on
+    /// them is approximated, e.g. images are drawn with half blocks.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::default()
+    }
+}
+
+/// Optional features of the device a backend draws to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The protocol used to draw the images on a
+    /// [`Canvas`](../terminal/struct.Canvas.html), if any.
+    pub graphics: Option<GraphicsProtocol>,
+}
+
+/// A terminal graphics protocol for drawing raster images.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    /// The [kitty graphics protocol](https://sw.kovidgoyal.net/kitty/graphics-protocol/),
+    /// also implemented by WezTerm, Ghostty and Konsole.
+    Kitty,
+    /// DEC sixel graphics, implemented by xterm (with `-ti vt340`), foot,
+    /// mlterm and others.
+    Sixel,
 }
 
 /// Alias for `Result` with a backend error.
@@ -60,7 +102,15 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug)]
 pub enum Event {
     Key(Key),
+    /// A key held down, repeated by the terminal or the OS. Backends which
+    /// can't tell repeats apart report them as `Key`, as do terminals without
+    /// support for the kitty keyboard protocol.
+    KeyRepeat(Key),
+    Mouse(MouseEvent),
+    Paste(String),
     Resize(Size),
+    /// The terminal window gained (`true`) or lost (`false`) focus.
+    Focus(bool),
 }
 
 /// Backend error
@@ -69,10 +119,6 @@ pub enum Error {
     #[error("{0}")]
     UnknownBackend(String),
 
-    #[cfg(feature = "backend-crossterm")]
-    #[error(transparent)]
-    Crossterm(#[from] crossterm::Error),
-
     #[error(transparent)]
     Io(#[from] io::Error),
 }
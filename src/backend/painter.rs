@@ -3,7 +3,7 @@
 use unicode_width::UnicodeWidthStr;
 
 use super::Result;
-use crate::terminal::{canvas::Textel, Canvas, Position, Size, Style};
+use crate::terminal::{canvas::Textel, Canvas, Hyperlink, ImagePlacement, Position, Size, Style};
 
 pub trait Painter {
     const INITIAL_POSITION: Position;
@@ -21,13 +21,27 @@ pub trait Painter {
 pub enum PaintOperation<'a> {
     WriteContent(&'a str),
     SetStyle(&'a Style),
+    /// Starts a hyperlink to the given URL, or ends the current one if `None`.
+    SetLink(Option<&'a str>),
     MoveTo(Position),
+    /// Removes all images drawn with a graphics protocol. The textels under
+    /// them have been or will be repainted.
+    ClearImages,
+    /// Draws an image with a graphics protocol, if the backend supports one.
+    /// The position of the terminal's cursor is unknown afterwards.
+    DrawImage(&'a ImagePlacement),
 }
 
+/// A position the terminal's cursor is never at, to force a `MoveTo` after
+/// drawing images.
+const UNKNOWN_POSITION: Position = Position::new(usize::MAX, usize::MAX);
+
 pub struct IncrementalPainter {
     screen: Canvas,
     current_position: Position,
     current_style: Style,
+    current_link: Option<Hyperlink>,
+    images: Vec<ImagePlacement>,
 }
 
 impl Painter for IncrementalPainter {
@@ -39,6 +53,8 @@ impl Painter for IncrementalPainter {
             screen: Canvas::new(size),
             current_position: Self::INITIAL_POSITION,
             current_style: Self::INITIAL_STYLE,
+            current_link: None,
+            images: Vec::new(),
         }
     }
 
@@ -52,12 +68,20 @@ impl Painter for IncrementalPainter {
             ref mut screen,
             ref mut current_position,
             ref mut current_style,
+            ref mut current_link,
+            ref mut images,
         } = *self;
         let size = target.size();
-        let force_redraw = size != screen.size();
+        // Repaint everything when the images change, to erase those drawn by
+        // protocols which don't support deleting them
+        let images_changed = images.as_slice() != target.images();
+        let force_redraw = size != screen.size() || images_changed;
         if force_redraw {
             screen.resize(size);
         }
+        if images_changed && !images.is_empty() {
+            paint(PaintOperation::ClearImages)?;
+        }
 
         screen
             .buffer_mut()
@@ -87,6 +111,11 @@ impl Painter for IncrementalPainter {
                         *current_style = new.style;
                     }
 
+                    if new.link != *current_link {
+                        paint(PaintOperation::SetLink(new.link.as_deref()))?;
+                        *current_link = new.link.clone();
+                    }
+
                     let content_width = UnicodeWidthStr::width(&new.grapheme[..]);
                     // eprintln!("Content({:?}) {}", new.grapheme, content_width);
                     paint(PaintOperation::WriteContent(&new.grapheme))?;
@@ -96,7 +125,31 @@ impl Painter for IncrementalPainter {
                 *current = new.clone();
 
                 Ok(())
-            })
+            })?;
+
+        // Don't leave a hyperlink open, it would apply to anything the
+        // terminal writes before the next frame.
+        if current_link.is_some() {
+            paint(PaintOperation::SetLink(None))?;
+            *current_link = None;
+        }
+
+        if images_changed {
+            for placement in target.images() {
+                paint(PaintOperation::DrawImage(placement))?;
+                *current_position = UNKNOWN_POSITION;
+            }
+            *images = target.images().to_vec();
+        }
+
+        if let Some(cursor) = target.cursor() {
+            if cursor != *current_position {
+                paint(PaintOperation::MoveTo(cursor))?;
+                *current_position = cursor;
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -124,6 +177,8 @@ impl Painter for FullPainter {
             ref mut current_style,
         } = *self;
         let size = target.size();
+        let mut current_link = None;
+        paint(PaintOperation::ClearImages)?;
         target
             .buffer()
             .chunks(size.width)
@@ -134,16 +189,35 @@ impl Painter for FullPainter {
                     if let Some(Textel {
                         ref style,
                         ref grapheme,
+                        ref link,
                     }) = textel
                     {
                         if *style != *current_style {
                             paint(PaintOperation::SetStyle(style))?;
                             *current_style = *style;
                         }
+                        if link.as_deref() != current_link {
+                            paint(PaintOperation::SetLink(link.as_deref()))?;
+                            current_link = link.as_deref();
+                        }
                         paint(PaintOperation::WriteContent(grapheme))?;
                     }
                     Ok(())
                 })
-            })
+            })?;
+
+        if current_link.is_some() {
+            paint(PaintOperation::SetLink(None))?;
+        }
+
+        for placement in target.images() {
+            paint(PaintOperation::DrawImage(placement))?;
+        }
+
+        if let Some(cursor) = target.cursor() {
+            paint(PaintOperation::MoveTo(cursor))?;
+        }
+
+        Ok(())
     }
 }
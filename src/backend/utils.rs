@@ -1,4 +1,9 @@
-use std::io::{self, Write};
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use crate::terminal::{Colour, Image, Size};
 
 pub(crate) struct MeteredWriter<WriterT: Write> {
     writer: WriterT,
@@ -33,3 +38,165 @@ impl<WriterT: Write> Write for MeteredWriter<WriterT> {
         self.writer.flush()
     }
 }
+
+/// Encodes bytes as standard, padded base64.
+pub(crate) fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let block = match *chunk {
+            [b0] => u32::from(b0) << 16,
+            [b0, b1] => u32::from(b0) << 16 | u32::from(b1) << 8,
+            [b0, b1, b2] => u32::from(b0) << 16 | u32::from(b1) << 8 | u32::from(b2),
+            _ => unreachable!(),
+        };
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(block >> (18 - 6 * index) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Encodes an image as a kitty graphics protocol escape sequence which
+/// displays it scaled to `cells`, starting at the cursor position.
+pub(crate) fn kitty_encode(image: &Image, cells: Size) -> String {
+    // The protocol limits the payload of every escape sequence to 4096 bytes
+    const CHUNK_SIZE: usize = 4096;
+
+    let payload = base64_encode(&image.to_rgb());
+    let mut encoded = String::with_capacity(payload.len() + 64);
+    let chunks = payload.as_bytes().chunks(CHUNK_SIZE);
+    let num_chunks = chunks.len();
+    for (index, chunk) in chunks.enumerate() {
+        let more = (index + 1 < num_chunks) as u8;
+        if index == 0 {
+            // Transmit and display RGB data without moving the cursor or
+            // receiving a response
+            let _ = write!(
+                encoded,
+                "\x1b_Ga=T,f=24,s={},v={},c={},r={},C=1,q=2,m={};",
+                image.size().width,
+                image.size().height,
+                cells.width,
+                cells.height,
+                more
+            );
+        } else {
+            let _ = write!(encoded, "\x1b_Gm={};", more);
+        }
+        encoded.push_str(std::str::from_utf8(chunk).expect("base64 is ASCII"));
+        encoded.push_str("\x1b\\");
+    }
+    encoded
+}
+
+/// The kitty graphics protocol escape sequence deleting all images.
+pub(crate) const KITTY_DELETE_IMAGES: &str = "\x1b_Ga=d,d=A,q=2\x1b\\";
+
+/// Encodes an image as a sixel escape sequence, drawn at its size in pixels
+/// starting at the cursor position. The colours are quantised to a 6x6x6 cube.
+pub(crate) fn sixel_encode(image: &Image) -> String {
+    let quantise = |colour: &Colour| {
+        let level = |channel: u8| (usize::from(channel) * 5 + 127) / 255;
+        36 * level(colour.red) + 6 * level(colour.green) + level(colour.blue)
+    };
+    let indices: Vec<usize> = image.pixels().iter().map(quantise).collect();
+    let Size { width, height, .. } = image.size();
+
+    let mut encoded = String::new();
+    let _ = write!(encoded, "\x1bPq\"1;1;{};{}", width, height);
+    let mut used = [false; 216];
+    indices.iter().for_each(|&index| used[index] = true);
+    for (index, _) in used.iter().enumerate().filter(|(_, &used)| used) {
+        let percentage = |level: usize| level * 100 / 5;
+        let _ = write!(
+            encoded,
+            "#{};2;{};{};{}",
+            index,
+            percentage(index / 36),
+            percentage(index / 6 % 6),
+            percentage(index % 6)
+        );
+    }
+
+    // Every band of six rows is drawn once per colour, the bits of every sixel
+    // are set for the pixels of that colour
+    for band in (0..height).step_by(6) {
+        let rows = band..(band + 6).min(height);
+        let mut colours: Vec<usize> = rows
+            .clone()
+            .flat_map(|y| indices[y * width..(y + 1) * width].iter().copied())
+            .collect();
+        colours.sort_unstable();
+        colours.dedup();
+        for colour in colours {
+            let _ = write!(encoded, "#{}", colour);
+            let sixels = (0..width).map(|x| {
+                rows.clone()
+                    .filter(|&y| indices[y * width + x] == colour)
+                    .fold(0, |bits, y| bits | 1 << (y - band))
+            });
+            push_sixel_runs(&mut encoded, sixels);
+            encoded.push('$');
+        }
+        encoded.push('-');
+    }
+    encoded.push_str("\x1b\\");
+    encoded
+}
+
+/// Appends sixels using run length encoding for repeated ones.
+fn push_sixel_runs(encoded: &mut String, sixels: impl Iterator<Item = u8>) {
+    let mut push_run = |sixel: u8, length: usize| {
+        let character = char::from(63 + sixel);
+        if length > 3 {
+            let _ = write!(encoded, "!{}{}", length, character);
+        } else {
+            (0..length).for_each(|_| encoded.push(character));
+        }
+    };
+    let mut run: Option<(u8, usize)> = None;
+    for sixel in sixels {
+        run = match run {
+            Some((current, length)) if current == sixel => Some((current, length + 1)),
+            Some((current, length)) => {
+                push_run(current, length);
+                Some((sixel, 1))
+            }
+            None => Some((sixel, 1)),
+        };
+    }
+    if let Some((sixel, length)) = run {
+        push_run(sixel, length);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64_encode, sixel_encode};
+    use crate::terminal::{Colour, Image, Size};
+
+    #[test]
+    fn base64() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn sixel() {
+        // A black pixel over a white one
+        let image = Image::new(Size::new(1, 2), vec![Colour::black(), Colour::white()]);
+        assert_eq!(
+            sixel_encode(&image),
+            "\x1bPq\"1;1;1;2#0;2;0;0;0#215;2;100;100;100#0@$#215A$-\x1b\\"
+        );
+    }
+}
@@ -2,9 +2,12 @@
 
 use smallvec::{smallvec, SmallVec};
 use std::{
+    cell::RefCell,
     cmp,
     collections::hash_map::DefaultHasher,
+    convert::TryFrom,
     hash::{Hash, Hasher},
+    rc::Rc,
 };
 
 use super::{
@@ -27,12 +30,12 @@ pub trait ComponentExt: Component {
     }
 
     fn item_with(flex: FlexBasis, properties: Self::Properties) -> Item {
-        Item {
+        Item::new(
             flex,
-            node: Layout(LayoutNode::Component(DynamicTemplate(Box::new(
+            Layout(LayoutNode::Component(DynamicTemplate(Box::new(
                 ComponentDef::<Self>::new(None, properties),
             )))),
-        }
+        )
     }
 
     fn item_with_key(
@@ -40,12 +43,12 @@ pub trait ComponentExt: Component {
         key: impl Into<ComponentKey>,
         properties: Self::Properties,
     ) -> Item {
-        Item {
+        Item::new(
             flex,
-            node: Layout(LayoutNode::Component(DynamicTemplate(Box::new(
+            Layout(LayoutNode::Component(DynamicTemplate(Box::new(
                 ComponentDef::<Self>::new(Some(key.into()), properties),
             )))),
-        }
+        )
     }
 }
 
@@ -115,8 +118,8 @@ pub fn row_reverse_iter(children: impl IntoIterator<Item = Item>) -> Layout {
 #[inline]
 pub fn container(direction: FlexDirection, children: impl Into<Items>) -> Layout {
     Layout(LayoutNode::Container(Box::new(Container {
-        direction,
         children: children.into().0,
+        ..Container::new(direction)
     })))
 }
 
@@ -128,8 +131,8 @@ pub fn container_iter(
     children: impl IntoIterator<Item = Item>,
 ) -> Layout {
     Layout(LayoutNode::Container(Box::new(Container {
-        direction,
         children: children.into_iter().collect(),
+        ..Container::new(direction)
     })))
 }
 
@@ -169,23 +172,176 @@ pub fn component_with_key_str<ComponentT: Component>(
     ))))
 }
 
+/// Creates a stack of layers: `base` is laid out as usual and every layer is
+/// drawn above it, in order. Use a [`Layer::modal`](struct.Layer.html#method.modal)
+/// layer for dialogs that should capture the input.
+///
+/// ```
+/// # use zi::prelude::*;
+/// # use zi::components::text::{Text, TextProperties};
+/// # use zi::layout::Layer;
+/// let layout = layout::overlay(
+///     Text::with(TextProperties::new().content("Hello")),
+///     [Layer::centered(
+///         Size::new(20, 3),
+///         Text::with(TextProperties::new().content("Are you sure?")),
+///     )
+///     .modal()],
+/// );
+/// ```
+pub fn overlay(base: Layout, layers: impl IntoIterator<Item = Layer>) -> Layout {
+    Layout(LayoutNode::Overlay(Box::new(Overlay {
+        base,
+        layers: layers.into_iter().collect(),
+    })))
+}
+
+/// Constrains the size of a layout. Containers respect the constraints when
+/// sizing their children, elsewhere the layout is given at most its maximum
+/// size, at the origin of its frame, and is clipped or hidden if smaller than
+/// its minimum size.
+///
+/// ```
+/// # use zi::prelude::*;
+/// # use zi::components::text::{Text, TextProperties};
+/// # use zi::layout::{Constraints, Overflow};
+/// let text = |content| Text::with(TextProperties::new().content(content));
+/// let layout = layout::row([
+///     layout::auto(layout::constrained(
+///         Constraints::new()
+///             .min_width(20)
+///             .max_width(40)
+///             .overflow(Overflow::Hide),
+///         text("Sidebar"),
+///     )),
+///     layout::auto(text("Content")),
+/// ]);
+/// ```
+pub fn constrained(constraints: Constraints, layout: Layout) -> Layout {
+    Layout(LayoutNode::Constrained(Box::new(Constrained {
+        node: layout,
+        constraints,
+    })))
+}
+
+/// Aligns a layout of the given size in its frame, horizontally and
+/// vertically. `Align::Stretch` fills the frame along that axis.
+///
+/// ```
+/// # use zi::prelude::*;
+/// # use zi::components::text::{Text, TextProperties};
+/// # use zi::layout::Align;
+/// // A status indicator in the bottom right corner
+/// let layout = layout::align(
+///     Align::End,
+///     Align::End,
+///     Size::new(12, 1),
+///     Text::with(TextProperties::new().content("● connected")),
+/// );
+/// ```
+pub fn align(horizontal: Align, vertical: Align, size: Size, layout: Layout) -> Layout {
+    Layout(LayoutNode::Aligned(Box::new(Aligned {
+        node: layout,
+        horizontal,
+        vertical,
+        size: Some(size),
+    })))
+}
+
+/// Aligns a layout in its frame like [`align`](fn.align.html), sized to fit
+/// its content, e.g. a dialog shrink-wrapped around its text. Components are
+/// sized with [`Component::measure`](../trait.Component.html#method.measure).
+pub fn align_content(horizontal: Align, vertical: Align, layout: Layout) -> Layout {
+    Layout(LayoutNode::Aligned(Box::new(Aligned {
+        node: layout,
+        horizontal,
+        vertical,
+        size: None,
+    })))
+}
+
+/// Centres a layout of the given size in its frame, e.g. a dialog.
+#[inline]
+pub fn centre(size: Size, layout: Layout) -> Layout {
+    align(Align::Centre, Align::Centre, size, layout)
+}
+
+/// Shows the part of a layout of the given size which starts at `offset`,
+/// clipped to the frame of the viewport. The layout is laid out as if there
+/// was enough space to show all of it, at least as large as the frame.
+///
+/// Layers in the layout aren't clipped, they're shown over the viewport.
+/// [`Viewport`](../components/viewport/struct.Viewport.html) keeps track of
+/// the offset and scrolls with the mouse wheel.
+pub fn viewport(size: Size, offset: Position, layout: Layout) -> Layout {
+    Layout(LayoutNode::Viewport(Box::new(Viewport {
+        node: layout,
+        size: Some(size),
+        offset,
+    })))
+}
+
+/// A viewport like [`viewport`](fn.viewport.html), the size of the layout is
+/// the size of its content (see
+/// [`Component::measure`](../trait.Component.html#method.measure)).
+pub fn viewport_content(offset: Position, layout: Layout) -> Layout {
+    Layout(LayoutNode::Viewport(Box::new(Viewport {
+        node: layout,
+        size: None,
+        offset,
+    })))
+}
+
+/// Adds space around a layout, e.g. to keep some text away from a border.
+/// The space is left blank.
+///
+/// ```
+/// # use zi::prelude::*;
+/// # use zi::components::text::{Text, TextProperties};
+/// # use zi::layout::Insets;
+/// let layout = layout::padding(
+///     Insets::symmetric(1, 2),
+///     Text::with(TextProperties::new().content("Hello")),
+/// );
+/// ```
+pub fn padding(padding: Insets, layout: Layout) -> Layout {
+    Layout(LayoutNode::Padded(Box::new(Padded {
+        node: layout,
+        padding,
+    })))
+}
+
 /// Creates an item that will share the available space equally with other
 /// sibling items with `FlexBasis::auto`.
 #[inline]
 pub fn auto(layout: Layout) -> Item {
-    Item {
-        node: layout,
-        flex: FlexBasis::Auto,
-    }
+    Item::new(FlexBasis::Auto, layout)
+}
+
+/// Creates an item sized to its content, as measured by its components (see
+/// [`Component::measure`](../trait.Component.html#method.measure)).
+#[inline]
+pub fn content(layout: Layout) -> Item {
+    Item::new(FlexBasis::Content, layout)
 }
 
 /// Creates an item that will have a fixed size.
 #[inline]
 pub fn fixed(size: usize, layout: Layout) -> Item {
-    Item {
-        node: layout,
-        flex: FlexBasis::Fixed(size),
-    }
+    Item::new(FlexBasis::Fixed(size), layout)
+}
+
+/// Creates an item that will take a percentage of the size of its container.
+#[inline]
+pub fn percent(percent: usize, layout: Layout) -> Item {
+    Item::new(FlexBasis::Percent(percent), layout)
+}
+
+/// Creates an item that will take a fraction of the size of its container,
+/// e.g. `ratio(1, 3, layout)` takes a third.
+#[inline]
+pub fn ratio(numerator: usize, denominator: usize, layout: Layout) -> Item {
+    Item::new(FlexBasis::Ratio(numerator, denominator), layout)
 }
 
 /// Wrapper type for user defined component identity.
@@ -212,6 +368,13 @@ impl From<&str> for ComponentKey {
 ///   - container
 ///   - component
 ///   - canvas
+///   - overlay
+///   - grid
+///   - constrained layout
+///   - padded layout
+///   - aligned layout
+///   - viewport
+///   - responsive layout
 #[derive(Clone)]
 pub struct Layout(pub(crate) LayoutNode);
 
@@ -220,6 +383,13 @@ pub(crate) enum LayoutNode {
     Container(Box<Container>),
     Component(DynamicTemplate),
     Canvas(Canvas),
+    Overlay(Box<Overlay>),
+    Grid(Box<Grid>),
+    Constrained(Box<Constrained>),
+    Padded(Box<Padded>),
+    Aligned(Box<Aligned>),
+    Viewport(Box<Viewport>),
+    Responsive(Box<Responsive>),
 }
 
 impl LayoutNode {
@@ -229,27 +399,39 @@ impl LayoutNode {
         position_hash: u64,
         view_fn: &mut impl FnMut(LaidComponent),
         draw_fn: &mut impl FnMut(LaidCanvas),
+        overlay_fn: &mut impl FnMut(LaidOverlay),
     ) {
         let mut hasher = DefaultHasher::new();
         hasher.write_u64(position_hash);
         match self {
             Self::Container(container) => {
                 hasher.write_u64(Self::CONTAINER_HASH);
+                let frames = container
+                    .frames
+                    .get_or_insert_with(frame, || splits(frame, container));
                 if container.direction.is_reversed() {
-                    let frames: SmallVec<[_; ARRAY_SIZE]> =
-                        splits_iter(frame, container.direction, container.children.iter().rev())
-                            .collect();
-                    for (child, frame) in container.children.iter_mut().rev().zip(frames) {
+                    for (child, frame) in container.children.iter_mut().zip(frames).rev() {
+                        let frame = match frame {
+                            Some(frame) => frame,
+                            None => continue,
+                        };
                         // hasher.write_u64(Self::CONTAINER_ITEM_HASH);
-                        child.node.0.crawl(frame, hasher.finish(), view_fn, draw_fn);
+                        child
+                            .node
+                            .0
+                            .crawl(frame, hasher.finish(), view_fn, draw_fn, overlay_fn);
                     }
                 } else {
-                    let frames: SmallVec<[_; ARRAY_SIZE]> =
-                        splits_iter(frame, container.direction, container.children.iter())
-                            .collect();
                     for (child, frame) in container.children.iter_mut().zip(frames) {
+                        let frame = match frame {
+                            Some(frame) => frame,
+                            None => continue,
+                        };
                         // hasher.write_u64(Self::CONTAINER_ITEM_HASH);
-                        child.node.0.crawl(frame, hasher.finish(), view_fn, draw_fn);
+                        child
+                            .node
+                            .0
+                            .crawl(frame, hasher.finish(), view_fn, draw_fn, overlay_fn);
                     }
                 }
             }
@@ -262,18 +444,212 @@ impl LayoutNode {
                     frame,
                     position_hash: hasher.finish(),
                     template,
+                    clip: None,
                 });
             }
             Self::Canvas(canvas) => {
-                draw_fn(LaidCanvas { frame, canvas });
+                draw_fn(LaidCanvas {
+                    frame,
+                    canvas,
+                    clip: None,
+                });
+            }
+            Self::Overlay(overlay) => {
+                hasher.write_u64(Self::OVERLAY_HASH);
+                overlay
+                    .base
+                    .0
+                    .crawl(frame, hasher.finish(), view_fn, draw_fn, overlay_fn);
+                // Layers are drawn once everything below them was, so they
+                // are handed over rather than crawled here.
+                for (index, layer) in overlay.layers.iter().enumerate() {
+                    let mut hasher = hasher.clone();
+                    hasher.write_usize(index);
+                    overlay_fn(LaidOverlay {
+                        frame: layer.frame(frame),
+                        position_hash: hasher.finish(),
+                        layout: layer.node.clone(),
+                        modal: layer.modal,
+                        z_index: layer.z_index,
+                        absolute: matches!(layer.placement, LayerPlacement::Absolute(_)),
+                    });
+                }
+            }
+            Self::Constrained(constrained) => {
+                let Constraints { min, max, overflow } = constrained.constraints;
+                if overflow == Overflow::Hide
+                    && (frame.size.width < min.width || frame.size.height < min.height)
+                {
+                    return;
+                }
+                let frame = Rect::new(frame.origin, frame.size.min(max));
+                // Constraints don't change the identity of the components
+                constrained
+                    .node
+                    .0
+                    .crawl(frame, position_hash, view_fn, draw_fn, overlay_fn);
+            }
+            Self::Padded(padded) => {
+                padded.node.0.crawl(
+                    padded.padding.shrink(frame),
+                    position_hash,
+                    view_fn,
+                    draw_fn,
+                    overlay_fn,
+                );
+            }
+            Self::Aligned(aligned) => {
+                let frame = aligned.frame(frame);
+                aligned
+                    .node
+                    .0
+                    .crawl(frame, position_hash, view_fn, draw_fn, overlay_fn);
+            }
+            Self::Viewport(viewport) => {
+                // The content is laid out in its own coordinates, which the
+                // clip maps to those of the viewport's frame
+                let (content_frame, clip) = viewport.layout(frame);
+                let mut view_fn: &mut dyn FnMut(LaidComponent) = &mut |mut component| {
+                    component.clip = Clip::nest(component.clip, Some(clip));
+                    view_fn(component);
+                };
+                let mut draw_fn: &mut dyn FnMut(LaidCanvas) = &mut |mut canvas| {
+                    canvas.clip = Clip::nest(canvas.clip, Some(clip));
+                    draw_fn(canvas);
+                };
+                // Layers are drawn over everything else, clipping them would
+                // hide what sticks out of the viewport. Those positioned
+                // relative to their parent are mapped to the visible part of
+                // it instead, absolute ones are left where they are
+                let mut overlay_fn: &mut dyn FnMut(LaidOverlay) = &mut |mut overlay| {
+                    if !overlay.absolute {
+                        overlay.frame = clip.visible(overlay.frame);
+                    }
+                    overlay_fn(overlay);
+                };
+                viewport.node.0.crawl(
+                    content_frame,
+                    position_hash,
+                    &mut view_fn,
+                    &mut draw_fn,
+                    &mut overlay_fn,
+                );
+            }
+            Self::Responsive(responsive) => {
+                // Which alternative is shown doesn't change the identity of
+                // the components, those in all of them keep their state
+                responsive.shown(frame.size).0.crawl(
+                    frame,
+                    position_hash,
+                    view_fn,
+                    draw_fn,
+                    overlay_fn,
+                );
+            }
+            Self::Grid(grid) => {
+                hasher.write_u64(Self::GRID_HASH);
+                let frames = grid.cache.get_or_insert_with(frame, || grid.frames(frame));
+                for (cell, frame) in grid.cells.iter_mut().zip(frames) {
+                    if let Some(frame) = frame {
+                        cell.node
+                            .0
+                            .crawl(frame, hasher.finish(), view_fn, draw_fn, overlay_fn);
+                    }
+                }
             }
         };
     }
 
+    /// The size needed to show the content of the node in the space
+    /// available, as measured by its components (see
+    /// [`Component::measure`](../trait.Component.html#method.measure)).
+    pub(crate) fn content_size(&self, available: Size) -> Size {
+        match self {
+            Self::Container(container) => {
+                let direction = container.direction;
+                let (main, cross) =
+                    container
+                        .children
+                        .iter()
+                        .fold((0, 0), |(main, cross), child| {
+                            let content_size = child.content_size(available);
+                            let margin = child.margin.size();
+                            let child_main = match child.flex {
+                                FlexBasis::Fixed(size) => size,
+                                FlexBasis::Auto
+                                | FlexBasis::Content
+                                | FlexBasis::Percent(_)
+                                | FlexBasis::Ratio(..) => direction.dimension(content_size),
+                            };
+                            let child_cross = child
+                                .cross_size
+                                .unwrap_or_else(|| direction.cross_dimension(content_size));
+                            (
+                                main + cmp::max(child_main, child.min_size)
+                                    + direction.dimension(margin),
+                                cmp::max(cross, child_cross + direction.cross_dimension(margin)),
+                            )
+                        });
+                direction.size(main, cross)
+            }
+            Self::Component(template) => template.measure(available).unwrap_or_else(Size::zero),
+            Self::Canvas(canvas) => canvas.size(),
+            Self::Overlay(overlay) => overlay.base.0.content_size(available),
+            Self::Grid(grid) => grid.content_size(available),
+            Self::Constrained(constrained) => {
+                let Constraints { min, max, .. } = constrained.constraints;
+                constrained
+                    .node
+                    .0
+                    .content_size(available.min(max))
+                    .clamp(min, max)
+            }
+            Self::Padded(padded) => {
+                let padding = padded.padding;
+                let inner = padding.shrink(Rect::from_size(available)).size;
+                padded.node.0.content_size(inner) + padding.size()
+            }
+            Self::Aligned(aligned) => aligned
+                .size
+                .unwrap_or_else(|| aligned.node.0.content_size(available)),
+            Self::Viewport(viewport) => viewport
+                .size
+                .unwrap_or_else(|| viewport.node.0.content_size(available)),
+            Self::Responsive(responsive) => {
+                let (_, layout) = responsive.select(available);
+                layout.0.content_size(available)
+            }
+        }
+    }
+
+    /// The constraints on the size of the node, unbounded unless it's a
+    /// [`constrained`](fn.constrained.html) layout.
+    pub(crate) fn constraints(&self) -> Constraints {
+        match self {
+            Self::Constrained(constrained) => constrained.constraints,
+            Self::Padded(padded) => {
+                let constraints = padded.node.0.constraints();
+                let padding = padded.padding.size();
+                Constraints {
+                    min: constraints.min + padding,
+                    max: Size::new(
+                        constraints.max.width.saturating_add(padding.width),
+                        constraints.max.height.saturating_add(padding.height),
+                    ),
+                    overflow: constraints.overflow,
+                }
+            }
+            Self::Aligned(aligned) => aligned.node.0.constraints(),
+            _ => Constraints::default(),
+        }
+    }
+
     // Some random number to initialise the hash (0 would also do, but hopefully
     // this is less pathological if a simpler hash the `DefaultHasher` was
     // used).
     const CONTAINER_HASH: u64 = 0x5aa2d5349a05cde8;
+    const OVERLAY_HASH: u64 = 0x1c7e3f0b9d5a2e64;
+    const GRID_HASH: u64 = 0x6b3d91f27ae05c13;
 }
 
 impl From<Canvas> for Layout {
@@ -287,6 +663,10 @@ impl From<Canvas> for Layout {
 pub struct Container {
     children: SmallVec<[Item; ARRAY_SIZE]>,
     direction: FlexDirection,
+    justify: Justify,
+    align: Align,
+    overflow: Overflow,
+    frames: FrameCache,
 }
 
 impl Container {
@@ -297,6 +677,10 @@ impl Container {
         Self {
             children: SmallVec::new(),
             direction,
+            justify: Justify::Start,
+            align: Align::Stretch,
+            overflow: Overflow::Clip,
+            frames: FrameCache::default(),
         }
     }
 
@@ -335,6 +719,30 @@ impl Container {
         self.children.push(item);
         self
     }
+
+    /// Sets how the space left along the main axis is distributed, when the
+    /// children don't grow to fill it. Defaults to `Justify::Start`.
+    #[inline]
+    pub fn justify(&mut self, justify: Justify) -> &mut Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Sets how children with a cross size are aligned along the cross axis.
+    /// Defaults to `Align::Stretch`.
+    #[inline]
+    pub fn align(&mut self, align: Align) -> &mut Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets what happens to the children when their minimum sizes don't fit
+    /// in the container. Defaults to `Overflow::Clip`.
+    #[inline]
+    pub fn overflow(&mut self, overflow: Overflow) -> &mut Self {
+        self.overflow = overflow;
+        self
+    }
 }
 
 impl From<Container> for Layout {
@@ -343,18 +751,974 @@ impl From<Container> for Layout {
     }
 }
 
+/// What happens to a layout which doesn't fit in its frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// It's laid out in the space available, truncated.
+    #[default]
+    Clip,
+    /// It isn't shown at all, e.g. a sidebar on a narrow screen. Components
+    /// inside are removed, they are created again once there's enough space.
+    Hide,
+}
+
+/// The minimum and maximum size of a [`constrained`](fn.constrained.html)
+/// layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Constraints {
+    pub min: Size,
+    pub max: Size,
+    /// What happens when the frame is smaller than the minimum size.
+    pub overflow: Overflow,
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Self {
+            min: Size::zero(),
+            max: Size::new(usize::MAX, usize::MAX),
+            overflow: Overflow::Clip,
+        }
+    }
+}
+
+impl Constraints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn min_width(mut self, width: usize) -> Self {
+        self.min.width = width;
+        self
+    }
+
+    #[inline]
+    pub fn min_height(mut self, height: usize) -> Self {
+        self.min.height = height;
+        self
+    }
+
+    #[inline]
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max.width = width;
+        self
+    }
+
+    #[inline]
+    pub fn max_height(mut self, height: usize) -> Self {
+        self.max.height = height;
+        self
+    }
+
+    #[inline]
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+/// Space around a layout, in cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Insets {
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+    pub left: usize,
+}
+
+impl Insets {
+    pub fn new(top: usize, right: usize, bottom: usize, left: usize) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// The same space on every side.
+    pub fn all(size: usize) -> Self {
+        Self::new(size, size, size, size)
+    }
+
+    /// `vertical` space above and below, `horizontal` space left and right.
+    pub fn symmetric(vertical: usize, horizontal: usize) -> Self {
+        Self::new(vertical, horizontal, vertical, horizontal)
+    }
+
+    /// The total space taken horizontally and vertically.
+    #[inline]
+    pub fn size(&self) -> Size {
+        Size::new(self.left + self.right, self.top + self.bottom)
+    }
+
+    /// Returns the part of the frame inside the insets, empty if they don't
+    /// fit.
+    pub fn shrink(&self, frame: Rect) -> Rect {
+        let left = cmp::min(self.left, frame.size.width);
+        let top = cmp::min(self.top, frame.size.height);
+        Rect::new(
+            Position::new(frame.origin.x + left, frame.origin.y + top),
+            Size::new(
+                frame.size.width.saturating_sub(self.left + self.right),
+                frame.size.height.saturating_sub(self.top + self.bottom),
+            ),
+        )
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Viewport {
+    node: Layout,
+    size: Option<Size>,
+    offset: Position,
+}
+
+impl Viewport {
+    /// The frame of the content, in its own coordinates, and how it is shown
+    /// in the frame of the viewport.
+    fn layout(&self, frame: Rect) -> (Rect, Clip) {
+        let content_size = viewport_content_size(self.size, &self.node, frame.size);
+        let offset = Position::new(
+            cmp::min(self.offset.x, content_size.width - frame.size.width),
+            cmp::min(self.offset.y, content_size.height - frame.size.height),
+        );
+        let clip = Clip {
+            region: frame,
+            translation: (
+                frame.origin.x as isize - offset.x as isize,
+                frame.origin.y as isize - offset.y as isize,
+            ),
+        };
+        (Rect::from_size(content_size), clip)
+    }
+}
+
+/// The size a layout is laid out in when shown in a viewport with the given
+/// frame size.
+pub(crate) fn viewport_content_size(size: Option<Size>, layout: &Layout, frame: Size) -> Size {
+    size.unwrap_or_else(|| layout.0.content_size(frame))
+        .max(frame)
+}
+
+/// How a component or canvas in a viewport is shown: its frame is in the
+/// coordinates of the viewport's content, which are translated and clipped
+/// to the viewport's frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Clip {
+    /// The visible region, in the coordinates the viewport was laid out in.
+    region: Rect,
+    /// Added to the coordinates of the content to get the coordinates the
+    /// viewport was laid out in.
+    translation: (isize, isize),
+}
+
+impl Clip {
+    /// Combines the clip of a nested viewport with the one it is shown in.
+    pub(crate) fn nest(inner: Option<Clip>, outer: Option<Clip>) -> Option<Clip> {
+        match (inner, outer) {
+            (inner, None) => inner,
+            (None, outer) => outer,
+            (Some(inner), Some(outer)) => Some(Clip {
+                region: outer.visible(inner.region),
+                translation: (
+                    inner.translation.0 + outer.translation.0,
+                    inner.translation.1 + outer.translation.1,
+                ),
+            }),
+        }
+    }
+
+    /// The visible part of a frame in the coordinates of the content, in the
+    /// coordinates the viewport was laid out in. Empty if it isn't visible.
+    pub(crate) fn visible(&self, frame: Rect) -> Rect {
+        let span = |start: usize, length: usize, translation: isize, region: (usize, usize)| {
+            let start = start as isize + translation;
+            let clipped_start = cmp::max(start, region.0 as isize);
+            let end = cmp::min(start + length as isize, (region.0 + region.1) as isize);
+            (
+                clipped_start as usize,
+                cmp::max(end - clipped_start, 0) as usize,
+            )
+        };
+        let (x, width) = span(
+            frame.origin.x,
+            frame.size.width,
+            self.translation.0,
+            (self.region.origin.x, self.region.size.width),
+        );
+        let (y, height) = span(
+            frame.origin.y,
+            frame.size.height,
+            self.translation.1,
+            (self.region.origin.y, self.region.size.height),
+        );
+        Rect::new(Position::new(x, y), Size::new(width, height))
+    }
+
+    /// The position in the coordinates of the content, if it is visible.
+    pub(crate) fn content_position(&self, position: Position) -> Option<Position> {
+        if !self.region.contains(position) {
+            return None;
+        }
+        let (x, y) = self.content_point(position);
+        Some(Position::new(
+            usize::try_from(x).ok()?,
+            usize::try_from(y).ok()?,
+        ))
+    }
+
+    /// The position in the coordinates of the content, visible or not.
+    pub(crate) fn content_point(&self, position: Position) -> (isize, isize) {
+        (
+            position.x as isize - self.translation.0,
+            position.y as isize - self.translation.1,
+        )
+    }
+
+    /// The position of a point of the content, if it is visible.
+    pub(crate) fn visible_position(&self, position: Position) -> Option<Position> {
+        let position = Position::new(
+            usize::try_from(position.x as isize + self.translation.0).ok()?,
+            usize::try_from(position.y as isize + self.translation.1).ok()?,
+        );
+        Some(position).filter(|position| self.region.contains(*position))
+    }
+
+    /// Draws the visible part of a canvas with the given frame.
+    pub(crate) fn draw(&self, screen: &mut Canvas, canvas: &Canvas, frame: Rect) {
+        let visible = self.visible(frame);
+        if visible.is_empty() {
+            return;
+        }
+        let origin = Position::new(
+            (visible.origin.x as isize - self.translation.0) as usize - frame.origin.x,
+            (visible.origin.y as isize - self.translation.1) as usize - frame.origin.y,
+        );
+        screen.copy_region(&canvas.crop(Rect::new(origin, visible.size)), visible);
+    }
+}
+
+type Frames = SmallVec<[Option<Rect>; ARRAY_SIZE]>;
+
+/// The frames of the children of a container or a grid, as computed for the
+/// last frame it was laid out in.
+///
+/// A layout can't be changed once built and the `App` keeps the layout of a
+/// component until it is rendered again, so the frames only need to be
+/// recomputed when it is laid out in a different frame, e.g. when the
+/// terminal is resized. Clones of a layout, like the ones made for layers,
+/// share the cache.
+#[derive(Clone, Default)]
+struct FrameCache(Rc<RefCell<Option<(Rect, Frames)>>>);
+
+impl FrameCache {
+    fn get_or_insert_with(&self, frame: Rect, compute: impl FnOnce() -> Frames) -> Frames {
+        let mut cache = self.0.borrow_mut();
+        match &*cache {
+            Some((cached_frame, frames)) if *cached_frame == frame => frames.clone(),
+            _ => cache.insert((frame, compute())).1.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Aligned {
+    node: Layout,
+    horizontal: Align,
+    vertical: Align,
+    size: Option<Size>,
+}
+
+impl Aligned {
+    fn frame(&self, frame: Rect) -> Rect {
+        let size = self
+            .size
+            .unwrap_or_else(|| self.node.0.content_size(frame.size));
+        let (x, width) = align_span(self.horizontal, frame.size.width, size.width);
+        let (y, height) = align_span(self.vertical, frame.size.height, size.height);
+        Rect::new(
+            Position::new(frame.origin.x + x, frame.origin.y + y),
+            Size::new(width, height),
+        )
+    }
+}
+
+/// The offset and size of a span aligned in the available space.
+fn align_span(align: Align, available: usize, size: usize) -> (usize, usize) {
+    let size = cmp::min(size, available);
+    match align {
+        Align::Stretch => (0, available),
+        Align::Start => (0, size),
+        Align::Centre => ((available - size) / 2, size),
+        Align::End => (available - size, size),
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Padded {
+    node: Layout,
+    padding: Insets,
+}
+
+#[derive(Clone)]
+pub(crate) struct Constrained {
+    node: Layout,
+    constraints: Constraints,
+}
+
+/// The size of a row or a column of a [`Grid`](struct.Grid.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Track {
+    Fixed(usize),
+    /// A percentage of the size of the grid.
+    Percent(usize),
+    /// A share of the space left by the other tracks, in proportion to the
+    /// other fractional tracks, like `fr` in CSS.
+    Fraction(usize),
+    /// Fits the content of the cells in the track, only the cells which
+    /// don't span other tracks are taken into account.
+    Auto,
+}
+
+/// A child of a [`Grid`](struct.Grid.html) and the tracks it covers.
+#[derive(Clone)]
+struct GridCell {
+    node: Layout,
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+}
+
+/// A layout container placing its children in the cells of a grid, like CSS
+/// grid, e.g. for dashboards.
+///
+/// ```
+/// # use zi::prelude::*;
+/// # use zi::components::text::{Text, TextProperties};
+/// # use zi::layout::{Grid, Track};
+/// let text = |content| Text::with(TextProperties::new().content(content));
+/// let mut grid = Grid::new(
+///     [Track::Fixed(20), Track::Fraction(2), Track::Fraction(1)],
+///     [Track::Fixed(1), Track::Fraction(1), Track::Fraction(1)],
+/// );
+/// grid.gap(1, 0)
+///     .add_span(0, 0, 1, 3, text("Header"))
+///     .add_span(1, 0, 2, 1, text("Sidebar"))
+///     .add(1, 1, text("Chart"))
+///     .add(1, 2, text("Stats"))
+///     .add_span(2, 1, 1, 2, text("Logs"));
+/// let layout: Layout = grid.into();
+/// ```
+#[derive(Clone)]
+pub struct Grid {
+    columns: SmallVec<[Track; ARRAY_SIZE]>,
+    rows: SmallVec<[Track; ARRAY_SIZE]>,
+    column_gap: usize,
+    row_gap: usize,
+    cells: Vec<GridCell>,
+    cache: FrameCache,
+}
+
+impl Grid {
+    pub fn new(
+        columns: impl IntoIterator<Item = Track>,
+        rows: impl IntoIterator<Item = Track>,
+    ) -> Self {
+        Self {
+            columns: columns.into_iter().collect(),
+            rows: rows.into_iter().collect(),
+            column_gap: 0,
+            row_gap: 0,
+            cells: Vec::new(),
+            cache: FrameCache::default(),
+        }
+    }
+
+    /// Sets the space between columns and between rows.
+    #[inline]
+    pub fn gap(&mut self, columns: usize, rows: usize) -> &mut Self {
+        self.column_gap = columns;
+        self.row_gap = rows;
+        self
+    }
+
+    /// Places a child in the cell at the given row and column, counting from
+    /// zero.
+    #[inline]
+    pub fn add(&mut self, row: usize, column: usize, layout: Layout) -> &mut Self {
+        self.add_span(row, column, 1, 1, layout)
+    }
+
+    /// Places a child covering `row_span` rows and `column_span` columns,
+    /// starting with the cell at the given row and column. Children outside
+    /// of the grid aren't shown, those partially outside are clipped to it.
+    pub fn add_span(
+        &mut self,
+        row: usize,
+        column: usize,
+        row_span: usize,
+        column_span: usize,
+        layout: Layout,
+    ) -> &mut Self {
+        self.cells.push(GridCell {
+            node: layout,
+            row,
+            column,
+            row_span: cmp::max(row_span, 1),
+            column_span: cmp::max(column_span, 1),
+        });
+        self
+    }
+
+    /// The frames of the cells, `None` for those outside the grid.
+    fn frames(&self, frame: Rect) -> Frames {
+        let columns = track_sizes(frame.size.width, &self.columns, self.column_gap, |column| {
+            self.auto_size(
+                column,
+                frame.size,
+                |cell| (cell.column, cell.column_span),
+                |size| size.width,
+            )
+        });
+        let rows = track_sizes(frame.size.height, &self.rows, self.row_gap, |row| {
+            self.auto_size(
+                row,
+                frame.size,
+                |cell| (cell.row, cell.row_span),
+                |size| size.height,
+            )
+        });
+        let span = |tracks: &[(usize, usize)], start: usize, span: usize| {
+            let (offset, _) = *tracks.get(start)?;
+            let end = cmp::min(start + span, tracks.len()).saturating_sub(1);
+            let (end_offset, end_size) = tracks[end];
+            Some((offset, end_offset + end_size - offset))
+        };
+        self.cells
+            .iter()
+            .map(|cell| {
+                let (x, width) = span(&columns, cell.column, cell.column_span)?;
+                let (y, height) = span(&rows, cell.row, cell.row_span)?;
+                Some(Rect::new(
+                    Position::new(frame.origin.x + x, frame.origin.y + y),
+                    Size::new(width, height),
+                ))
+            })
+            .collect()
+    }
+
+    /// The size of an auto track, the largest content of the cells only in it.
+    fn auto_size(
+        &self,
+        track: usize,
+        available: Size,
+        cell_tracks: impl Fn(&GridCell) -> (usize, usize),
+        dimension: impl Fn(Size) -> usize,
+    ) -> usize {
+        self.cells
+            .iter()
+            .filter(|cell| cell_tracks(cell) == (track, 1))
+            .map(|cell| dimension(cell.node.0.content_size(available)))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn content_size(&self, available: Size) -> Size {
+        let total = |tracks: &[Track], gap: usize, auto_size: &dyn Fn(usize) -> usize| {
+            tracks
+                .iter()
+                .enumerate()
+                .map(|(index, track)| match track {
+                    Track::Fixed(size) => *size,
+                    Track::Percent(_) | Track::Fraction(_) | Track::Auto => auto_size(index),
+                })
+                .sum::<usize>()
+                + gap * tracks.len().saturating_sub(1)
+        };
+        Size::new(
+            total(&self.columns, self.column_gap, &|column| {
+                self.auto_size(
+                    column,
+                    available,
+                    |cell| (cell.column, cell.column_span),
+                    |size| size.width,
+                )
+            }),
+            total(&self.rows, self.row_gap, &|row| {
+                self.auto_size(
+                    row,
+                    available,
+                    |cell| (cell.row, cell.row_span),
+                    |size| size.height,
+                )
+            }),
+        )
+    }
+}
+
+impl From<Grid> for Layout {
+    fn from(grid: Grid) -> Self {
+        Layout(LayoutNode::Grid(Box::new(grid)))
+    }
+}
+
+/// Chooses between alternative layouts depending on the size of its frame,
+/// e.g. to hide a sidebar in narrow terminals. The first alternative whose
+/// minimum size fits in the frame is shown, in the order they were added,
+/// otherwise the fallback. The choice is made again whenever the frame
+/// changes size.
+///
+/// ```
+/// # use zi::prelude::*;
+/// # use zi::components::text::{Text, TextProperties};
+/// # use zi::layout::Responsive;
+/// let text = |content| Text::with(TextProperties::new().content(content));
+/// let mut responsive = Responsive::new(text("Content"));
+/// responsive.min_width(
+///     80,
+///     layout::row([layout::fixed(20, text("Sidebar")), layout::auto(text("Content"))]),
+/// );
+/// let layout: Layout = responsive.into();
+/// ```
+#[derive(Clone)]
+pub struct Responsive {
+    alternatives: SmallVec<[(Size, Layout); 2]>,
+    fallback: Layout,
+    /// The alternative last shown, by index (`None` for the fallback), and a
+    /// copy of it to crawl. Layouts hand their components' properties over
+    /// when crawled, the alternatives are kept intact so that they can be
+    /// shown again.
+    shown: Option<(Option<usize>, Layout)>,
+}
+
+impl Responsive {
+    /// Creates a layout showing `fallback` until alternatives are added.
+    pub fn new(fallback: Layout) -> Self {
+        Self {
+            alternatives: SmallVec::new(),
+            fallback,
+            shown: None,
+        }
+    }
+
+    /// Adds an alternative shown when the frame is at least `width` wide.
+    #[inline]
+    pub fn min_width(&mut self, width: usize, layout: Layout) -> &mut Self {
+        self.min_size(Size::new(width, 0), layout)
+    }
+
+    /// Adds an alternative shown when the frame is at least `height` high.
+    #[inline]
+    pub fn min_height(&mut self, height: usize, layout: Layout) -> &mut Self {
+        self.min_size(Size::new(0, height), layout)
+    }
+
+    /// Adds an alternative shown when the frame is at least as large as
+    /// `size` in both dimensions.
+    #[inline]
+    pub fn min_size(&mut self, size: Size, layout: Layout) -> &mut Self {
+        self.alternatives.push((size, layout));
+        self.shown = None;
+        self
+    }
+
+    /// The index of the alternative for the given size and its layout.
+    fn select(&self, size: Size) -> (Option<usize>, &Layout) {
+        self.alternatives
+            .iter()
+            .position(|(min_size, _)| {
+                size.width >= min_size.width && size.height >= min_size.height
+            })
+            .map_or((None, &self.fallback), |index| {
+                (Some(index), &self.alternatives[index].1)
+            })
+    }
+
+    fn shown(&mut self, size: Size) -> &mut Layout {
+        let (index, layout) = self.select(size);
+        if !matches!(self.shown, Some((shown_index, _)) if shown_index == index) {
+            self.shown = Some((index, layout.clone()));
+        }
+        let (_, shown) = self.shown.as_mut().expect("An alternative is shown");
+        shown
+    }
+}
+
+impl From<Responsive> for Layout {
+    fn from(responsive: Responsive) -> Self {
+        Layout(LayoutNode::Responsive(Box::new(responsive)))
+    }
+}
+
+/// Resolves the offsets and sizes of the tracks of a grid along one axis.
+/// Tracks which don't fit are truncated in order.
+fn track_sizes(
+    total_size: usize,
+    tracks: &[Track],
+    gap: usize,
+    auto_size: impl Fn(usize) -> usize,
+) -> SmallVec<[(usize, usize); ARRAY_SIZE]> {
+    let mut sizes: SmallVec<[usize; ARRAY_SIZE]> = tracks
+        .iter()
+        .enumerate()
+        .map(|(index, track)| match track {
+            Track::Fixed(size) => *size,
+            Track::Percent(percent) => total_size * percent / 100,
+            Track::Fraction(_) => 0,
+            Track::Auto => auto_size(index),
+        })
+        .collect();
+    let used = sizes.iter().sum::<usize>() + gap * tracks.len().saturating_sub(1);
+    let shares = distribute(
+        total_size.saturating_sub(used),
+        tracks.iter().map(|track| match track {
+            Track::Fraction(fraction) => *fraction,
+            Track::Fixed(_) | Track::Percent(_) | Track::Auto => 0,
+        }),
+    );
+
+    let mut offset = 0;
+    sizes
+        .iter_mut()
+        .zip(shares)
+        .map(|(size, share)| {
+            let track_offset = cmp::min(offset, total_size);
+            let size = cmp::min(*size + share, total_size - track_offset);
+            offset = track_offset + size + gap;
+            (track_offset, size)
+        })
+        .collect()
+}
+
+/// A layout drawn above a base layout, see [`overlay`](fn.overlay.html).
+#[derive(Clone)]
+pub(crate) struct Overlay {
+    base: Layout,
+    layers: SmallVec<[Layer; 1]>,
+}
+
+/// A layer of an [`overlay`](fn.overlay.html).
+#[derive(Clone)]
+pub struct Layer {
+    node: Layout,
+    placement: LayerPlacement,
+    modal: bool,
+    z_index: i32,
+}
+
+#[derive(Clone, Copy)]
+enum LayerPlacement {
+    Fill,
+    Centered(Size),
+    At(Rect),
+    Absolute(Rect),
+}
+
+impl Layer {
+    /// Creates a layer covering the whole frame of the overlay.
+    pub fn new(layout: Layout) -> Self {
+        Self::with_placement(LayerPlacement::Fill, layout)
+    }
+
+    /// Creates a layer of the given size, centered in the frame of the
+    /// overlay. The size is clamped to the frame.
+    pub fn centered(size: Size, layout: Layout) -> Self {
+        Self::with_placement(LayerPlacement::Centered(size), layout)
+    }
+
+    /// Creates a layer at the given position and size relative to the origin
+    /// of the overlay. Unlike the other layers, it isn't clamped to the frame
+    /// of the overlay, e.g. the list of a dropdown can be drawn below it.
+    pub fn at(frame: Rect, layout: Layout) -> Self {
+        Self::with_placement(LayerPlacement::At(frame), layout)
+    }
+
+    /// Creates a layer at the given position and size on the screen,
+    /// wherever the overlay is, e.g. for a tooltip next to the mouse pointer.
+    ///
+    /// ```
+    /// # use zi::prelude::*;
+    /// # use zi::components::text::{Text, TextProperties};
+    /// # use zi::layout::Layer;
+    /// let text = |content| Text::with(TextProperties::new().content(content));
+    /// let layout = layout::overlay(
+    ///     text("Editor"),
+    ///     [
+    ///         Layer::absolute(
+    ///             Rect::new(Position::new(12, 4), Size::new(30, 8)),
+    ///             text("Completions"),
+    ///         )
+    ///         .z_index(1),
+    ///         Layer::absolute(Rect::new(Position::new(44, 4), Size::new(20, 1)), text("Docs")),
+    ///     ],
+    /// );
+    /// ```
+    pub fn absolute(frame: Rect, layout: Layout) -> Self {
+        Self::with_placement(LayerPlacement::Absolute(frame), layout)
+    }
+
+    /// Makes the layer modal. While a modal layer is shown, only the
+    /// components inside it (and in layers above it) receive keyboard and
+    /// mouse input. Components below keep their focus and get the input back
+    /// once the layer is removed.
+    pub fn modal(mut self) -> Self {
+        self.modal = true;
+        self
+    }
+
+    /// Sets the z-index of the layer. Layers with a higher z-index are drawn
+    /// above those with a lower one anywhere in the layout, layers with the
+    /// same z-index are drawn in order. Layers inside a layer are drawn
+    /// above it regardless. Defaults to 0.
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    fn with_placement(placement: LayerPlacement, layout: Layout) -> Self {
+        Self {
+            node: layout,
+            placement,
+            modal: false,
+            z_index: 0,
+        }
+    }
+
+    fn frame(&self, frame: Rect) -> Rect {
+        match self.placement {
+            LayerPlacement::Fill => frame,
+            LayerPlacement::Centered(size) => {
+                let size = Size::new(
+                    cmp::min(size.width, frame.size.width),
+                    cmp::min(size.height, frame.size.height),
+                );
+                Rect::new(
+                    Position::new(
+                        frame.origin.x + (frame.size.width - size.width) / 2,
+                        frame.origin.y + (frame.size.height - size.height) / 2,
+                    ),
+                    size,
+                )
+            }
+            LayerPlacement::At(layer_frame) => Rect::new(
+                frame.origin + layer_frame.origin.to_vector(),
+                layer_frame.size,
+            ),
+            LayerPlacement::Absolute(layer_frame) => layer_frame,
+        }
+    }
+}
+
 /// An item inside a container.
+///
+/// Items with `FlexBasis::Auto` grow to share the space left by the others,
+/// fixed items keep their size unless given a grow or shrink factor.
+///
+/// ```
+/// # use zi::prelude::*;
+/// # use zi::components::text::{Text, TextProperties};
+/// # use zi::layout::{Align, Container};
+/// let text = |content| Text::with(TextProperties::new().content(content));
+/// let mut container = Container::row();
+/// container
+///     .add(layout::fixed(20, text("Sidebar")).shrink(1).min_size(10))
+///     .add(layout::auto(text("Content")).grow(2))
+///     .add(layout::auto(text("Details")))
+///     .add(layout::fixed(10, text("OK")).cross_size(1).align(Align::End));
+/// let layout: Layout = container.into();
+/// ```
 #[derive(Clone)]
 pub struct Item {
     node: Layout,
     flex: FlexBasis,
+    grow: usize,
+    shrink: usize,
+    min_size: usize,
+    max_size: Option<usize>,
+    cross_size: Option<usize>,
+    align: Option<Align>,
+    margin: Insets,
+}
+
+impl Item {
+    pub fn new(flex: FlexBasis, layout: Layout) -> Self {
+        Self {
+            node: layout,
+            flex,
+            grow: match flex {
+                FlexBasis::Auto => 1,
+                FlexBasis::Content
+                | FlexBasis::Fixed(_)
+                | FlexBasis::Percent(_)
+                | FlexBasis::Ratio(..) => 0,
+            },
+            shrink: 0,
+            min_size: 0,
+            max_size: None,
+            cross_size: None,
+            align: None,
+            margin: Insets::default(),
+        }
+    }
+
+    /// Sets the share of the free space the item grows by, relative to its
+    /// siblings. Defaults to 1 for `FlexBasis::Auto` and 0 otherwise.
+    #[inline]
+    pub fn grow(mut self, factor: usize) -> Self {
+        self.grow = factor;
+        self
+    }
+
+    /// Sets how much the item shrinks, relative to its siblings and in
+    /// proportion to its basis, when they don't fit. Defaults to 0, items that
+    /// don't fit are truncated starting from the last one.
+    #[inline]
+    pub fn shrink(mut self, factor: usize) -> Self {
+        self.shrink = factor;
+        self
+    }
+
+    /// Sets the size the item doesn't shrink below, along the main axis.
+    #[inline]
+    pub fn min_size(mut self, size: usize) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    /// Sets the size the item doesn't grow above, along the main axis.
+    #[inline]
+    pub fn max_size(mut self, size: usize) -> Self {
+        self.max_size = Some(size);
+        self
+    }
+
+    /// Sets the size of the item along the cross axis. By default items
+    /// stretch to the size of the container.
+    #[inline]
+    pub fn cross_size(mut self, size: usize) -> Self {
+        self.cross_size = Some(size);
+        self
+    }
+
+    /// Overrides the alignment of the container for this item, it applies if
+    /// the item has a cross size.
+    #[inline]
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = Some(align);
+        self
+    }
+
+    /// Sets the space around the item, inside the container. It's added to
+    /// the size of the item.
+    #[inline]
+    pub fn margin(mut self, margin: Insets) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// The basis of the item, including its margin.
+    fn basis(&self, direction: FlexDirection, available: Size) -> usize {
+        let margin = direction.dimension(self.margin.size());
+        match self.flex {
+            FlexBasis::Content => direction.dimension(self.content_size(available)) + margin,
+            flex => flex.resolve(direction.dimension(available)) + margin,
+        }
+    }
+
+    /// The size of the content of the item, measured in the space available
+    /// inside its margin.
+    fn content_size(&self, available: Size) -> Size {
+        let available = self.margin.shrink(Rect::from_size(available)).size;
+        self.node.0.content_size(available)
+    }
+
+    /// The minimum and maximum sizes along the main axis, from the item and
+    /// the constraints of its layout, including the margin.
+    fn limits(&self, direction: FlexDirection) -> (usize, usize) {
+        let constraints = self.node.0.constraints();
+        let margin = direction.dimension(self.margin.size());
+        let min_size = cmp::max(self.min_size, direction.dimension(constraints.min)) + margin;
+        let max_size = cmp::min(
+            self.max_size.unwrap_or(usize::MAX),
+            direction.dimension(constraints.max),
+        )
+        .saturating_add(margin);
+        (min_size, cmp::max(min_size, max_size))
+    }
+
+    /// The size along the cross axis including the margin, if the item
+    /// doesn't stretch.
+    fn cross_limit(&self, direction: FlexDirection) -> Option<usize> {
+        let max_size = direction.cross_dimension(self.node.0.constraints().max);
+        let margin = direction.cross_dimension(self.margin.size());
+        match self.cross_size {
+            Some(size) => Some(cmp::min(size, max_size) + margin),
+            None if max_size < usize::MAX => Some(max_size + margin),
+            None => None,
+        }
+    }
 }
 
 /// Enum to control the size of an item inside a container.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FlexBasis {
     Auto,
+    /// The size of the content of the item, as measured by its components.
+    Content,
     Fixed(usize),
+    /// A percentage of the size of the container.
+    Percent(usize),
+    /// A fraction of the size of the container, `Ratio(1, 3)` is a third.
+    Ratio(usize, usize),
+}
+
+impl FlexBasis {
+    /// Resolves the basis to a size, given the size of the container. `Auto`
+    /// resolves to 0, before growing, and so does `Content` which depends on
+    /// the item.
+    #[inline]
+    pub fn resolve(self, total_size: usize) -> usize {
+        match self {
+            FlexBasis::Auto | FlexBasis::Content => 0,
+            FlexBasis::Fixed(size) => size,
+            FlexBasis::Percent(percent) => total_size * percent / 100,
+            FlexBasis::Ratio(numerator, denominator) => (total_size * numerator)
+                .checked_div(denominator)
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// How the space left in a container is distributed along the main axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Justify {
+    /// Items are packed at the start of the container.
+    #[default]
+    Start,
+    /// Items are packed at the end of the container.
+    End,
+    Centre,
+    /// The space is distributed between the items, the first and last are at
+    /// the edges of the container.
+    SpaceBetween,
+    /// The space is distributed around the items, with half as much at the
+    /// edges of the container.
+    SpaceAround,
+}
+
+/// How items are placed in a container along the cross axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    /// Items fill the container, unless they have a cross size, in which case
+    /// they are aligned at the start.
+    #[default]
+    Stretch,
+    Start,
+    Centre,
+    End,
 }
 
 /// Enum to control how items are placed in a container. It defines the main
@@ -386,17 +1750,49 @@ impl FlexDirection {
             FlexDirection::ColumnReverse => size.height,
         }
     }
+
+    /// Creates a size from its dimensions along the main and cross axes.
+    #[inline]
+    pub(crate) fn size(self, main: usize, cross: usize) -> Size {
+        match self {
+            FlexDirection::Row | FlexDirection::RowReverse => Size::new(main, cross),
+            FlexDirection::Column | FlexDirection::ColumnReverse => Size::new(cross, main),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn cross_dimension(self, size: Size) -> usize {
+        match self {
+            FlexDirection::Row | FlexDirection::RowReverse => size.height,
+            FlexDirection::Column | FlexDirection::ColumnReverse => size.width,
+        }
+    }
 }
 
 pub(crate) struct LaidComponent<'a> {
     pub(crate) frame: Rect,
     pub(crate) position_hash: u64,
     pub(crate) template: &'a mut DynamicTemplate,
+    /// Set when the component is in a viewport.
+    pub(crate) clip: Option<Clip>,
 }
 
 pub(crate) struct LaidCanvas<'a> {
     pub(crate) frame: Rect,
     pub(crate) canvas: &'a Canvas,
+    /// Set when the canvas is in a viewport.
+    pub(crate) clip: Option<Clip>,
+}
+
+pub(crate) struct LaidOverlay {
+    pub(crate) frame: Rect,
+    pub(crate) position_hash: u64,
+    pub(crate) layout: Layout,
+    pub(crate) modal: bool,
+    pub(crate) z_index: i32,
+    /// Whether the frame is in screen coordinates rather than relative to
+    /// the overlay.
+    pub(crate) absolute: bool,
 }
 
 pub struct Items(SmallVec<[Item; ARRAY_SIZE]>);
@@ -488,71 +1884,587 @@ impl From<[Item; 8]> for Items {
     }
 }
 
-#[inline]
-fn splits_iter<'a>(
-    frame: Rect,
-    direction: FlexDirection,
-    children: impl Iterator<Item = &'a Item> + Clone + 'a,
-) -> impl Iterator<Item = Rect> + 'a {
+/// Computes the frames of the children of a container, in their order (also
+/// for reversed containers). Hidden children have no frame.
+fn splits(frame: Rect, container: &Container) -> Frames {
+    let direction = container.direction;
     let total_size = direction.dimension(frame.size);
+    let cross_size = direction.cross_dimension(frame.size);
+    let sizes = main_sizes(
+        frame.size,
+        direction,
+        &container.children,
+        container.overflow,
+    );
 
-    // Compute how much space is available for stretched components
-    let (stretched_budget, num_stretched_children, total_fixed_size) = {
-        let mut stretched_budget = total_size;
-        let mut num_stretched_children = 0;
-        let mut total_fixed_size = 0;
-        for child in children.clone() {
-            match child.flex {
-                FlexBasis::Auto => {
-                    num_stretched_children += 1;
-                }
-                FlexBasis::Fixed(size) => {
-                    stretched_budget = stretched_budget.saturating_sub(size);
-                    total_fixed_size += size;
-                }
-            }
+    // Distribute the space left, if any, as per `justify`
+    let num_children = sizes.iter().flatten().count();
+    let free_space = total_size - sizes.iter().flatten().sum::<usize>();
+    let (mut offset, gap, mut remainder) = match container.justify {
+        Justify::Start => (0, 0, 0),
+        Justify::End => (free_space, 0, 0),
+        Justify::Centre => (free_space / 2, 0, 0),
+        Justify::SpaceBetween if num_children > 1 => (
+            0,
+            free_space / (num_children - 1),
+            free_space % (num_children - 1),
+        ),
+        Justify::SpaceBetween => (0, 0, 0),
+        Justify::SpaceAround if num_children > 0 => {
+            let gap = free_space / num_children;
+            (gap / 2, gap, free_space % num_children)
         }
-        (stretched_budget, num_stretched_children, total_fixed_size)
+        Justify::SpaceAround => (0, 0, 0),
     };
 
-    // Divvy up the space equaly between stretched components.
-    let stretched_size = if num_stretched_children > 0 {
-        stretched_budget / num_stretched_children
-    } else {
-        0
+    container
+        .children
+        .iter()
+        .zip(sizes)
+        .map(|(child, size)| {
+            let size = size?;
+            let main_offset = if direction.is_reversed() {
+                total_size - offset - size
+            } else {
+                offset
+            };
+            offset += size + gap;
+            if remainder > 0 {
+                offset += 1;
+                remainder -= 1;
+            }
+
+            let (cross_offset, cross_size) = match (child.cross_limit(direction), child.align) {
+                (Some(size), align) => {
+                    let size = cmp::min(size, cross_size);
+                    let cross_offset = match align.unwrap_or(container.align) {
+                        Align::Stretch | Align::Start => 0,
+                        Align::Centre => (cross_size - size) / 2,
+                        Align::End => cross_size - size,
+                    };
+                    (cross_offset, size)
+                }
+                (None, _) => (0, cross_size),
+            };
+
+            let frame = match direction {
+                FlexDirection::Row | FlexDirection::RowReverse => Rect::new(
+                    Position::new(frame.origin.x + main_offset, frame.origin.y + cross_offset),
+                    Size::new(size, cross_size),
+                ),
+                FlexDirection::Column | FlexDirection::ColumnReverse => Rect::new(
+                    Position::new(frame.origin.x + cross_offset, frame.origin.y + main_offset),
+                    Size::new(cross_size, size),
+                ),
+            };
+            Some(child.margin.shrink(frame))
+        })
+        .collect()
+}
+
+/// Resolves the sizes of the children along the main axis, like the flexible
+/// lengths of CSS flexbox: the free space is shared between the children in
+/// proportion to their grow factors or, if there isn't enough space, taken
+/// from them in proportion to their shrink factors and sizes. Children whose
+/// size would fall outside their limits are frozen at the limit and the space
+/// is shared again between the others.
+///
+/// If the minimum sizes don't fit, children are truncated in order or, with
+/// `Overflow::Hide`, hidden starting from the last one. Hidden children have
+/// no size.
+fn main_sizes(
+    available: Size,
+    direction: FlexDirection,
+    children: &[Item],
+    overflow: Overflow,
+) -> SmallVec<[Option<usize>; ARRAY_SIZE]> {
+    let total_size = direction.dimension(available);
+    let bases: SmallVec<[usize; ARRAY_SIZE]> = children
+        .iter()
+        .map(|child| child.basis(direction, available))
+        .collect();
+    let limits: SmallVec<[(usize, usize); ARRAY_SIZE]> = children
+        .iter()
+        .map(|child| child.limits(direction))
+        .collect();
+    let clamp = |index: usize, size: usize| {
+        let (min_size, max_size) = limits[index];
+        size.clamp(min_size, max_size)
     };
-    let mut remainder =
-        total_size.saturating_sub(num_stretched_children * stretched_size + total_fixed_size);
-    let mut remaining_size = total_size;
 
-    children
-        .map(move |child| match child.flex {
-            FlexBasis::Auto => {
-                let offset = total_size - remaining_size;
-                let size = if remainder > 0 {
-                    remainder -= 1;
-                    stretched_size + 1
-                } else {
-                    stretched_size
-                };
-                remaining_size -= size;
-                (offset, size)
+    let mut shown: SmallVec<[bool; ARRAY_SIZE]> = smallvec![true; children.len()];
+    if overflow == Overflow::Hide {
+        let required = |index: usize| {
+            if children[index].shrink == 0 {
+                clamp(index, bases[index])
+            } else {
+                limits[index].0
             }
-            FlexBasis::Fixed(size) => {
-                let offset = total_size - remaining_size;
-                let size = cmp::min(remaining_size, size);
-                remaining_size -= size;
-                (offset, size)
+        };
+        let mut total_required: usize = (0..children.len()).map(required).sum();
+        for index in (0..children.len()).rev() {
+            if total_required <= total_size {
+                break;
             }
+            shown[index] = false;
+            total_required -= required(index);
+        }
+    }
+
+    // A child is frozen once its size is final
+    let growing = (0..children.len())
+        .filter(|&index| shown[index])
+        .map(|index| bases[index])
+        .sum::<usize>()
+        <= total_size;
+    let mut frozen: SmallVec<[bool; ARRAY_SIZE]> = children
+        .iter()
+        .zip(shown.iter())
+        .map(|(child, &shown)| {
+            !shown
+                || if growing {
+                    child.grow == 0
+                } else {
+                    child.shrink == 0
+                }
         })
-        .map(move |(offset, size)| match direction {
-            FlexDirection::Row | FlexDirection::RowReverse => Rect::new(
-                Position::new(frame.origin.x + offset, frame.origin.y),
-                Size::new(size, frame.size.height),
-            ),
-            FlexDirection::Column | FlexDirection::ColumnReverse => Rect::new(
-                Position::new(frame.origin.x, frame.origin.y + offset),
-                Size::new(frame.size.width, size),
-            ),
+        .collect();
+    let mut sizes: SmallVec<[usize; ARRAY_SIZE]> = (0..children.len())
+        .map(|index| {
+            if shown[index] {
+                clamp(index, bases[index])
+            } else {
+                0
+            }
         })
+        .collect();
+
+    while frozen.iter().any(|frozen| !frozen) {
+        let used: usize = sizes
+            .iter()
+            .zip(frozen.iter())
+            .map(|(&size, &frozen)| if frozen { size } else { 0 })
+            .sum();
+        let unfrozen_bases: usize = bases
+            .iter()
+            .zip(frozen.iter())
+            .map(|(&base, &frozen)| if frozen { 0 } else { base })
+            .sum();
+
+        // Flex the children which aren't frozen
+        if used + unfrozen_bases <= total_size {
+            let shares = distribute(
+                total_size - used - unfrozen_bases,
+                children
+                    .iter()
+                    .zip(frozen.iter())
+                    .map(|(child, &frozen)| if frozen { 0 } else { child.grow }),
+            );
+            for index in 0..children.len() {
+                if !frozen[index] {
+                    sizes[index] = bases[index] + shares[index];
+                }
+            }
+        } else {
+            let shares = distribute(
+                used + unfrozen_bases - total_size,
+                children.iter().zip(bases.iter()).zip(frozen.iter()).map(
+                    |((child, &base), &frozen)| {
+                        if frozen {
+                            0
+                        } else {
+                            child.shrink * base
+                        }
+                    },
+                ),
+            );
+            for index in 0..children.len() {
+                if !frozen[index] {
+                    sizes[index] = bases[index].saturating_sub(shares[index]);
+                }
+            }
+        }
+
+        // Clamp the sizes to the limits. If they grew overall, freeze the
+        // children below their minimum, if they shrank, those above their
+        // maximum, otherwise all of them.
+        let mut violation = 0isize;
+        for index in 0..children.len() {
+            if !frozen[index] {
+                violation += clamp(index, sizes[index]) as isize - sizes[index] as isize;
+            }
+        }
+        for index in 0..children.len() {
+            if frozen[index] {
+                continue;
+            }
+            let clamped = clamp(index, sizes[index]);
+            if violation == 0
+                || (violation > 0 && clamped > sizes[index])
+                || (violation < 0 && clamped < sizes[index])
+            {
+                sizes[index] = clamped;
+                frozen[index] = true;
+            }
+        }
+    }
+
+    // Truncate what doesn't fit
+    let mut remaining_size = total_size;
+    sizes
+        .into_iter()
+        .zip(shown)
+        .map(|(size, shown)| {
+            shown.then(|| {
+                let size = cmp::min(size, remaining_size);
+                remaining_size -= size;
+                size
+            })
+        })
+        .collect()
+}
+
+/// Splits an amount in proportion to the given weights. The remainder goes
+/// to the first items with a non-zero weight, one unit each.
+fn distribute(
+    amount: usize,
+    weights: impl Iterator<Item = usize> + Clone,
+) -> SmallVec<[usize; ARRAY_SIZE]> {
+    let total_weight: usize = weights.clone().sum();
+    if total_weight == 0 {
+        return weights.map(|_| 0).collect();
+    }
+    let mut shares: SmallVec<[usize; ARRAY_SIZE]> = weights
+        .clone()
+        .map(|weight| (amount as u128 * weight as u128 / total_weight as u128) as usize)
+        .collect();
+    let mut remainder = amount - shares.iter().sum::<usize>();
+    for (share, weight) in shares.iter_mut().zip(weights) {
+        if remainder == 0 {
+            break;
+        }
+        if weight > 0 {
+            *share += 1;
+            remainder -= 1;
+        }
+    }
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(flex: FlexBasis) -> Item {
+        Item::new(flex, Canvas::new(Size::zero()).into())
+    }
+
+    fn sizes(total_size: usize, children: &[Item]) -> Vec<usize> {
+        let available = Size::new(total_size, 1);
+        main_sizes(available, FlexDirection::Row, children, Overflow::Clip)
+            .into_iter()
+            .map(|size| size.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn flex_sizes() {
+        // Auto items share the space left, the first ones get the remainder
+        let children = [
+            item(FlexBasis::Fixed(3)),
+            item(FlexBasis::Auto),
+            item(FlexBasis::Auto).grow(2),
+        ];
+        assert_eq!(sizes(14, &children), [3, 4, 7]);
+
+        // Minimum and maximum sizes are respected, the others share what's
+        // left
+        let children = [item(FlexBasis::Auto).min_size(8), item(FlexBasis::Auto)];
+        assert_eq!(sizes(10, &children), [8, 2]);
+        let children = [
+            item(FlexBasis::Auto).max_size(2),
+            item(FlexBasis::Auto),
+            item(FlexBasis::Auto),
+        ];
+        assert_eq!(sizes(10, &children), [2, 4, 4]);
+
+        // Shrinking in proportion to the basis, then truncating in order
+        let children = [
+            item(FlexBasis::Fixed(10)).shrink(1),
+            item(FlexBasis::Fixed(20)).shrink(1).min_size(18),
+        ];
+        assert_eq!(sizes(27, &children), [9, 18]);
+        assert_eq!(sizes(20, &children), [2, 18]);
+        let children = [item(FlexBasis::Fixed(10)), item(FlexBasis::Fixed(10))];
+        assert_eq!(sizes(15, &children), [10, 5]);
+
+        // Relative sizes are resolved against the container
+        let children = [
+            item(FlexBasis::Percent(25)),
+            item(FlexBasis::Ratio(1, 3)),
+            item(FlexBasis::Auto),
+        ];
+        assert_eq!(sizes(60, &children), [15, 20, 25]);
+    }
+
+    #[test]
+    fn hide_overflow() {
+        let constrained = |min_width| {
+            Item::new(
+                FlexBasis::Auto,
+                constrained(
+                    Constraints::new().min_width(min_width),
+                    Canvas::new(Size::zero()).into(),
+                ),
+            )
+        };
+        let children = [constrained(10), constrained(5), constrained(5)];
+        assert_eq!(
+            main_sizes(
+                Size::new(16, 1),
+                FlexDirection::Row,
+                &children,
+                Overflow::Hide
+            )
+            .as_slice(),
+            &[Some(10), Some(6), None]
+        );
+    }
+
+    #[test]
+    fn margins() {
+        let mut container = Container::column();
+        container
+            .add(item(FlexBasis::Fixed(2)).margin(Insets::new(1, 2, 0, 1)))
+            .add(item(FlexBasis::Auto).margin(Insets::all(1)));
+        let frame = Rect::new(Position::new(0, 0), Size::new(10, 10));
+        assert_eq!(
+            splits(frame, &container).as_slice(),
+            &[
+                Some(Rect::new(Position::new(1, 1), Size::new(7, 2))),
+                Some(Rect::new(Position::new(1, 4), Size::new(8, 5))),
+            ]
+        );
+    }
+
+    #[test]
+    fn aligned() {
+        let frame = Rect::new(Position::new(2, 2), Size::new(10, 5));
+        let aligned = |horizontal, vertical| Aligned {
+            node: Canvas::new(Size::new(4, 2)).into(),
+            horizontal,
+            vertical,
+            size: None,
+        };
+        assert_eq!(
+            aligned(Align::Centre, Align::End).frame(frame),
+            Rect::new(Position::new(5, 5), Size::new(4, 2))
+        );
+        assert_eq!(
+            aligned(Align::Stretch, Align::Start).frame(frame),
+            Rect::new(Position::new(2, 2), Size::new(10, 2))
+        );
+    }
+
+    #[test]
+    fn frame_cache() {
+        let cache = FrameCache::default();
+        let mut computed = 0;
+        let mut frames = |frame: Rect| {
+            cache.get_or_insert_with(frame, || {
+                computed += 1;
+                smallvec![Some(frame)]
+            })
+        };
+        let frame = Rect::new(Position::new(0, 0), Size::new(10, 5));
+        let resized = Rect::new(Position::new(0, 0), Size::new(12, 5));
+        assert_eq!(frames(frame), frames(frame));
+        assert_eq!(frames(resized).into_vec(), vec![Some(resized)]);
+        assert_eq!(computed, 2);
+    }
+
+    #[test]
+    fn content_sizes() {
+        use crate::components::text::{Text, TextProperties, TextWrap};
+
+        let text =
+            |content: &str, wrap| Text::with(TextProperties::new().content(content).wrap(wrap));
+        let children = [
+            content(text("Save changes?", TextWrap::None)),
+            content(text("Yes or no", TextWrap::Word)),
+            auto(Canvas::new(Size::zero()).into()),
+        ];
+        assert_eq!(sizes(30, &children), [13, 9, 8]);
+
+        let dialog = padding(
+            Insets::all(1),
+            text("Unsaved changes will be lost", TextWrap::Word),
+        );
+        assert_eq!(dialog.0.content_size(Size::new(16, 10)), Size::new(14, 5));
+    }
+
+    #[test]
+    fn responsive() {
+        let canvas = |width| Layout::from(Canvas::new(Size::new(width, 1)));
+        let mut responsive = Responsive::new(canvas(1));
+        responsive
+            .min_width(80, canvas(3))
+            .min_height(10, canvas(2));
+        let mut layout = Layout::from(responsive);
+        let mut shown = |width, height| {
+            let mut widths = Vec::new();
+            layout.0.crawl(
+                Rect::from_size(Size::new(width, height)),
+                0,
+                &mut |_| {},
+                &mut |laid| widths.push(laid.canvas.size().width),
+                &mut |_| {},
+            );
+            widths
+        };
+        assert_eq!(shown(100, 20), [3]);
+        assert_eq!(shown(79, 20), [2]);
+        assert_eq!(shown(79, 9), [1]);
+        assert_eq!(shown(80, 5), [3]);
+    }
+
+    #[test]
+    fn measure_after_properties_taken() {
+        use crate::components::text::{Text, TextProperties, TextWrap};
+
+        let mut layout = column([
+            content(Text::with(
+                TextProperties::new()
+                    .content("one two three four")
+                    .wrap(TextWrap::Word),
+            )),
+            auto(Canvas::new(Size::zero()).into()),
+        ]);
+        let mut mounted = false;
+        let mut heights = |width| {
+            let mut heights = Vec::new();
+            layout.0.crawl(
+                Rect::from_size(Size::new(width, 10)),
+                0,
+                &mut |laid| {
+                    // Like the `App`, which hands the properties over to the
+                    // component the first time it's laid out
+                    if !mounted {
+                        let _ = laid.template.dynamic_properties();
+                        mounted = true;
+                    }
+                    heights.push(laid.frame.size.height);
+                },
+                &mut |_| {},
+                &mut |_| {},
+            );
+            heights
+        };
+        assert_eq!(heights(20), [1]);
+        assert_eq!(heights(9), [3]);
+        assert_eq!(heights(5), [4]);
+    }
+
+    #[test]
+    fn justify_and_align() {
+        let mut container = Container::row();
+        container
+            .add(item(FlexBasis::Fixed(2)))
+            .add(item(FlexBasis::Fixed(2)).cross_size(1).align(Align::End))
+            .justify(Justify::SpaceBetween);
+        let frame = Rect::new(Position::new(1, 1), Size::new(10, 3));
+        assert_eq!(
+            splits(frame, &container).as_slice(),
+            &[
+                Some(Rect::new(Position::new(1, 1), Size::new(2, 3))),
+                Some(Rect::new(Position::new(9, 3), Size::new(2, 1))),
+            ]
+        );
+    }
+
+    #[test]
+    fn grid_tracks() {
+        let mut grid = Grid::new(
+            [Track::Fixed(4), Track::Auto, Track::Fraction(1)],
+            [Track::Fraction(1), Track::Fraction(2)],
+        );
+        grid.gap(1, 0)
+            .add(0, 1, Canvas::new(Size::new(3, 1)).into())
+            .add_span(0, 0, 2, 3, Canvas::new(Size::zero()).into())
+            .add(2, 0, Canvas::new(Size::zero()).into());
+        let frame = Rect::new(Position::new(0, 0), Size::new(15, 9));
+        assert_eq!(
+            grid.frames(frame).into_vec(),
+            vec![
+                Some(Rect::new(Position::new(5, 0), Size::new(3, 3))),
+                Some(Rect::new(Position::new(0, 0), Size::new(15, 9))),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn grid_without_tracks() {
+        let mut grid = Grid::new(None, [Track::Fixed(1)]);
+        grid.add(0, 0, Canvas::new(Size::zero()).into());
+        let frame = Rect::new(Position::new(0, 0), Size::new(5, 5));
+        assert_eq!(grid.frames(frame).into_vec(), vec![None]);
+        let mut grid = Grid::new(None, None);
+        grid.add(0, 0, Canvas::new(Size::zero()).into());
+        assert_eq!(grid.frames(frame).into_vec(), vec![None]);
+    }
+
+    #[test]
+    fn viewport_clip() {
+        // A 4x3 viewport at (2, 1), scrolled to (3, 5) in 10x10 content
+        let viewport = Viewport {
+            node: Canvas::new(Size::zero()).into(),
+            size: Some(Size::new(10, 10)),
+            offset: Position::new(3, 5),
+        };
+        let frame = Rect::new(Position::new(2, 1), Size::new(4, 3));
+        let (content_frame, clip) = viewport.layout(frame);
+        assert_eq!(content_frame, Rect::from_size(Size::new(10, 10)));
+
+        assert_eq!(
+            clip.content_position(Position::new(2, 1)),
+            Some(Position::new(3, 5))
+        );
+        assert_eq!(
+            clip.content_position(Position::new(5, 3)),
+            Some(Position::new(6, 7))
+        );
+        assert_eq!(clip.content_position(Position::new(6, 1)), None);
+        assert_eq!(clip.content_position(Position::new(1, 1)), None);
+        assert_eq!(clip.content_point(Position::new(0, 0)), (1, 4));
+        assert_eq!(
+            clip.visible_position(Position::new(6, 7)),
+            Some(Position::new(5, 3))
+        );
+        assert_eq!(clip.visible_position(Position::new(0, 0)), None);
+
+        // Frames of the content are clipped to the viewport
+        assert_eq!(
+            clip.visible(Rect::new(Position::new(3, 5), Size::new(2, 2))),
+            Rect::new(Position::new(2, 1), Size::new(2, 2))
+        );
+        assert_eq!(
+            clip.visible(Rect::new(Position::new(5, 7), Size::new(5, 5))),
+            Rect::new(Position::new(4, 3), Size::new(2, 1))
+        );
+        assert!(clip
+            .visible(Rect::new(Position::new(0, 0), Size::new(3, 3)))
+            .is_empty());
+
+        // The offset is clamped so the viewport doesn't scroll past the end
+        let viewport = Viewport {
+            offset: Position::new(9, 9),
+            ..viewport
+        };
+        let (_, clip) = viewport.layout(frame);
+        assert_eq!(
+            clip.content_position(Position::new(5, 3)),
+            Some(Position::new(9, 9))
+        );
+    }
 }
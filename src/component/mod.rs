@@ -3,7 +3,7 @@
 pub mod layout;
 pub(crate) mod template;
 
-pub use self::layout::{ComponentExt, Layout};
+pub use self::layout::Layout;
 
 use smallvec::SmallVec;
 use std::{
@@ -17,7 +17,11 @@ use std::{
 use tokio::sync::mpsc::UnboundedSender;
 
 use self::template::{ComponentId, DynamicMessage};
-use crate::terminal::{Key, Rect};
+use crate::{
+    notification::Toast,
+    terminal::{CursorStyle, Key, MouseEvent, Position, Rect, Size},
+    theme::{SharedTheme, Theme},
+};
 
 /// Components are the building blocks of the UI in Zi.
 ///
@@ -60,6 +64,18 @@ pub trait Component: Sized + 'static {
     /// Returns the current visual layout of the component.
     fn view(&self) -> Layout;
 
+    /// The size the component needs to show its content in the space
+    /// available, used by layouts sized to their content, e.g.
+    /// [`layout::content`](layout/fn.content.html) items or
+    /// [`layout::align_content`](layout/fn.align_content.html). Components
+    /// which return `None`, the default, take no space on their own.
+    ///
+    /// Layouts are computed before the components in them are created, so the
+    /// size is measured from the properties rather than from an instance.
+    fn measure(_properties: &Self::Properties, _available: Size) -> Option<Size> {
+        None
+    }
+
     /// When the parent of a Component is re-rendered, it will either be re-created or
     /// receive new properties in the `change` lifecycle method. Component's can choose
     /// to re-render if the new properties are different than the previously
@@ -97,6 +113,62 @@ pub trait Component: Sized + 'static {
         }
     }
 
+    /// If the component is currently focused (see `has_focus`), `paste_binding`
+    /// will be called when text is pasted, e.g. from the clipboard. The whole
+    /// text is delivered at once, rather than as individual key presses.
+    fn paste_binding(&self, _text: &str) -> Option<Self::Message> {
+        None
+    }
+
+    /// Called on every mouse event that occurs inside the component's frame.
+    /// The position of the event is relative to the component's frame.
+    ///
+    /// Once a button is pressed inside the frame, the component also gets the
+    /// drags and the release outside of it, with the position clamped to the
+    /// frame.
+    ///
+    /// Unlike `input_binding`, the component doesn't have to be focused to
+    /// receive mouse events.
+    fn mouse_binding(&self, _event: &MouseEvent) -> Option<Self::Message> {
+        None
+    }
+
+    /// Called on every component when the terminal window gains or loses
+    /// focus. Useful to pause animations or stop polling while the user is not
+    /// looking at the app.
+    ///
+    /// Not to be confused with `has_focus`, which is about the component having
+    /// the keyboard focus inside the app.
+    fn window_focus_changed(&self, _focused: bool) -> Option<Self::Message> {
+        None
+    }
+
+    /// Called on every component when the keys of a pending chord change,
+    /// i.e. after a focused component returned `BindingTransition::Continue`
+    /// from `input_binding`. `pending` is empty when the chord was completed,
+    /// cancelled or timed out (see
+    /// [`App::set_chord_timeout`](../struct.App.html#method.set_chord_timeout)).
+    ///
+    /// Useful for showing the pending prefix, e.g. `C-x` in a status bar.
+    fn pending_keys_changed(&self, _pending: &[Key]) -> Option<Self::Message> {
+        None
+    }
+
+    /// If the component is currently focused (see `has_focus`), the position
+    /// of the text cursor relative to the component's frame. Backends show the
+    /// cursor at this position, which is also where input methods (IME) place
+    /// their composition window.
+    ///
+    /// If multiple focused components report a cursor, the outermost one wins.
+    fn cursor_position(&self) -> Option<Position> {
+        None
+    }
+
+    /// How the cursor reported by `cursor_position` should be drawn.
+    fn cursor_style(&self) -> CursorStyle {
+        CursorStyle::default()
+    }
+
     fn tick(&self) -> Option<Self::Message> {
         None
     }
@@ -172,6 +244,7 @@ where
 pub struct ComponentLink<ComponentT> {
     sender: UnboundedSender<LinkMessage>,
     component_id: ComponentId,
+    theme: SharedTheme,
     _component: PhantomData<fn() -> ComponentT>,
 }
 
@@ -225,14 +298,85 @@ impl<ComponentT: Component> ComponentLink<ComponentT> {
             .expect("App needs to outlive components");
     }
 
-    pub(crate) fn new(sender: UnboundedSender<LinkMessage>, component_id: ComponentId) -> Self {
+    /// Sends a message to the `App` runtime requesting it to set the contents
+    /// of the system clipboard.
+    pub fn set_clipboard(&self, contents: impl Into<String>) {
+        self.sender
+            .send(LinkMessage::SetClipboard(contents.into()))
+            .map_err(|_| ()) // tokio's SendError doesn't implement Debug
+            .expect("App needs to outlive components");
+    }
+
+    /// Sends a message to the `App` runtime requesting it to set the title of
+    /// the window the application is running in (e.g. the terminal emulator's
+    /// window).
+    pub fn set_title(&self, title: impl Into<String>) {
+        self.sender
+            .send(LinkMessage::SetTitle(title.into()))
+            .map_err(|_| ()) // tokio's SendError doesn't implement Debug
+            .expect("App needs to outlive components");
+    }
+
+    /// Sends a message to the `App` runtime requesting it to show a toast
+    /// notification. Toasts are stacked in the top right corner of the screen
+    /// and dismissed after their timeout.
+    pub fn notify(&self, toast: impl Into<Toast>) {
+        self.sender
+            .send(LinkMessage::Notify(toast.into()))
+            .map_err(|_| ()) // tokio's SendError doesn't implement Debug
+            .expect("App needs to outlive components");
+    }
+
+    /// Sends a message to the `App` runtime requesting it to show or hide the
+    /// performance overlay (see
+    /// [`App::set_performance_overlay`](struct.App.html#method.set_performance_overlay)).
+    pub fn toggle_performance_overlay(&self) {
+        self.sender
+            .send(LinkMessage::TogglePerformanceOverlay)
+            .map_err(|_| ()) // tokio's SendError doesn't implement Debug
+            .expect("App needs to outlive components");
+    }
+
+    /// Returns the current theme of the application (see
+    /// [`App::set_theme`](struct.App.html#method.set_theme)).
+    pub fn theme(&self) -> Theme {
+        *self.theme.read().expect("Theme lock shouldn't be poisoned")
+    }
+
+    /// Sends a message to the `App` runtime requesting it to switch to
+    /// another theme. All components are rendered again once it's switched.
+    pub fn set_theme(&self, theme: Theme) {
+        self.sender
+            .send(LinkMessage::SetTheme(theme))
+            .map_err(|_| ()) // tokio's SendError doesn't implement Debug
+            .expect("App needs to outlive components");
+    }
+
+    pub(crate) fn new(
+        sender: UnboundedSender<LinkMessage>,
+        component_id: ComponentId,
+        theme: SharedTheme,
+    ) -> Self {
         assert_eq!(TypeId::of::<ComponentT>(), component_id.type_id());
         Self {
             sender,
             component_id,
+            theme,
             _component: PhantomData,
         }
     }
+
+    /// A link for testing a component created outside of an `App`. There is
+    /// no runtime listening, sending messages through it panics.
+    #[cfg(test)]
+    pub(crate) fn detached() -> Self {
+        let (sender, _) = tokio::sync::mpsc::unbounded_channel();
+        Self::new(
+            sender,
+            ComponentId::new::<ComponentT>(0),
+            Default::default(),
+        )
+    }
 }
 
 impl<ComponentT> Clone for ComponentLink<ComponentT> {
@@ -240,6 +384,7 @@ impl<ComponentT> Clone for ComponentLink<ComponentT> {
         Self {
             sender: self.sender.clone(),
             component_id: self.component_id,
+            theme: self.theme.clone(),
             _component: PhantomData,
         }
     }
@@ -294,6 +439,11 @@ pub(crate) enum LinkMessage {
     Component(ComponentId, DynamicMessage),
     Exit,
     RunExclusive(Box<dyn FnOnce() -> Option<(ComponentId, DynamicMessage)> + Send + 'static>),
+    SetClipboard(String),
+    SetTitle(String),
+    TogglePerformanceOverlay,
+    Notify(Toast),
+    SetTheme(Theme),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -1,5 +1,6 @@
 use std::{
     any::{Any, TypeId},
+    cell::Cell,
     hash::{Hash, Hasher},
 };
 use tokio::sync::mpsc::UnboundedSender;
@@ -8,7 +9,10 @@ use super::{
     layout::{ComponentKey, Layout},
     BindingMatch, Component, ComponentLink, LinkMessage, ShouldRender,
 };
-use crate::terminal::{Key, Rect};
+use crate::{
+    terminal::{CursorStyle, Key, MouseEvent, Position, Rect, Size},
+    theme::SharedTheme,
+};
 
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct ComponentId {
@@ -96,8 +100,9 @@ impl Template for DynamicTemplate {
         id: ComponentId,
         frame: Rect,
         sender: UnboundedSender<LinkMessage>,
+        theme: SharedTheme,
     ) -> Box<dyn Renderable + 'static> {
-        self.0.create(id, frame, sender)
+        self.0.create(id, frame, sender, theme)
     }
 
     #[inline]
@@ -105,6 +110,11 @@ impl Template for DynamicTemplate {
         self.0.dynamic_properties()
     }
 
+    #[inline]
+    fn measure(&self, available: Size) -> Option<Size> {
+        self.0.measure(available)
+    }
+
     #[inline]
     fn clone(&self) -> DynamicTemplate {
         self.0.clone()
@@ -124,6 +134,18 @@ pub(crate) trait Renderable {
 
     fn input_binding(&self, pressed: &[Key]) -> BindingMatch<DynamicMessage>;
 
+    fn paste_binding(&self, text: &str) -> Option<DynamicMessage>;
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<DynamicMessage>;
+
+    fn window_focus_changed(&self, focused: bool) -> Option<DynamicMessage>;
+
+    fn pending_keys_changed(&self, pending: &[Key]) -> Option<DynamicMessage>;
+
+    fn cursor_position(&self) -> Option<Position>;
+
+    fn cursor_style(&self) -> CursorStyle;
+
     fn tick(&self) -> Option<DynamicMessage>;
 }
 
@@ -176,6 +198,40 @@ impl<ComponentT: Component> Renderable for ComponentT {
         }
     }
 
+    #[inline]
+    fn paste_binding(&self, text: &str) -> Option<DynamicMessage> {
+        <Self as Component>::paste_binding(self, text)
+            .map(|message| DynamicMessage(Box::new(message)))
+    }
+
+    #[inline]
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<DynamicMessage> {
+        <Self as Component>::mouse_binding(self, event)
+            .map(|message| DynamicMessage(Box::new(message)))
+    }
+
+    #[inline]
+    fn window_focus_changed(&self, focused: bool) -> Option<DynamicMessage> {
+        <Self as Component>::window_focus_changed(self, focused)
+            .map(|message| DynamicMessage(Box::new(message)))
+    }
+
+    #[inline]
+    fn pending_keys_changed(&self, pending: &[Key]) -> Option<DynamicMessage> {
+        <Self as Component>::pending_keys_changed(self, pending)
+            .map(|message| DynamicMessage(Box::new(message)))
+    }
+
+    #[inline]
+    fn cursor_position(&self) -> Option<Position> {
+        <Self as Component>::cursor_position(self)
+    }
+
+    #[inline]
+    fn cursor_style(&self) -> CursorStyle {
+        <Self as Component>::cursor_style(self)
+    }
+
     #[inline]
     fn tick(&self) -> Option<DynamicMessage> {
         <Self as Component>::tick(self).map(|message| DynamicMessage(Box::new(message)))
@@ -194,16 +250,27 @@ pub(crate) trait Template {
         id: ComponentId,
         frame: Rect,
         sender: UnboundedSender<LinkMessage>,
+        theme: SharedTheme,
     ) -> Box<dyn Renderable + 'static>;
 
     fn dynamic_properties(&mut self) -> DynamicProperties;
 
+    /// Measures the component from its properties, see
+    /// [`Component::measure`](trait.Component.html#method.measure).
+    fn measure(&self, available: Size) -> Option<Size>;
+
     fn clone(&self) -> DynamicTemplate;
 }
 
 pub(crate) struct ComponentDef<ComponentT: Component> {
     pub key: Option<ComponentKey>,
     pub properties: Option<ComponentT::Properties>,
+    /// Whether the component was measured, i.e. it is sized by its content.
+    measured: Cell<bool>,
+    /// A copy of the properties of a measured component, kept to measure it
+    /// again after the properties were handed over to the component, e.g. when
+    /// the layout is laid out in a different frame.
+    retained: Option<ComponentT::Properties>,
 }
 
 impl<ComponentT: Component> Clone for ComponentDef<ComponentT> {
@@ -211,6 +278,8 @@ impl<ComponentT: Component> Clone for ComponentDef<ComponentT> {
         Self {
             key: self.key,
             properties: self.properties.clone(),
+            measured: self.measured.clone(),
+            retained: self.retained.clone(),
         }
     }
 }
@@ -220,13 +289,20 @@ impl<ComponentT: Component> ComponentDef<ComponentT> {
         Self {
             key,
             properties: properties.into(),
+            measured: Cell::new(false),
+            retained: None,
         }
     }
 
     fn properties_unwrap(&mut self) -> ComponentT::Properties {
         let mut properties = None;
         std::mem::swap(&mut properties, &mut self.properties);
-        properties.expect("Already called a method that used the `Properties` value")
+        let properties =
+            properties.expect("Already called a method that used the `Properties` value");
+        if self.measured.get() {
+            self.retained = Some(properties.clone());
+        }
+        properties
     }
 }
 
@@ -252,8 +328,9 @@ impl<ComponentT: Component> Template for ComponentDef<ComponentT> {
         component_id: ComponentId,
         frame: Rect,
         sender: UnboundedSender<LinkMessage>,
+        theme: SharedTheme,
     ) -> Box<dyn Renderable> {
-        let link = ComponentLink::new(sender, component_id);
+        let link = ComponentLink::new(sender, component_id, theme);
         Box::new(ComponentT::create(self.properties_unwrap(), frame, link))
     }
 
@@ -262,6 +339,12 @@ impl<ComponentT: Component> Template for ComponentDef<ComponentT> {
         DynamicProperties(Box::new(self.properties_unwrap()))
     }
 
+    fn measure(&self, available: Size) -> Option<Size> {
+        self.measured.set(true);
+        let properties = self.properties.as_ref().or(self.retained.as_ref())?;
+        ComponentT::measure(properties, available)
+    }
+
     #[inline]
     fn clone(&self) -> DynamicTemplate {
         DynamicTemplate(Box::new(Clone::clone(self)))
@@ -1,6 +1,9 @@
 use std::iter;
 
-use crate::{layout, Canvas, Component, ComponentLink, Layout, Rect, ShouldRender, Size, Style};
+use super::text::TextAlign;
+use crate::{
+    layout, Canvas, Component, ComponentLink, Layout, Position, Rect, ShouldRender, Size, Style,
+};
 
 #[derive(Clone)]
 pub struct BorderProperties {
@@ -8,6 +11,9 @@ pub struct BorderProperties {
     pub style: Style,
     pub stroke: BorderStroke,
     pub title: Option<(String, Style)>,
+    /// Where the title is drawn in the top edge. Justified titles are drawn
+    /// on the left.
+    pub title_align: TextAlign,
 }
 
 impl BorderProperties {
@@ -17,6 +23,7 @@ impl BorderProperties {
             style: Style::default(),
             stroke: BorderStroke::default(),
             title: None,
+            title_align: TextAlign::Left,
         }
     }
 
@@ -34,8 +41,15 @@ impl BorderProperties {
         self.title = title.map(|title| (title.0.into(), title.1));
         self
     }
+
+    pub fn title_align(mut self, title_align: TextAlign) -> Self {
+        self.title_align = title_align;
+        self
+    }
 }
 
+/// A container drawing a border around its child, e.g. a panel with a title.
+/// The child is laid out in the region inside the border.
 pub struct Border {
     properties: BorderProperties,
     frame: Rect,
@@ -69,16 +83,29 @@ impl Component for Border {
             0,
             self.properties.style,
             iter::once(stroke.top_left_corner)
-                .chain(
-                    iter::repeat(stroke.top_horizontal)
-                        .take(self.frame.size.width.saturating_sub(2)),
-                )
+                .chain(iter::repeat_n(
+                    stroke.top_horizontal,
+                    self.frame.size.width.saturating_sub(2),
+                ))
                 .chain(iter::once(stroke.top_right_corner)),
         );
 
-        // Draw title if present
-        if let Some(title) = self.properties.title.as_ref() {
-            top_border.draw_str(2, 0, title.1, &title.0);
+        // Draw title if present, clipped to leave the corners and a
+        // horizontal stroke on each side visible
+        if let Some((title, title_style)) = self.properties.title.as_ref() {
+            let available = self.frame.size.width.saturating_sub(4);
+            let mut title_canvas = Canvas::new(Size::new(available, 1));
+            title_canvas.clear(self.properties.style);
+            let width = title_canvas.draw_str(0, 0, *title_style, title);
+            let x = match self.properties.title_align {
+                TextAlign::Left | TextAlign::Justify => 2,
+                TextAlign::Centre => 2 + (available - width) / 2,
+                TextAlign::Right => 2 + available - width,
+            };
+            top_border.copy_region(
+                &title_canvas,
+                Rect::new(Position::new(x, 0), Size::new(width, 1)),
+            );
         }
 
         // Draw right border
@@ -99,10 +126,10 @@ impl Component for Border {
             0,
             self.properties.style,
             iter::once(stroke.bottom_left_corner)
-                .chain(
-                    iter::repeat(stroke.bottom_horizontal)
-                        .take(self.frame.size.width.saturating_sub(2)),
-                )
+                .chain(iter::repeat_n(
+                    stroke.bottom_horizontal,
+                    self.frame.size.width.saturating_sub(2),
+                ))
                 .chain(iter::once(stroke.bottom_right_corner)),
         );
 
@@ -149,6 +176,20 @@ impl Default for BorderStroke {
 }
 
 impl BorderStroke {
+    /// Thin lines with square corners.
+    pub const fn light() -> Self {
+        Self {
+            top_left_corner: '┌',
+            top_horizontal: '─',
+            top_right_corner: '┐',
+            bottom_left_corner: '└',
+            bottom_horizontal: '─',
+            bottom_right_corner: '┘',
+            left_vertical: '│',
+            right_vertical: '│',
+        }
+    }
+
     pub const fn light_rounded() -> Self {
         Self {
             top_left_corner: '╭',
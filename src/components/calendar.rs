@@ -0,0 +1,683 @@
+use ropey::Rope;
+use std::{
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::input::{Cursor, Input, InputChange, InputProperties, InputStyle};
+use crate::{
+    layout::Layout, BindingMatch, BindingTransition, Callback, Canvas, Colour, Component,
+    ComponentExt, ComponentLink, Key, MouseButton, MouseEvent, MouseEventKind, Position, Rect,
+    ShouldRender, Style, Theme,
+};
+
+/// A day in the proleptic Gregorian calendar.
+///
+/// ```
+/// # use zi::components::calendar::Date;
+/// let date: Date = "2024-02-28".parse().unwrap();
+/// assert_eq!(date.add_days(1).to_string(), "2024-02-29");
+/// assert_eq!(date.add_months(12).to_string(), "2025-02-28");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Returns `None` if the month or the day are out of range.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month) {
+            Some(Self { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// The current day in UTC.
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or(0);
+        Self::from_days(seconds.div_euclid(SECONDS_PER_DAY))
+    }
+
+    #[inline]
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The month, from 1 to 12.
+    #[inline]
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// The day of the month, starting at 1.
+    #[inline]
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// The day of the week, from 0 for Monday to 6 for Sunday.
+    #[inline]
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.to_days() + 3).rem_euclid(7) as u32
+    }
+
+    /// The number of days in the month of the date.
+    #[inline]
+    pub fn days_in_month(&self) -> u32 {
+        days_in_month(self.year, self.month)
+    }
+
+    pub fn add_days(&self, days: i64) -> Self {
+        Self::from_days(self.to_days() + days)
+    }
+
+    /// Moves the date by a number of months, keeping the day of the month if
+    /// possible, otherwise using the last day of the month.
+    pub fn add_months(&self, months: i32) -> Self {
+        let month_index = self.year * 12 + self.month as i32 - 1 + months;
+        let year = month_index.div_euclid(12);
+        let month = month_index.rem_euclid(12) as u32 + 1;
+        Self {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+
+    /// The first day of the month of the date.
+    #[inline]
+    pub fn start_of_month(&self) -> Self {
+        Self { day: 1, ..*self }
+    }
+
+    /// The last day of the month of the date.
+    #[inline]
+    pub fn end_of_month(&self) -> Self {
+        Self {
+            day: self.days_in_month(),
+            ..*self
+        }
+    }
+
+    /// Days since 1970-01-01.
+    fn to_days(self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(self.day)
+            - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * DAYS_PER_ERA + day_of_era - DAYS_TO_EPOCH
+    }
+
+    fn from_days(days: i64) -> Self {
+        let days = days + DAYS_TO_EPOCH;
+        let era = days.div_euclid(DAYS_PER_ERA);
+        let day_of_era = days.rem_euclid(DAYS_PER_ERA);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = (year_of_era + era * 400) as i32 + i32::from(month <= 2);
+        Self { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{:04}-{:02}-{:02}",
+            self.year, self.month, self.day
+        )
+    }
+}
+
+/// The error returned when parsing a [`Date`](struct.Date.html) fails.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("invalid date, expected YYYY-MM-DD")]
+pub struct ParseDateError;
+
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    /// Parses a date in the `YYYY-MM-DD` format.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut parts = text.trim().splitn(3, '-');
+        let mut next_part = |length: usize| {
+            parts
+                .next()
+                .filter(|part| {
+                    part.len() == length && part.bytes().all(|byte| byte.is_ascii_digit())
+                })
+                .and_then(|part| part.parse::<u32>().ok())
+                .ok_or(ParseDateError)
+        };
+        let year = next_part(4)?;
+        let month = next_part(2)?;
+        let day = next_part(2)?;
+        Date::new(year as i32, month, day).ok_or(ParseDateError)
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const DAYS_PER_ERA: i64 = 146_097;
+const DAYS_TO_EPOCH: i64 = 719_468;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAY_NAMES: &str = "Mo Tu We Th Fr Sa Su";
+
+/// The width of the month view, seven columns of days.
+const GRID_WIDTH: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalendarStyle {
+    pub title: Style,
+    pub weekdays: Style,
+    pub day: Style,
+    /// Style of the days of the previous and next months.
+    pub outside: Style,
+    pub today: Style,
+    pub selected: Style,
+    /// Style of the day under the cursor, when the calendar is focused.
+    pub cursor: Style,
+}
+
+impl Default for CalendarStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const DARK3: Colour = Colour::rgb(102, 92, 84);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+        const BRIGHT_YELLOW: Colour = Colour::rgb(250, 189, 47);
+
+        Self {
+            title: Style::bold(DARK0_SOFT, LIGHT2),
+            weekdays: Style::normal(DARK0_SOFT, GRAY_245),
+            day: Style::normal(DARK0_SOFT, LIGHT2),
+            outside: Style::normal(DARK0_SOFT, DARK3),
+            today: Style::bold(DARK0_SOFT, BRIGHT_YELLOW),
+            selected: Style::bold(BRIGHT_BLUE, DARK0_SOFT),
+            cursor: Style::bold(DARK3, LIGHT2),
+        }
+    }
+}
+
+impl From<&Theme> for CalendarStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            title: Style::bold(theme.background.background, theme.background.foreground),
+            weekdays: theme.muted,
+            day: theme.background,
+            outside: theme.border,
+            today: Style::bold(theme.warning.background, theme.warning.foreground),
+            selected: Style::bold(theme.accent.foreground, theme.background.background),
+            cursor: theme.selection,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalendarProperties {
+    pub style: CalendarStyle,
+    /// The selected date. The month shown follows it when it changes.
+    pub selected: Option<Date>,
+    /// The date highlighted as the current day, see
+    /// [`Date::today`](struct.Date.html#method.today).
+    pub today: Option<Date>,
+    pub focused: bool,
+    /// Called with the date under the cursor when enter is pressed, or with
+    /// the date clicked.
+    pub on_select: Option<Callback<Date>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    MoveDays(i64),
+    MoveMonths(i32),
+    StartOfMonth,
+    EndOfMonth,
+    Select,
+    Click(Position),
+}
+
+/// A month view calendar.
+///
+/// The cursor is moved by day with `C-b`/`C-f`, by week with `C-p`/`C-n`,
+/// by month with `A-v`/`C-v` and to the start or end of the month with
+/// `C-a`/`C-e`. The arrow keys, page up and down, home and end work too.
+/// Weeks start on Monday.
+///
+/// ```text
+///   ‹  October 2026  ›
+/// Mo Tu We Th Fr Sa Su
+/// 28 29 30  1  2  3  4
+///  5  6  7  8  9 10 11
+/// ```
+pub struct Calendar {
+    properties: CalendarProperties,
+    frame: Rect,
+    /// The date under the cursor, the month shown is the one containing it.
+    cursor: Date,
+}
+
+impl Calendar {
+    fn move_cursor(&mut self, date: Date) -> ShouldRender {
+        if self.cursor != date {
+            self.cursor = date;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn select(&self, date: Date) {
+        if let Some(on_select) = self.properties.on_select.as_ref() {
+            on_select.emit(date);
+        }
+    }
+
+    /// The first day shown, the Monday on or before the first of the month.
+    fn grid_start(&self) -> Date {
+        let start_of_month = self.cursor.start_of_month();
+        start_of_month.add_days(-i64::from(start_of_month.weekday()))
+    }
+
+    fn grid_offset(&self) -> usize {
+        self.frame.size.width.saturating_sub(GRID_WIDTH) / 2
+    }
+
+    /// Returns the date drawn at a position in the frame, if any.
+    fn date_at(&self, position: Position) -> Option<Date> {
+        let x = position.x.checked_sub(self.grid_offset())?;
+        let week = position.y.checked_sub(2)?;
+        if x >= GRID_WIDTH || x % 3 == 2 || week >= 6 {
+            return None;
+        }
+        Some(self.grid_start().add_days((week * 7 + x / 3) as i64))
+    }
+
+    fn day_style(&self, date: Date) -> Style {
+        let style = &self.properties.style;
+        if self.properties.focused && date == self.cursor {
+            style.cursor
+        } else if self.properties.selected == Some(date) {
+            style.selected
+        } else if self.properties.today == Some(date) {
+            style.today
+        } else if date.month() != self.cursor.month() {
+            style.outside
+        } else {
+            style.day
+        }
+    }
+}
+
+impl Component for Calendar {
+    type Message = Message;
+    type Properties = CalendarProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        let cursor = properties
+            .selected
+            .or(properties.today)
+            .unwrap_or_else(Date::today);
+        Self {
+            properties,
+            frame,
+            cursor,
+        }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            if let Some(selected) = properties.selected {
+                if properties.selected != self.properties.selected {
+                    self.cursor = selected;
+                }
+            }
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        match message {
+            Message::MoveDays(days) => self.move_cursor(self.cursor.add_days(days)),
+            Message::MoveMonths(months) => self.move_cursor(self.cursor.add_months(months)),
+            Message::StartOfMonth => self.move_cursor(self.cursor.start_of_month()),
+            Message::EndOfMonth => self.move_cursor(self.cursor.end_of_month()),
+            Message::Select => {
+                self.select(self.cursor);
+                ShouldRender::No
+            }
+            Message::Click(position) => {
+                let offset = self.grid_offset();
+                if position.y == 0 && position.x == offset {
+                    self.move_cursor(self.cursor.add_months(-1))
+                } else if position.y == 0 && position.x == offset + GRID_WIDTH - 1 {
+                    self.move_cursor(self.cursor.add_months(1))
+                } else if let Some(date) = self.date_at(position) {
+                    self.select(date);
+                    self.move_cursor(date)
+                } else {
+                    ShouldRender::No
+                }
+            }
+        }
+    }
+
+    fn view(&self) -> Layout {
+        let style = &self.properties.style;
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style.day);
+
+        let offset = self.grid_offset();
+        let title = format!(
+            "{} {}",
+            MONTH_NAMES[self.cursor.month() as usize - 1],
+            self.cursor.year()
+        );
+        canvas.draw_str(offset, 0, style.title, "‹");
+        canvas.draw_str(
+            offset + (GRID_WIDTH.saturating_sub(title.len()) / 2),
+            0,
+            style.title,
+            &title,
+        );
+        canvas.draw_str(offset + GRID_WIDTH - 1, 0, style.title, "›");
+        canvas.draw_str(offset, 1, style.weekdays, WEEKDAY_NAMES);
+
+        let mut date = self.grid_start();
+        for week in 0..6 {
+            for weekday in 0..7 {
+                canvas.draw_str(
+                    offset + weekday * 3,
+                    week + 2,
+                    self.day_style(date),
+                    &format!("{:>2}", date.day()),
+                );
+                date = date.add_days(1);
+            }
+        }
+
+        canvas.into()
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        BindingMatch::clear(match pressed {
+            [Key::Ctrl('b')] | [Key::Left] => Some(Message::MoveDays(-1)),
+            [Key::Ctrl('f')] | [Key::Right] => Some(Message::MoveDays(1)),
+            [Key::Ctrl('p')] | [Key::Up] => Some(Message::MoveDays(-7)),
+            [Key::Ctrl('n')] | [Key::Down] => Some(Message::MoveDays(7)),
+            [Key::Alt('v')] | [Key::PageUp] => Some(Message::MoveMonths(-1)),
+            [Key::Ctrl('v')] | [Key::PageDown] => Some(Message::MoveMonths(1)),
+            [Key::Ctrl('a')] | [Key::Home] => Some(Message::StartOfMonth),
+            [Key::Ctrl('e')] | [Key::End] => Some(Message::EndOfMonth),
+            [Key::Char('\n')] => Some(Message::Select),
+            _ => None,
+        })
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::Press(MouseButton::Left) => Some(Message::Click(event.position)),
+            MouseEventKind::ScrollUp => Some(Message::MoveMonths(-1)),
+            MouseEventKind::ScrollDown => Some(Message::MoveMonths(1)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DateInputStyle {
+    pub input: InputStyle,
+    /// Foreground colour of the content when it isn't a valid date.
+    pub invalid: Colour,
+}
+
+impl Default for DateInputStyle {
+    fn default() -> Self {
+        const BRIGHT_RED: Colour = Colour::rgb(251, 73, 52);
+
+        Self {
+            input: InputStyle::default(),
+            invalid: BRIGHT_RED,
+        }
+    }
+}
+
+impl From<&Theme> for DateInputStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            input: theme.into(),
+            invalid: theme.error.foreground,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DateInputProperties {
+    pub style: DateInputStyle,
+    /// The content of the input is replaced when this changes.
+    pub date: Option<Date>,
+    pub focused: bool,
+    /// Called whenever the content of the input is a valid date.
+    pub on_change: Option<Callback<Date>>,
+    /// Called when enter is pressed and the content is a valid date.
+    pub on_submit: Option<Callback<Date>>,
+}
+
+#[derive(Clone, Debug)]
+pub enum DateInputMessage {
+    Change(InputChange),
+    MoveDays(i64),
+    MoveMonths(i32),
+    Submit,
+}
+
+/// A single line input for a date in the `YYYY-MM-DD` format, shown in red
+/// while it isn't valid.
+///
+/// Besides typing, the date can be moved by day with the up and down arrows
+/// and by month with page up and down.
+pub struct DateInput {
+    properties: DateInputProperties,
+    link: ComponentLink<Self>,
+    content: Rope,
+    cursor: Cursor,
+}
+
+impl DateInput {
+    fn date(&self) -> Option<Date> {
+        self.content.to_string().parse().ok()
+    }
+
+    fn set_date(&mut self, date: Date) {
+        self.content = Rope::from(date.to_string());
+        self.cursor = Cursor::new();
+        self.cursor.move_to_end_of_buffer(&self.content);
+    }
+
+    /// Replaces a valid date with a new one, computed from it.
+    fn move_date(&mut self, step: impl FnOnce(Date) -> Date) -> Option<Date> {
+        let date = step(self.date()?);
+        self.set_date(date);
+        Some(date)
+    }
+
+    fn emit_change(&self, date: Date) {
+        if let Some(on_change) = self.properties.on_change.as_ref() {
+            on_change.emit(date);
+        }
+    }
+}
+
+impl Component for DateInput {
+    type Message = DateInputMessage;
+    type Properties = DateInputProperties;
+
+    fn create(properties: Self::Properties, _frame: Rect, link: ComponentLink<Self>) -> Self {
+        let mut input = Self {
+            properties,
+            link,
+            content: Rope::new(),
+            cursor: Cursor::new(),
+        };
+        if let Some(date) = input.properties.date {
+            input.set_date(date);
+        }
+        input
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            if properties.date != self.properties.date {
+                if let Some(date) = properties.date.filter(|&date| self.date() != Some(date)) {
+                    self.set_date(date);
+                }
+            }
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let date = match message {
+            DateInputMessage::Change(InputChange { content, cursor }) => {
+                self.cursor = cursor;
+                match content {
+                    Some(content) => {
+                        self.content = content;
+                        self.date()
+                    }
+                    None => None,
+                }
+            }
+            DateInputMessage::MoveDays(days) => self.move_date(|date| date.add_days(days)),
+            DateInputMessage::MoveMonths(months) => self.move_date(|date| date.add_months(months)),
+            DateInputMessage::Submit => {
+                if let (Some(on_submit), Some(date)) =
+                    (self.properties.on_submit.as_ref(), self.date())
+                {
+                    on_submit.emit(date);
+                }
+                return ShouldRender::No;
+            }
+        };
+        if let Some(date) = date {
+            self.emit_change(date);
+        }
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        let mut style = self.properties.style.input.clone();
+        if self.date().is_none() && self.content.len_chars() > 0 {
+            style.content.foreground = self.properties.style.invalid;
+        }
+        Input::with(InputProperties {
+            style,
+            content: self.content.clone(),
+            cursor: self.cursor.clone(),
+            placeholder: Some("YYYY-MM-DD".into()),
+            on_change: Some(self.link.callback(DateInputMessage::Change)),
+            on_submit: Some(self.link.callback(|_| DateInputMessage::Submit)),
+            focused: self.properties.focused,
+        })
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let message = match pressed {
+            [Key::Up] => Some(DateInputMessage::MoveDays(1)),
+            [Key::Down] => Some(DateInputMessage::MoveDays(-1)),
+            [Key::PageUp] => Some(DateInputMessage::MoveMonths(1)),
+            [Key::PageDown] => Some(DateInputMessage::MoveMonths(-1)),
+            _ => None,
+        };
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates() {
+        let date = Date::new(2000, 2, 29).unwrap();
+        assert_eq!(Date::from_days(date.to_days()), date);
+        assert_eq!(Date::from_days(0).to_string(), "1970-01-01");
+        assert_eq!(date.add_days(366).to_string(), "2001-03-01");
+        assert_eq!(date.add_months(-14).to_string(), "1998-12-29");
+        // 2000-02-29 was a Tuesday
+        assert_eq!(date.weekday(), 1);
+        assert_eq!(Date::new(1900, 2, 29), None);
+        assert_eq!("2024-13-01".parse::<Date>(), Err(ParseDateError));
+        assert_eq!("2024-1-01".parse::<Date>(), Err(ParseDateError));
+    }
+}
@@ -0,0 +1,542 @@
+//! Small data visualisation components for dashboards: gauges, sparklines,
+//! bar charts and line charts.
+
+use unicode_width::UnicodeWidthStr;
+
+use super::drawing::{DotGrid, Marker, Shape, Transform};
+use super::progress::{ProgressBar, ProgressBarProperties, ProgressBarStyle};
+use crate::{
+    layout::Layout, Canvas, Colour, Component, ComponentExt, ComponentLink, Rect, ShouldRender,
+    Style, Theme,
+};
+
+/// Vertical blocks, from one to eight eighths of a cell.
+const BARS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// Draws a vertical bar `length` eighths of a cell tall, growing up from
+/// `bottom`.
+fn draw_vertical_bar(
+    canvas: &mut Canvas,
+    x: usize,
+    width: usize,
+    bottom: usize,
+    length: usize,
+    style: Style,
+) {
+    for row in 0..length.div_ceil(8) {
+        let y = match bottom.checked_sub(row) {
+            Some(y) => y,
+            None => break,
+        };
+        let eighths = (length - 8 * row).min(8);
+        for x in x..x + width {
+            canvas.draw_str(x, y, style, BARS[eighths - 1]);
+        }
+    }
+}
+
+/// Scales `value`, between 0 and `max`, to a number of eighths of `cells`.
+fn eighths(value: f64, max: f64, cells: usize) -> usize {
+    if max <= 0.0 || !value.is_finite() {
+        return 0;
+    }
+    ((value / max).clamp(0.0, 1.0) * (8 * cells) as f64).round() as usize
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GaugeStyle {
+    pub normal: ProgressBarStyle,
+    pub warning: ProgressBarStyle,
+    pub critical: ProgressBarStyle,
+}
+
+impl Default for GaugeStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const BRIGHT_YELLOW: Colour = Colour::rgb(250, 189, 47);
+        const BRIGHT_RED: Colour = Colour::rgb(251, 73, 52);
+
+        let normal = ProgressBarStyle::default();
+        Self {
+            warning: ProgressBarStyle {
+                filled: Style::bold(BRIGHT_YELLOW, DARK0_SOFT),
+                ..normal.clone()
+            },
+            critical: ProgressBarStyle {
+                filled: Style::bold(BRIGHT_RED, DARK0_SOFT),
+                ..normal.clone()
+            },
+            normal,
+        }
+    }
+}
+
+impl From<&Theme> for GaugeStyle {
+    fn from(theme: &Theme) -> Self {
+        let normal = ProgressBarStyle::from(theme);
+        Self {
+            warning: ProgressBarStyle {
+                filled: Style::bold(theme.warning.foreground, theme.background.background),
+                ..normal.clone()
+            },
+            critical: ProgressBarStyle {
+                filled: Style::bold(theme.error.foreground, theme.background.background),
+                ..normal.clone()
+            },
+            normal,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaugeProperties {
+    pub style: GaugeStyle,
+    /// The measured ratio, between 0 and 1.
+    pub ratio: f64,
+    pub label: Option<String>,
+    /// Uses the warning style at or above this ratio.
+    pub warning: Option<f64>,
+    /// Uses the critical style at or above this ratio.
+    pub critical: Option<f64>,
+}
+
+impl Default for GaugeProperties {
+    fn default() -> Self {
+        Self {
+            style: GaugeStyle::default(),
+            ratio: 0.0,
+            label: None,
+            warning: None,
+            critical: None,
+        }
+    }
+}
+
+/// A horizontal gauge showing a ratio with its percentage, changing colour
+/// when the ratio crosses the warning or critical thresholds.
+///
+/// ```text
+/// CPU ███████████▋     68%
+/// ```
+pub struct Gauge {
+    properties: GaugeProperties,
+}
+
+impl Component for Gauge {
+    type Message = ();
+    type Properties = GaugeProperties;
+
+    fn create(properties: Self::Properties, _frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn view(&self) -> Layout {
+        let GaugeProperties {
+            ref style,
+            ratio,
+            ref label,
+            warning,
+            critical,
+        } = self.properties;
+        let exceeds =
+            |threshold: Option<f64>| threshold.is_some_and(|threshold| ratio >= threshold);
+        let bar_style = if exceeds(critical) {
+            &style.critical
+        } else if exceeds(warning) {
+            &style.warning
+        } else {
+            &style.normal
+        };
+        ProgressBar::with(ProgressBarProperties {
+            style: bar_style.clone(),
+            progress: Some(ratio),
+            label: label.clone(),
+            show_percentage: true,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SparklineProperties {
+    pub style: Style,
+    /// The values to plot, the most recent last. Only the last values that
+    /// fit in the width of the component are shown, one per cell.
+    pub data: Vec<f64>,
+    /// The value drawn as a full column. The largest value shown by default.
+    pub max: Option<f64>,
+}
+
+/// A compact chart of a series of values, e.g. to show a metric over time.
+///
+/// ```text
+/// ▁▂▃▅▂▇█▆▃▂▁▁▄
+/// ```
+pub struct Sparkline {
+    properties: SparklineProperties,
+    frame: Rect,
+}
+
+impl Component for Sparkline {
+    type Message = ();
+    type Properties = SparklineProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties, frame }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        self.draw().into()
+    }
+}
+
+impl Sparkline {
+    fn draw(&self) -> Canvas {
+        let SparklineProperties {
+            style,
+            ref data,
+            max,
+        } = self.properties;
+        let size = self.frame.size;
+        let mut canvas = Canvas::new(size);
+        canvas.clear(style);
+
+        let visible = &data[data.len().saturating_sub(size.width)..];
+        let max = max.unwrap_or_else(|| visible.iter().copied().fold(0.0, f64::max));
+        for (x, &value) in visible.iter().enumerate() {
+            let length = eighths(value, max, size.height);
+            draw_vertical_bar(
+                &mut canvas,
+                x,
+                1,
+                size.height.saturating_sub(1),
+                length,
+                style,
+            );
+        }
+        canvas
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BarChartStyle {
+    pub bar: Style,
+    pub label: Style,
+    pub value: Style,
+}
+
+impl Default for BarChartStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+
+        Self {
+            bar: Style::normal(DARK0_SOFT, BRIGHT_BLUE),
+            label: Style::normal(DARK0_SOFT, GRAY_245),
+            value: Style::normal(DARK0_SOFT, LIGHT2),
+        }
+    }
+}
+
+impl From<&Theme> for BarChartStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            bar: Style::normal(theme.background.background, theme.accent.foreground),
+            label: theme.muted,
+            value: theme.background,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BarChartProperties {
+    pub style: BarChartStyle,
+    /// The label and value of every bar.
+    pub bars: Vec<(String, f64)>,
+    /// The value of a bar as tall as the chart. The largest value by default.
+    pub max: Option<f64>,
+    pub bar_width: usize,
+    /// Number of cells between bars.
+    pub gap: usize,
+}
+
+impl Default for BarChartProperties {
+    fn default() -> Self {
+        Self {
+            style: BarChartStyle::default(),
+            bars: Vec::new(),
+            max: None,
+            bar_width: 3,
+            gap: 1,
+        }
+    }
+}
+
+/// A vertical bar chart, with the value above every bar and the label below.
+///
+/// ```text
+///      7
+///  3  ███
+/// ▅▅▅ ███
+/// Mon Tue
+/// ```
+pub struct BarChart {
+    properties: BarChartProperties,
+    frame: Rect,
+}
+
+impl Component for BarChart {
+    type Message = ();
+    type Properties = BarChartProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties, frame }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        let BarChartProperties {
+            ref style,
+            ref bars,
+            max,
+            bar_width,
+            gap,
+        } = self.properties;
+        let size = self.frame.size;
+        let mut canvas = Canvas::new(size);
+        canvas.clear(style.value);
+
+        // The bottom row has the labels and there's room for a value above
+        // the tallest bar
+        let bars_height = size.height.saturating_sub(2);
+        let label_y = size.height.saturating_sub(1);
+        let max = max.unwrap_or_else(|| bars.iter().map(|&(_, value)| value).fold(0.0, f64::max));
+        for (index, (label, value)) in bars.iter().enumerate() {
+            let x = index * (bar_width + gap);
+            if x >= size.width {
+                break;
+            }
+            let length = eighths(*value, max, bars_height);
+            draw_vertical_bar(
+                &mut canvas,
+                x,
+                bar_width,
+                label_y.saturating_sub(1),
+                length,
+                style.bar,
+            );
+
+            let centred = |text: &str| x + bar_width.saturating_sub(text.width()) / 2;
+            let value = format!("{}", value);
+            let value_y = label_y.saturating_sub(length.div_ceil(8) + 1);
+            canvas.draw_str(centred(&value), value_y, style.value, &value);
+            canvas.draw_str(centred(label), label_y, style.label, label);
+        }
+
+        canvas.into()
+    }
+}
+
+/// A series of points of a [`LineChart`](struct.LineChart.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Series {
+    /// The points, as `(x, y)` in data coordinates, sorted by `x`.
+    pub points: Vec<(f64, f64)>,
+    pub colour: Colour,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LineChartProperties {
+    pub style: Style,
+    pub series: Vec<Series>,
+    /// The range of `x` shown. Fits all the points by default.
+    pub x_bounds: Option<(f64, f64)>,
+    /// The range of `y` shown. Fits all the points by default.
+    pub y_bounds: Option<(f64, f64)>,
+}
+
+/// A line chart drawn with braille characters, which gives a resolution of
+/// 2x4 points per cell.
+pub struct LineChart {
+    properties: LineChartProperties,
+    frame: Rect,
+}
+
+impl LineChart {
+    fn bounds(&self, coordinate: impl Fn(&(f64, f64)) -> f64) -> (f64, f64) {
+        self.properties
+            .series
+            .iter()
+            .flat_map(|series| series.points.iter().map(&coordinate))
+            .fold(None, |bounds: Option<(f64, f64)>, value| match bounds {
+                Some((min, max)) => Some((min.min(value), max.max(value))),
+                None => Some((value, value)),
+            })
+            .unwrap_or((0.0, 1.0))
+    }
+}
+
+impl Component for LineChart {
+    type Message = ();
+    type Properties = LineChartProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties, frame }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        let LineChartProperties {
+            style,
+            ref series,
+            x_bounds,
+            y_bounds,
+        } = self.properties;
+        let size = self.frame.size;
+        let mut canvas = Canvas::new(size);
+        canvas.clear(style);
+
+        let transform = Transform::new(
+            x_bounds.unwrap_or_else(|| self.bounds(|point| point.0)),
+            y_bounds.unwrap_or_else(|| self.bounds(|point| point.1)),
+        );
+        let mut grid = DotGrid::new(Marker::Braille, size);
+        for series in series {
+            Shape::Polyline {
+                points: series.points.clone(),
+                colour: series.colour,
+            }
+            .draw(&mut grid, &transform);
+        }
+        grid.draw(&mut canvas, style);
+
+        canvas.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Position, Size};
+
+    fn sparkline(data: &[f64], max: Option<f64>, size: Size) -> Vec<String> {
+        let sparkline = Sparkline {
+            properties: SparklineProperties {
+                style: Style::default(),
+                data: data.to_vec(),
+                max,
+            },
+            frame: Rect::new(Position::zero(), size),
+        };
+        let canvas = sparkline.draw();
+        (0..size.height)
+            .map(|y| {
+                (0..size.width)
+                    .map(|x| {
+                        canvas
+                            .textel(x, y)
+                            .as_ref()
+                            .map_or(String::new(), |textel| textel.grapheme.to_string())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn scale_to_eighths() {
+        assert_eq!(eighths(5.0, 10.0, 2), 8);
+        assert_eq!(eighths(10.0, 10.0, 1), 8);
+        // Out of range values are clamped, invalid ones are empty
+        assert_eq!(eighths(20.0, 10.0, 1), 8);
+        assert_eq!(eighths(-1.0, 10.0, 1), 0);
+        assert_eq!(eighths(f64::NAN, 10.0, 1), 0);
+        assert_eq!(eighths(1.0, 0.0, 1), 0);
+    }
+
+    #[test]
+    fn sparkline_shows_latest_values() {
+        // Scaled to the largest visible value by default
+        assert_eq!(
+            sparkline(&[100.0, 1.0, 2.0, 4.0, 8.0], None, Size::new(4, 1)),
+            ["▁▂▄█"]
+        );
+        assert_eq!(
+            sparkline(&[0.0, 4.0, 12.0, 16.0], Some(16.0), Size::new(4, 2)),
+            ["  ▄█", " ▄██"]
+        );
+    }
+
+    #[test]
+    fn line_chart_bounds_fit_points() {
+        let line_chart = |series: Vec<Series>| LineChart {
+            properties: LineChartProperties {
+                series,
+                ..Default::default()
+            },
+            frame: Rect::new(Position::zero(), Size::new(4, 4)),
+        };
+        let series = |points: &[(f64, f64)]| Series {
+            points: points.to_vec(),
+            colour: Colour::white(),
+        };
+        let chart = line_chart(vec![
+            series(&[(0.0, 3.0), (2.0, -1.0)]),
+            series(&[(-2.0, 1.0), (1.0, 5.0)]),
+        ]);
+        assert_eq!(chart.bounds(|point| point.0), (-2.0, 2.0));
+        assert_eq!(chart.bounds(|point| point.1), (-1.0, 5.0));
+        // Without points the bounds are arbitrary but not empty
+        assert_eq!(line_chart(Vec::new()).bounds(|point| point.0), (0.0, 1.0));
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{
+    layout::Layout, BindingMatch, BindingTransition, Callback, Canvas, Colour, Component,
+    ComponentLink, Key, MouseButton, MouseEvent, MouseEventKind, Rect, ShouldRender, Style, Theme,
+};
+
+/// Style of form controls, i.e. [`Checkbox`](struct.Checkbox.html) and
+/// [`RadioGroup`](../radio/struct.RadioGroup.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ControlStyle {
+    pub normal: Style,
+    /// Style of the label when the control is focused.
+    pub focused: Style,
+    /// Style of the mark of checked controls.
+    pub checked: Style,
+    /// Style of the mark of unchecked controls.
+    pub unchecked: Style,
+}
+
+impl Default for ControlStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+        const BRIGHT_GREEN: Colour = Colour::rgb(184, 187, 38);
+
+        Self {
+            normal: Style::normal(DARK0_SOFT, LIGHT2),
+            focused: Style::bold(DARK0_SOFT, BRIGHT_BLUE),
+            checked: Style::bold(DARK0_SOFT, BRIGHT_GREEN),
+            unchecked: Style::normal(DARK0_SOFT, GRAY_245),
+        }
+    }
+}
+
+impl From<&Theme> for ControlStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            normal: theme.background,
+            focused: theme.accent,
+            checked: theme.success,
+            unchecked: theme.muted,
+        }
+    }
+}
+
+/// How a [`Checkbox`](struct.Checkbox.html) is drawn.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CheckboxVariant {
+    /// A box with a cross, `[x]`.
+    #[default]
+    Checkbox,
+    /// An on/off switch, `(  ●)`.
+    Toggle,
+}
+
+impl CheckboxVariant {
+    fn mark(self, checked: bool) -> &'static str {
+        match (self, checked) {
+            (Self::Checkbox, true) => "[x]",
+            (Self::Checkbox, false) => "[ ]",
+            (Self::Toggle, true) => "(  ●)",
+            (Self::Toggle, false) => "(●  )",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckboxProperties {
+    pub style: ControlStyle,
+    pub variant: CheckboxVariant,
+    pub label: String,
+    pub checked: bool,
+    pub focused: bool,
+    /// Called with the new value when the user toggles the control.
+    pub on_change: Option<Callback<bool>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Message;
+
+/// A checkbox or on/off toggle with a label, toggled with space, enter or a
+/// click.
+///
+/// ```text
+/// [x] Show hidden files
+/// (  ●) Dark mode
+/// ```
+pub struct Checkbox {
+    properties: CheckboxProperties,
+    frame: Rect,
+}
+
+impl Component for Checkbox {
+    type Message = Message;
+    type Properties = CheckboxProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties, frame }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, _message: Self::Message) -> ShouldRender {
+        if let Some(on_change) = self.properties.on_change.as_ref() {
+            on_change.emit(!self.properties.checked);
+        }
+        ShouldRender::No
+    }
+
+    fn view(&self) -> Layout {
+        let CheckboxProperties {
+            ref style,
+            variant,
+            ref label,
+            checked,
+            focused,
+            ..
+        } = self.properties;
+        let mark_style = if checked {
+            style.checked
+        } else {
+            style.unchecked
+        };
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style.normal);
+        draw_control_line(
+            &mut canvas,
+            0,
+            style,
+            focused,
+            variant.mark(checked),
+            mark_style,
+            label,
+        );
+        canvas.into()
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message: match pressed {
+                [Key::Char(' ')] | [Key::Char('\n')] => Some(Message),
+                _ => None,
+            },
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::Press(MouseButton::Left) => Some(Message),
+            _ => None,
+        }
+    }
+}
+
+/// Draws the mark of a control followed by its label.
+pub(crate) fn draw_control_line(
+    canvas: &mut Canvas,
+    y: usize,
+    style: &ControlStyle,
+    focused: bool,
+    mark: &str,
+    mark_style: Style,
+    label: &str,
+) {
+    let label_style = if focused { style.focused } else { style.normal };
+    let mut x = canvas.draw_str(0, y, mark_style, mark);
+    x += canvas.draw_str(x, y, style.normal, " ");
+    canvas.draw_str(x, y, label_style, label);
+}
@@ -0,0 +1,445 @@
+use ropey::Rope;
+use std::{borrow::Cow, cmp, ops::Range, rc::Rc};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    layout::Layout, BindingMatch, BindingTransition, Canvas, Colour, Component, ComponentLink, Key,
+    MouseEvent, MouseEventKind, Rect, ShouldRender, Style, Theme,
+};
+
+/// Highlights text one line at a time.
+///
+/// The state at the start of every line is cached by
+/// [`CodeView`](struct.CodeView.html), so only the lines that changed or that
+/// weren't displayed yet are highlighted again.
+pub trait Highlighter {
+    /// The state carried from one line to the next, e.g. whether the line
+    /// starts inside a multi-line comment.
+    type State: Clone;
+
+    /// The state at the start of the text.
+    fn start(&self) -> Self::State;
+
+    /// Highlights a line, including its line ending, updating `state` to the
+    /// state at the start of the next line. Returns the styles of byte ranges
+    /// of the line, sorted and not overlapping. Text outside the ranges is
+    /// drawn with the default style.
+    fn highlight_line(&self, state: &mut Self::State, line: &str) -> Vec<(Range<usize>, Style)>;
+}
+
+/// A highlighter that doesn't highlight anything.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PlainText;
+
+impl Highlighter for PlainText {
+    type State = ();
+
+    fn start(&self) -> Self::State {}
+
+    fn highlight_line(&self, _state: &mut Self::State, _line: &str) -> Vec<(Range<usize>, Style)> {
+        Vec::new()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeViewStyle {
+    pub text: Style,
+    pub line_number: Style,
+}
+
+impl Default for CodeViewStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+
+        Self {
+            text: Style::normal(DARK0_SOFT, LIGHT2),
+            line_number: Style::normal(DARK0_SOFT, GRAY_245),
+        }
+    }
+}
+
+impl From<&Theme> for CodeViewStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            text: theme.background,
+            line_number: theme.muted,
+        }
+    }
+}
+
+pub struct CodeViewProperties<HighlighterT> {
+    pub style: CodeViewStyle,
+    pub content: Rope,
+    pub highlighter: Rc<HighlighterT>,
+    pub line_numbers: bool,
+    pub focused: bool,
+}
+
+impl<HighlighterT> Clone for CodeViewProperties<HighlighterT> {
+    fn clone(&self) -> Self {
+        Self {
+            style: self.style.clone(),
+            content: self.content.clone(),
+            highlighter: self.highlighter.clone(),
+            line_numbers: self.line_numbers,
+            focused: self.focused,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    ScrollDown(usize),
+    ScrollUp(usize),
+    ScrollRight(usize),
+    ScrollLeft(usize),
+    NextPage,
+    PreviousPage,
+    Top,
+    Bottom,
+}
+
+/// A read-only, syntax highlighted view of some code, with line numbers.
+///
+/// Any [`Highlighter`](trait.Highlighter.html) can be used. With the
+/// `syntect` feature enabled,
+/// [`SyntectHighlighter`](struct.SyntectHighlighter.html) highlights the
+/// languages supported by [syntect](https://docs.rs/syntect).
+pub struct CodeView<HighlighterT: Highlighter> {
+    properties: CodeViewProperties<HighlighterT>,
+    frame: Rect,
+    /// The highlighter state at the start of the first `states.len()` lines.
+    states: Vec<HighlighterT::State>,
+    line_offset: usize,
+    column_offset: usize,
+}
+
+impl<HighlighterT: Highlighter> CodeView<HighlighterT> {
+    const TAB_WIDTH: usize = 4;
+
+    fn gutter_width(&self) -> usize {
+        if self.properties.line_numbers {
+            self.properties.content.len_lines().to_string().len() + 1
+        } else {
+            0
+        }
+    }
+
+    fn max_line_offset(&self) -> usize {
+        self.properties
+            .content
+            .len_lines()
+            .saturating_sub(self.frame.size.height)
+    }
+
+    /// Highlights the lines up to the last visible one, if they aren't yet.
+    fn update_states(&mut self) {
+        let CodeViewProperties {
+            ref content,
+            ref highlighter,
+            ..
+        } = self.properties;
+        if self.states.is_empty() {
+            self.states.push(highlighter.start());
+        }
+        let last_line = cmp::min(
+            self.line_offset + self.frame.size.height,
+            content.len_lines(),
+        );
+        while self.states.len() <= last_line {
+            let index = self.states.len() - 1;
+            let mut state = self.states[index].clone();
+            let line: Cow<str> = content.line(index).into();
+            highlighter.highlight_line(&mut state, &line);
+            self.states.push(state);
+        }
+    }
+
+    fn scroll_to(&mut self, line_offset: usize) -> ShouldRender {
+        let line_offset = cmp::min(line_offset, self.max_line_offset());
+        if line_offset != self.line_offset {
+            self.line_offset = line_offset;
+            self.update_states();
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn draw_line(&self, canvas: &mut Canvas, y: usize, index: usize, gutter_width: usize) {
+        let CodeViewProperties {
+            ref style,
+            ref content,
+            ref highlighter,
+            ..
+        } = self.properties;
+        let line: Cow<str> = content.line(index).into();
+        let mut state = self.states[index].clone();
+        let spans = highlighter.highlight_line(&mut state, &line);
+        let mut spans = spans.iter().peekable();
+
+        let text_width = self.frame.size.width.saturating_sub(gutter_width);
+        let mut visual_x = 0;
+        for (byte_index, grapheme) in line.grapheme_indices(true) {
+            if grapheme == "\n" || grapheme == "\r\n" {
+                break;
+            }
+            while spans
+                .peek()
+                .map(|(range, _)| range.end <= byte_index)
+                .unwrap_or(false)
+            {
+                spans.next();
+            }
+            let grapheme_style = match spans.peek() {
+                Some((range, span_style)) if range.contains(&byte_index) => *span_style,
+                _ => style.text,
+            };
+            let (text, width) = if grapheme == "\t" {
+                let width = Self::TAB_WIDTH - visual_x % Self::TAB_WIDTH;
+                (&"    "[..width], width)
+            } else {
+                (grapheme, grapheme.width())
+            };
+            if visual_x >= self.column_offset {
+                let x = visual_x - self.column_offset;
+                if x + width > text_width {
+                    break;
+                }
+                canvas.draw_str(gutter_width + x, y, grapheme_style, text);
+            }
+            visual_x += width;
+        }
+    }
+}
+
+impl<HighlighterT: Highlighter + 'static> Component for CodeView<HighlighterT> {
+    type Message = Message;
+    type Properties = CodeViewProperties<HighlighterT>;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        let mut code_view = Self {
+            properties,
+            frame,
+            states: Vec::new(),
+            line_offset: 0,
+            column_offset: 0,
+        };
+        code_view.update_states();
+        code_view
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if !Rc::ptr_eq(&self.properties.highlighter, &properties.highlighter) {
+            self.states.clear();
+        } else if self.properties.content != properties.content {
+            // Only the lines after the first changed one need highlighting
+            let unchanged = self
+                .properties
+                .content
+                .lines()
+                .zip(properties.content.lines())
+                .take_while(|(old, new)| old == new)
+                .count();
+            self.states.truncate(unchanged + 1);
+        }
+        self.properties = properties;
+        self.line_offset = cmp::min(self.line_offset, self.max_line_offset());
+        self.update_states();
+        ShouldRender::Yes
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        self.line_offset = cmp::min(self.line_offset, self.max_line_offset());
+        self.update_states();
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let page = self.frame.size.height.saturating_sub(1).max(1);
+        match message {
+            Message::ScrollDown(lines) => self.scroll_to(self.line_offset + lines),
+            Message::ScrollUp(lines) => self.scroll_to(self.line_offset.saturating_sub(lines)),
+            Message::NextPage => self.scroll_to(self.line_offset + page),
+            Message::PreviousPage => self.scroll_to(self.line_offset.saturating_sub(page)),
+            Message::Top => self.scroll_to(0),
+            Message::Bottom => self.scroll_to(usize::MAX),
+            Message::ScrollRight(columns) => {
+                self.column_offset += columns;
+                ShouldRender::Yes
+            }
+            Message::ScrollLeft(columns) => {
+                let column_offset = self.column_offset.saturating_sub(columns);
+                if column_offset != self.column_offset {
+                    self.column_offset = column_offset;
+                    ShouldRender::Yes
+                } else {
+                    ShouldRender::No
+                }
+            }
+        }
+    }
+
+    fn view(&self) -> Layout {
+        let CodeViewProperties {
+            ref style,
+            ref content,
+            line_numbers,
+            ..
+        } = self.properties;
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style.text);
+
+        let gutter_width = self.gutter_width();
+        let last_line = cmp::min(
+            self.line_offset + self.frame.size.height,
+            content.len_lines(),
+        );
+        for (y, index) in (self.line_offset..last_line).enumerate() {
+            if line_numbers {
+                let number = format!("{:>1$} ", index + 1, gutter_width - 1);
+                canvas.draw_str(0, y, style.line_number, &number);
+            }
+            self.draw_line(&mut canvas, y, index, gutter_width);
+        }
+
+        canvas.into()
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let message = match pressed {
+            [Key::Ctrl('n')] | [Key::Down] => Some(Message::ScrollDown(1)),
+            [Key::Ctrl('p')] | [Key::Up] => Some(Message::ScrollUp(1)),
+            [Key::Ctrl('f')] | [Key::Right] => Some(Message::ScrollRight(1)),
+            [Key::Ctrl('b')] | [Key::Left] => Some(Message::ScrollLeft(1)),
+            [Key::Ctrl('v')] | [Key::PageDown] => Some(Message::NextPage),
+            [Key::Alt('v')] | [Key::PageUp] => Some(Message::PreviousPage),
+            [Key::Alt('<')] | [Key::Home] => Some(Message::Top),
+            [Key::Alt('>')] | [Key::End] => Some(Message::Bottom),
+            _ => None,
+        };
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message,
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::ScrollDown => Some(Message::ScrollDown(3)),
+            MouseEventKind::ScrollUp => Some(Message::ScrollUp(3)),
+            MouseEventKind::ScrollRight => Some(Message::ScrollRight(3)),
+            MouseEventKind::ScrollLeft => Some(Message::ScrollLeft(3)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "syntect")]
+pub use self::syntect_highlighter::SyntectHighlighter;
+
+#[cfg(feature = "syntect")]
+mod syntect_highlighter {
+    use std::ops::Range;
+    use syntect::{
+        highlighting::{self, FontStyle, HighlightIterator, HighlightState, Theme, ThemeSet},
+        parsing::{ParseState, ScopeStack, SyntaxSet},
+    };
+
+    use super::Highlighter;
+    use crate::{Colour, Style, Underline};
+
+    /// A [`Highlighter`](trait.Highlighter.html) using
+    /// [syntect](https://docs.rs/syntect), which supports Sublime Text syntax
+    /// definitions and themes.
+    pub struct SyntectHighlighter {
+        syntax_set: SyntaxSet,
+        syntax_name: String,
+        theme: Theme,
+    }
+
+    impl SyntectHighlighter {
+        /// Creates a highlighter for the syntax with the given name. Returns
+        /// `None` if the syntax set doesn't contain it.
+        pub fn new(syntax_set: SyntaxSet, syntax_name: &str, theme: Theme) -> Option<Self> {
+            syntax_set.find_syntax_by_name(syntax_name)?;
+            Some(Self {
+                syntax_set,
+                syntax_name: syntax_name.to_owned(),
+                theme,
+            })
+        }
+
+        /// Creates a highlighter using syntect's default syntaxes and the
+        /// `base16-ocean.dark` theme, for files with the given extension,
+        /// e.g. `rs`.
+        pub fn for_extension(extension: &str) -> Option<Self> {
+            let syntax_set = SyntaxSet::load_defaults_newlines();
+            let syntax_name = syntax_set.find_syntax_by_extension(extension)?.name.clone();
+            let mut themes = ThemeSet::load_defaults().themes;
+            let theme = themes.remove("base16-ocean.dark")?;
+            Self::new(syntax_set, &syntax_name, theme)
+        }
+    }
+
+    impl Highlighter for SyntectHighlighter {
+        type State = (ParseState, HighlightState);
+
+        fn start(&self) -> Self::State {
+            let syntax = self
+                .syntax_set
+                .find_syntax_by_name(&self.syntax_name)
+                .expect("The syntax is checked when creating the highlighter");
+            let highlighter = highlighting::Highlighter::new(&self.theme);
+            (
+                ParseState::new(syntax),
+                HighlightState::new(&highlighter, ScopeStack::new()),
+            )
+        }
+
+        fn highlight_line(
+            &self,
+            (parse_state, highlight_state): &mut Self::State,
+            line: &str,
+        ) -> Vec<(Range<usize>, Style)> {
+            let operations = match parse_state.parse_line(line, &self.syntax_set) {
+                Ok(operations) => operations,
+                Err(error) => {
+                    log::warn!("Could not highlight line: {}", error);
+                    return Vec::new();
+                }
+            };
+            let highlighter = highlighting::Highlighter::new(&self.theme);
+            let mut start = 0;
+            HighlightIterator::new(highlight_state, &operations, line, &highlighter)
+                .map(|(style, text)| {
+                    let range = start..start + text.len();
+                    start = range.end;
+                    (range, convert_style(style))
+                })
+                .collect()
+        }
+    }
+
+    fn convert_style(style: highlighting::Style) -> Style {
+        let colour = |colour: highlighting::Color| Colour::rgb(colour.r, colour.g, colour.b);
+        Style {
+            bold: style.font_style.contains(FontStyle::BOLD),
+            italic: style.font_style.contains(FontStyle::ITALIC),
+            underline: if style.font_style.contains(FontStyle::UNDERLINE) {
+                Underline::Single
+            } else {
+                Underline::None
+            },
+            ..Style::normal(colour(style.background), colour(style.foreground))
+        }
+    }
+}
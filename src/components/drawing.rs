@@ -0,0 +1,482 @@
+//! A component for free-form drawing of points, lines and rectangles at a
+//! higher resolution than the cells of the terminal.
+
+use crate::{
+    layout::Layout, Canvas, Colour, Component, ComponentLink, Position, Rect, ShouldRender, Size,
+    Style,
+};
+
+/// The characters used to draw the dots of a [`Drawing`](struct.Drawing.html).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Marker {
+    /// Braille characters, 2x4 dots per cell. Every cell has a single colour.
+    #[default]
+    Braille,
+    /// Half blocks, 1x2 dots per cell, each with its own colour.
+    HalfBlock,
+}
+
+impl Marker {
+    /// Number of dots per cell, horizontally and vertically.
+    fn dots_per_cell(self) -> (usize, usize) {
+        match self {
+            Self::Braille => (2, 4),
+            Self::HalfBlock => (1, 2),
+        }
+    }
+}
+
+/// Maps points in data space to dots and cells. In data space `y` grows
+/// upwards, the opposite of cells.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    /// The range of `x` spanning the width of the drawing.
+    pub x_bounds: (f64, f64),
+    /// The range of `y` spanning the height of the drawing.
+    pub y_bounds: (f64, f64),
+}
+
+impl Transform {
+    pub fn new(x_bounds: (f64, f64), y_bounds: (f64, f64)) -> Self {
+        Self { x_bounds, y_bounds }
+    }
+
+    /// Maps a point to a dot of a grid with the given resolution. Points out of
+    /// bounds are mapped to dots outside of the grid, saturating at the limits
+    /// of `isize`.
+    pub fn to_dot(&self, point: (f64, f64), resolution: (usize, usize)) -> (isize, isize) {
+        let (x, y) = self.dot_position(point, resolution);
+        (x.round() as isize, y.round() as isize)
+    }
+
+    /// The unrounded position of a point in a grid of dots with the given
+    /// resolution.
+    fn dot_position(&self, (x, y): (f64, f64), (width, height): (usize, usize)) -> (f64, f64) {
+        let scale = |value: f64, (min, max): (f64, f64), dots: usize| {
+            let range = if max > min { max - min } else { 1.0 };
+            (value - min) / range * dots.saturating_sub(1) as f64
+        };
+        (
+            scale(x, self.x_bounds, width),
+            height as f64 - 1.0 - scale(y, self.y_bounds, height),
+        )
+    }
+
+    /// Maps a point to the cell containing it in a drawing of the given size,
+    /// e.g. to place a label next to a shape. Returns `None` if the point is
+    /// out of bounds.
+    pub fn to_cell(&self, point: (f64, f64), size: Size, marker: Marker) -> Option<Position> {
+        if !is_finite(point) {
+            return None;
+        }
+        let (dots_x, dots_y) = marker.dots_per_cell();
+        let (x, y) = self.to_dot(point, (dots_x * size.width, dots_y * size.height));
+        if x < 0 || y < 0 {
+            return None;
+        }
+        let (x, y) = (x as usize, y as usize);
+        (x < dots_x * size.width && y < dots_y * size.height)
+            .then(|| Position::new(x / dots_x, y / dots_y))
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::new((0.0, 1.0), (0.0, 1.0))
+    }
+}
+
+/// A grid of dots, each of which can be set with a colour, drawn using braille
+/// or half block characters.
+pub(crate) struct DotGrid {
+    marker: Marker,
+    size: Size,
+    dots: Vec<Option<Colour>>,
+}
+
+impl DotGrid {
+    pub(crate) fn new(marker: Marker, size: Size) -> Self {
+        let (dots_x, dots_y) = marker.dots_per_cell();
+        Self {
+            marker,
+            size,
+            dots: vec![None; dots_x * size.width * dots_y * size.height],
+        }
+    }
+
+    /// Size of the grid in dots.
+    pub(crate) fn resolution(&self) -> (usize, usize) {
+        let (dots_x, dots_y) = self.marker.dots_per_cell();
+        (dots_x * self.size.width, dots_y * self.size.height)
+    }
+
+    fn dot(&self, x: usize, y: usize) -> Option<Colour> {
+        self.dots[y * self.resolution().0 + x]
+    }
+
+    /// Sets a dot, ignoring dots outside of the grid.
+    pub(crate) fn set(&mut self, x: isize, y: isize, colour: Colour) {
+        let (width, height) = self.resolution();
+        if x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height {
+            self.dots[y as usize * width + x as usize] = Some(colour);
+        }
+    }
+
+    /// Sets the dot nearest to a position, ignoring positions outside of the
+    /// grid or not finite.
+    pub(crate) fn point(&mut self, (x, y): (f64, f64), colour: Colour) {
+        if is_finite((x, y)) {
+            self.set(x.round() as isize, y.round() as isize, colour);
+        }
+    }
+
+    /// Sets the dots on the line between two positions, inclusive. The line is
+    /// clipped to the grid first, and ignored if either end isn't finite.
+    pub(crate) fn line(&mut self, from: (f64, f64), to: (f64, f64), colour: Colour) {
+        let (width, height) = self.resolution();
+        if width == 0 || height == 0 {
+            return;
+        }
+        let bounds = ((width - 1) as f64, (height - 1) as f64);
+        let (from, to) = match clip_line(from, to, bounds) {
+            Some(((x0, y0), (x1, y1))) => (
+                (x0.round() as isize, y0.round() as isize),
+                (x1.round() as isize, y1.round() as isize),
+            ),
+            None => return,
+        };
+
+        let (mut x, mut y) = from;
+        let dx = (to.0 - x).abs();
+        let dy = -(to.1 - y).abs();
+        let step_x = if x < to.0 { 1 } else { -1 };
+        let step_y = if y < to.1 { 1 } else { -1 };
+        let mut error = dx + dy;
+        loop {
+            self.set(x, y, colour);
+            if (x, y) == to {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Draws the cells with at least a dot set. The dots are drawn with the
+    /// foreground colour replaced, other cells are left untouched.
+    pub(crate) fn draw(&self, canvas: &mut Canvas, style: Style) {
+        for y in 0..self.size.height {
+            for x in 0..self.size.width {
+                let cell = match self.marker {
+                    Marker::Braille => self.braille_cell(x, y, style),
+                    Marker::HalfBlock => self.half_block_cell(x, y, style),
+                };
+                if let Some((character, style)) = cell {
+                    let mut buffer = [0; 4];
+                    canvas.draw_str(x, y, style, character.encode_utf8(&mut buffer));
+                }
+            }
+        }
+    }
+
+    fn braille_cell(&self, x: usize, y: usize, style: Style) -> Option<(char, Style)> {
+        const BITS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+        let mut bits = 0;
+        let mut colour = None;
+        for (column, column_bits) in BITS.iter().enumerate() {
+            for (row, bit) in column_bits.iter().enumerate() {
+                if let Some(dot) = self.dot(2 * x + column, 4 * y + row) {
+                    bits |= bit;
+                    colour = Some(dot);
+                }
+            }
+        }
+        let foreground = colour?;
+        let character = char::from_u32(0x2800 + bits)?;
+        Some((
+            character,
+            Style {
+                foreground,
+                ..style
+            },
+        ))
+    }
+
+    fn half_block_cell(&self, x: usize, y: usize, style: Style) -> Option<(char, Style)> {
+        match (self.dot(x, 2 * y), self.dot(x, 2 * y + 1)) {
+            (Some(upper), Some(lower)) if upper == lower => Some((
+                '█',
+                Style {
+                    foreground: upper,
+                    ..style
+                },
+            )),
+            (Some(upper), lower) => Some((
+                '▀',
+                Style {
+                    foreground: upper,
+                    background: lower.unwrap_or(style.background),
+                    ..style
+                },
+            )),
+            (None, Some(lower)) => Some((
+                '▄',
+                Style {
+                    foreground: lower,
+                    ..style
+                },
+            )),
+            (None, None) => None,
+        }
+    }
+}
+
+fn is_finite((x, y): (f64, f64)) -> bool {
+    x.is_finite() && y.is_finite()
+}
+
+/// Clips the line between two positions to the rectangle between the origin
+/// and `(max_x, max_y)` with the Liang–Barsky algorithm. Returns `None` if no
+/// part of the line is inside it.
+fn clip_line(
+    from: (f64, f64),
+    to: (f64, f64),
+    (max_x, max_y): (f64, f64),
+) -> Option<((f64, f64), (f64, f64))> {
+    if !is_finite(from) || !is_finite(to) {
+        return None;
+    }
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let (mut start, mut end) = (0.0, 1.0);
+    let edges = [
+        (-dx, from.0),
+        (dx, max_x - from.0),
+        (-dy, from.1),
+        (dy, max_y - from.1),
+    ];
+    for &(direction, distance) in &edges {
+        if direction == 0.0 {
+            // Parallel to the edge, either inside or outside of it throughout
+            if distance < 0.0 {
+                return None;
+            }
+            continue;
+        }
+        let t = distance / direction;
+        if direction < 0.0 {
+            if t > end {
+                return None;
+            }
+            start = f64::max(start, t);
+        } else {
+            if t < start {
+                return None;
+            }
+            end = f64::min(end, t);
+        }
+    }
+    let at = |t: f64| (from.0 + t * dx, from.1 + t * dy);
+    let (from, to) = (at(start), at(end));
+    // Lines between huge coordinates can overflow to infinity
+    (is_finite(from) && is_finite(to)).then_some((from, to))
+}
+
+/// A shape in a [`Drawing`](struct.Drawing.html), in data coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Point {
+        position: (f64, f64),
+        colour: Colour,
+    },
+    Line {
+        from: (f64, f64),
+        to: (f64, f64),
+        colour: Colour,
+    },
+    /// The outline of the rectangle between two opposite corners.
+    Rectangle {
+        from: (f64, f64),
+        to: (f64, f64),
+        colour: Colour,
+    },
+    /// Lines joining consecutive points.
+    Polyline {
+        points: Vec<(f64, f64)>,
+        colour: Colour,
+    },
+}
+
+impl Shape {
+    pub(crate) fn draw(&self, grid: &mut DotGrid, transform: &Transform) {
+        let resolution = grid.resolution();
+        let to_dot = |point: &(f64, f64)| transform.dot_position(*point, resolution);
+        match *self {
+            Self::Point {
+                ref position,
+                colour,
+            } => grid.point(to_dot(position), colour),
+            Self::Line {
+                ref from,
+                ref to,
+                colour,
+            } => grid.line(to_dot(from), to_dot(to), colour),
+            Self::Rectangle {
+                ref from,
+                ref to,
+                colour,
+            } => {
+                let ((x0, y0), (x1, y1)) = (to_dot(from), to_dot(to));
+                grid.line((x0, y0), (x1, y0), colour);
+                grid.line((x1, y0), (x1, y1), colour);
+                grid.line((x1, y1), (x0, y1), colour);
+                grid.line((x0, y1), (x0, y0), colour);
+            }
+            Self::Polyline { ref points, colour } => {
+                let mut points = points.iter().map(to_dot);
+                if let Some(mut previous) = points.next() {
+                    grid.point(previous, colour);
+                    for point in points {
+                        // Points after a gap in the line are drawn on their own
+                        if !is_finite(previous) {
+                            grid.point(point, colour);
+                        }
+                        grid.line(previous, point, colour);
+                        previous = point;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DrawingProperties {
+    /// Style of the empty cells. Shapes are drawn in their own colour over
+    /// its background.
+    pub style: Style,
+    pub marker: Marker,
+    /// Maps the data coordinates of the shapes to the drawing.
+    pub transform: Transform,
+    /// The shapes to draw, later ones over earlier ones.
+    pub shapes: Vec<Shape>,
+}
+
+/// A canvas for free-form drawing of shapes, e.g. maps, diagrams or custom
+/// charts.
+pub struct Drawing {
+    properties: DrawingProperties,
+    frame: Rect,
+}
+
+impl Component for Drawing {
+    type Message = ();
+    type Properties = DrawingProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties, frame }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        let DrawingProperties {
+            style,
+            marker,
+            ref transform,
+            ref shapes,
+        } = self.properties;
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style);
+
+        let mut grid = DotGrid::new(marker, self.frame.size);
+        for shape in shapes {
+            shape.draw(&mut grid, transform);
+        }
+        grid.draw(&mut canvas, style);
+
+        canvas.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(marker: Marker, size: Size, shape: Shape) -> String {
+        let mut canvas = Canvas::new(size);
+        canvas.clear(Style::default());
+        let mut grid = DotGrid::new(marker, size);
+        shape.draw(&mut grid, &Transform::default());
+        grid.draw(&mut canvas, Style::default());
+        (0..size.height)
+            .flat_map(|y| (0..size.width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                canvas
+                    .textel(x, y)
+                    .as_ref()
+                    .map_or(String::new(), |textel| textel.grapheme.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn diagonal_line() {
+        let line = Shape::Line {
+            from: (0.0, 1.0),
+            to: (1.0, 0.0),
+            colour: Colour::white(),
+        };
+        assert_eq!(draw(Marker::Braille, Size::new(2, 1), line.clone()), "⠑⢄");
+        assert_eq!(draw(Marker::HalfBlock, Size::new(2, 1), line), "▀▄");
+    }
+
+    #[test]
+    fn line_clipped_to_drawing() {
+        let line = Shape::Line {
+            from: (-1e9, 0.0),
+            to: (1e9, 0.0),
+            colour: Colour::white(),
+        };
+        assert_eq!(draw(Marker::HalfBlock, Size::new(3, 1), line), "▄▄▄");
+
+        let outside = Shape::Line {
+            from: (2.0, 0.0),
+            to: (3.0, 1.0),
+            colour: Colour::white(),
+        };
+        assert_eq!(draw(Marker::HalfBlock, Size::new(3, 1), outside), "   ");
+    }
+
+    #[test]
+    fn non_finite_points_skipped() {
+        let polyline = Shape::Polyline {
+            points: vec![
+                (0.0, 1.0),
+                (f64::NAN, 0.5),
+                (f64::INFINITY, 0.0),
+                (1.0, 1.0),
+            ],
+            colour: Colour::white(),
+        };
+        assert_eq!(draw(Marker::HalfBlock, Size::new(3, 1), polyline), "▀ ▀");
+    }
+}
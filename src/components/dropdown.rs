@@ -0,0 +1,465 @@
+use std::cmp;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    layout::{self, Layer, Layout},
+    BindingMatch, BindingTransition, Callback, Canvas, Colour, Component, ComponentExt,
+    ComponentLink, Key, MouseButton, MouseEvent, MouseEventKind, Position, Rect, ShouldRender,
+    Size, Style, Theme,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DropdownStyle {
+    pub button: Style,
+    pub focused: Style,
+    pub placeholder: Style,
+    pub item: Style,
+    pub highlighted: Style,
+}
+
+impl Default for DropdownStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const DARK1: Colour = Colour::rgb(60, 56, 54);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+
+        Self {
+            button: Style::normal(DARK1, LIGHT2),
+            focused: Style::normal(DARK1, BRIGHT_BLUE),
+            placeholder: Style::normal(DARK1, GRAY_245),
+            item: Style::normal(DARK0_SOFT, LIGHT2),
+            highlighted: Style::normal(BRIGHT_BLUE, DARK0_SOFT),
+        }
+    }
+}
+
+impl From<&Theme> for DropdownStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            button: theme.surface,
+            focused: Style::normal(theme.surface.background, theme.accent.foreground),
+            placeholder: Style::normal(theme.surface.background, theme.muted.foreground),
+            item: theme.background,
+            highlighted: theme.selection,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropdownProperties {
+    pub style: DropdownStyle,
+    pub options: Vec<String>,
+    /// Index of the selected option.
+    pub selected: Option<usize>,
+    /// Shown when no option is selected.
+    pub placeholder: Option<String>,
+    /// The maximum number of options shown at once when the dropdown is open.
+    pub max_height: usize,
+    pub focused: bool,
+    /// Called with the index of the newly selected option.
+    pub on_change: Option<Callback<usize>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    Open,
+    Close,
+    Toggle,
+    NextOption,
+    PreviousOption,
+    /// Selects the highlighted option and closes the dropdown.
+    Confirm,
+    /// Selects the option with the given index and closes the dropdown.
+    Pick(usize),
+    /// Appends a character to the filter, opening the dropdown if needed.
+    Filter(char),
+    DeleteFilterChar,
+}
+
+/// A button showing the selected option which opens a list of options in an
+/// overlay layer below it.
+///
+/// Typing while the dropdown is focused filters the options, keeping those
+/// containing the typed text (ignoring case).
+pub struct Dropdown {
+    properties: DropdownProperties,
+    frame: Rect,
+    link: ComponentLink<Self>,
+    open: bool,
+    filter: String,
+    /// Indices of the options matching the filter.
+    matches: Vec<usize>,
+    /// Index in `matches` of the highlighted option.
+    highlighted: usize,
+}
+
+impl Dropdown {
+    fn update_matches(&mut self) {
+        let filter = self.filter.to_lowercase();
+        self.matches = self
+            .properties
+            .options
+            .iter()
+            .enumerate()
+            .filter(|(_, option)| option.to_lowercase().contains(&filter))
+            .map(|(index, _)| index)
+            .collect();
+        self.highlighted = self
+            .properties
+            .selected
+            .and_then(|selected| self.matches.iter().position(|&index| index == selected))
+            .unwrap_or(0);
+    }
+
+    fn open(&mut self) -> ShouldRender {
+        if self.open {
+            return ShouldRender::No;
+        }
+        self.open = true;
+        self.filter.clear();
+        self.update_matches();
+        ShouldRender::Yes
+    }
+
+    fn close(&mut self) -> ShouldRender {
+        if !self.open {
+            return ShouldRender::No;
+        }
+        self.open = false;
+        self.filter.clear();
+        ShouldRender::Yes
+    }
+
+    fn pick(&mut self, index: usize) -> ShouldRender {
+        if let Some(on_change) = self.properties.on_change.as_ref() {
+            if index < self.properties.options.len() && Some(index) != self.properties.selected {
+                on_change.emit(index);
+            }
+        }
+        self.close()
+    }
+
+    fn draw_button(&self) -> Canvas {
+        let DropdownProperties {
+            ref style,
+            ref options,
+            selected,
+            ref placeholder,
+            focused,
+            ..
+        } = self.properties;
+        let button_style = if focused { style.focused } else { style.button };
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(button_style);
+
+        let (text, text_style) = if self.open && !self.filter.is_empty() {
+            (self.filter.as_str(), button_style)
+        } else if let Some(option) = selected.and_then(|selected| options.get(selected)) {
+            (option.as_str(), button_style)
+        } else {
+            (placeholder.as_deref().unwrap_or(""), style.placeholder)
+        };
+        canvas.draw_str(1, 0, text_style, text);
+
+        let arrow = if self.open { "▴" } else { "▾" };
+        let x = self.frame.size.width.saturating_sub(arrow.width() + 1);
+        canvas.draw_str(x, 0, button_style, arrow);
+        canvas
+    }
+}
+
+impl Component for Dropdown {
+    type Message = Message;
+    type Properties = DropdownProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, link: ComponentLink<Self>) -> Self {
+        let mut dropdown = Self {
+            properties,
+            frame,
+            link,
+            open: false,
+            filter: String::new(),
+            matches: Vec::new(),
+            highlighted: 0,
+        };
+        dropdown.update_matches();
+        dropdown
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            if !self.properties.focused {
+                self.open = false;
+                self.filter.clear();
+            }
+            self.update_matches();
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        match message {
+            Message::Open => self.open(),
+            Message::Close => self.close(),
+            Message::Toggle if self.open => self.close(),
+            Message::Toggle => self.open(),
+            Message::NextOption if self.open => {
+                self.highlighted =
+                    cmp::min(self.highlighted + 1, self.matches.len().saturating_sub(1));
+                ShouldRender::Yes
+            }
+            Message::PreviousOption if self.open => {
+                self.highlighted = self.highlighted.saturating_sub(1);
+                ShouldRender::Yes
+            }
+            Message::NextOption | Message::PreviousOption => self.open(),
+            Message::Confirm => match self.matches.get(self.highlighted) {
+                Some(&index) if self.open => self.pick(index),
+                _ => self.close(),
+            },
+            Message::Pick(index) => self.pick(index),
+            Message::Filter(character) => {
+                self.open();
+                self.filter.push(character);
+                self.update_matches();
+                ShouldRender::Yes
+            }
+            Message::DeleteFilterChar => {
+                if self.filter.pop().is_some() {
+                    self.update_matches();
+                    ShouldRender::Yes
+                } else {
+                    ShouldRender::No
+                }
+            }
+        }
+    }
+
+    fn view(&self) -> Layout {
+        let button = self.draw_button().into();
+        if !self.open {
+            return button;
+        }
+
+        let height = cmp::max(cmp::min(self.matches.len(), self.properties.max_height), 1);
+        let list = DropdownList::with(DropdownListProperties {
+            style: self.properties.style.clone(),
+            options: self
+                .matches
+                .iter()
+                .map(|&index| self.properties.options[index].clone())
+                .collect(),
+            highlighted: self.highlighted,
+            on_pick: {
+                let matches = self.matches.clone();
+                self.link
+                    .callback(move |index: usize| Message::Pick(matches[index]))
+            },
+        });
+        layout::overlay(
+            button,
+            [Layer::at(
+                Rect::new(
+                    Position::new(0, self.frame.size.height),
+                    Size::new(self.frame.size.width, height),
+                ),
+                list,
+            )],
+        )
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let message = match pressed {
+            [Key::Ctrl('n')] | [Key::Down] => Some(Message::NextOption),
+            [Key::Ctrl('p')] | [Key::Up] => Some(Message::PreviousOption),
+            [Key::Char('\n')] if self.open => Some(Message::Confirm),
+            [Key::Char('\n')] | [Key::Char(' ')] if !self.open => Some(Message::Open),
+            [Key::Esc] | [Key::Ctrl('g')] if self.open => Some(Message::Close),
+            [Key::Backspace] if self.open => Some(Message::DeleteFilterChar),
+            [Key::Char(character)] if !character.is_control() => Some(Message::Filter(*character)),
+            _ => None,
+        };
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message,
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::Press(MouseButton::Left) => Some(Message::Toggle),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct DropdownListProperties {
+    style: DropdownStyle,
+    options: Vec<String>,
+    highlighted: usize,
+    on_pick: Callback<usize>,
+}
+
+/// The list of options of an open dropdown. It's a separate component so that
+/// it receives the mouse events in its layer.
+struct DropdownList {
+    properties: DropdownListProperties,
+    frame: Rect,
+}
+
+impl DropdownList {
+    /// Index of the first visible option, such that the highlighted one is
+    /// visible.
+    fn offset(&self) -> usize {
+        (self.properties.highlighted + 1).saturating_sub(self.frame.size.height)
+    }
+}
+
+impl Component for DropdownList {
+    type Message = usize;
+    type Properties = DropdownListProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties, frame }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, index: Self::Message) -> ShouldRender {
+        self.properties.on_pick.emit(index);
+        ShouldRender::No
+    }
+
+    fn view(&self) -> Layout {
+        let DropdownListProperties {
+            ref style,
+            ref options,
+            highlighted,
+            ..
+        } = self.properties;
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style.item);
+        if options.is_empty() {
+            canvas.draw_str(1, 0, style.placeholder, "No matches");
+        }
+
+        let offset = self.offset();
+        for (y, (index, option)) in options
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(self.frame.size.height)
+            .enumerate()
+        {
+            let item_style = if index == highlighted {
+                canvas.clear_region(
+                    Rect::new(Position::new(0, y), Size::new(self.frame.size.width, 1)),
+                    style.highlighted,
+                );
+                style.highlighted
+            } else {
+                style.item
+            };
+            canvas.draw_str(1, y, item_style, option);
+        }
+
+        canvas.into()
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::Press(MouseButton::Left) => {
+                let index = self.offset() + event.position.y;
+                (index < self.properties.options.len()).then_some(index)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    #[test]
+    fn filter_options() {
+        let picked = Rc::new(RefCell::new(Vec::new()));
+        let properties = DropdownProperties {
+            style: DropdownStyle::default(),
+            options: ["Apple", "banana", "Cherry", "grape"]
+                .iter()
+                .map(|option| option.to_string())
+                .collect(),
+            selected: Some(1),
+            placeholder: None,
+            max_height: 5,
+            focused: true,
+            on_change: Some({
+                let picked = picked.clone();
+                (move |index| picked.borrow_mut().push(index)).into()
+            }),
+        };
+        let frame = Rect::new(Position::new(0, 0), Size::new(10, 1));
+        let mut dropdown = Dropdown::create(properties, frame, ComponentLink::detached());
+        assert!(!dropdown.open);
+        assert_eq!(dropdown.matches, [0, 1, 2, 3]);
+
+        // Typing opens the dropdown, matches ignore case and the selected
+        // option stays highlighted while it matches
+        dropdown.update(Message::Filter('A'));
+        assert!(dropdown.open);
+        assert_eq!(dropdown.matches, [0, 1, 3]);
+        assert_eq!(dropdown.highlighted, 1);
+        dropdown.update(Message::Filter('p'));
+        assert_eq!(dropdown.matches, [0, 3]);
+        assert_eq!(dropdown.highlighted, 0);
+        dropdown.update(Message::Filter('e'));
+        assert_eq!(dropdown.matches, [3]);
+        dropdown.update(Message::DeleteFilterChar);
+        assert_eq!(dropdown.filter, "Ap");
+        assert_eq!(dropdown.matches, [0, 3]);
+
+        // Confirming picks the highlighted match by its index in the options
+        dropdown.update(Message::NextOption);
+        dropdown.update(Message::Confirm);
+        assert_eq!(*picked.borrow(), [3]);
+        assert!(!dropdown.open);
+        assert!(dropdown.filter.is_empty());
+
+        // Nothing matches, confirming only closes the dropdown
+        dropdown.update(Message::Filter('x'));
+        assert!(dropdown.matches.is_empty());
+        dropdown.update(Message::Confirm);
+        assert_eq!(*picked.borrow(), [3]);
+        assert!(!dropdown.open);
+    }
+}
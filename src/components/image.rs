@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use crate::{
+    layout::Layout, terminal::Image as RasterImage, Canvas, Component, ComponentLink, Position,
+    Rect, ShouldRender, Size, Style,
+};
+
+#[derive(Clone, Debug)]
+pub struct ImageProperties {
+    /// Style of the cells around the image.
+    pub style: Style,
+    pub image: Arc<RasterImage>,
+}
+
+impl PartialEq for ImageProperties {
+    fn eq(&self, other: &Self) -> bool {
+        // Images are compared by identity, they're potentially large
+        self.style == other.style && Arc::ptr_eq(&self.image, &other.image)
+    }
+}
+
+/// A component showing an image, scaled to fit and centred, preserving its
+/// aspect ratio.
+///
+/// The image is drawn with the graphics protocol of the terminal if the
+/// backend supports one, see
+/// [`Capabilities`](../../backend/struct.Capabilities.html). Otherwise it's
+/// approximated with half blocks, two pixels per cell.
+pub struct Image {
+    properties: ImageProperties,
+    frame: Rect,
+}
+
+impl Image {
+    /// The cells covered by the image. Assumes cells are twice as tall as
+    /// they are wide, as with most fonts.
+    fn region(&self) -> Rect {
+        let available = self.frame.size;
+        let image = self.properties.image.size();
+        if image.is_empty() || available.is_empty() {
+            return Rect::zero();
+        }
+
+        // Size in half cells, i.e. square "pixels"
+        let scale = f64::min(
+            available.width as f64 / image.width as f64,
+            (2 * available.height) as f64 / image.height as f64,
+        );
+        let size = Size::new(
+            ((image.width as f64 * scale).round() as usize).clamp(1, available.width),
+            ((image.height as f64 * scale / 2.0).round() as usize).clamp(1, available.height),
+        );
+        Rect::new(
+            Position::new(
+                (available.width - size.width) / 2,
+                (available.height - size.height) / 2,
+            ),
+            size,
+        )
+    }
+}
+
+impl Component for Image {
+    type Message = ();
+    type Properties = ImageProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties, frame }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(self.properties.style);
+        canvas.draw_image(self.region(), self.properties.image.clone());
+        canvas.into()
+    }
+}
@@ -1,11 +1,12 @@
 use ropey::Rope;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 use crate::{
     layout::Layout,
     text::{cursor, CharIndex, TextStorage},
-    BindingMatch, BindingTransition, Callback, Canvas, Colour, Component, ComponentLink, Key, Rect,
-    ShouldRender, Style,
+    BindingMatch, BindingTransition, Callback, Canvas, Colour, Component, ComponentLink, Key,
+    Position, Rect, ShouldRender, Style, Theme,
 };
 
 pub use crate::text::Cursor;
@@ -15,7 +16,12 @@ pub struct InputProperties {
     pub style: InputStyle,
     pub content: Rope,
     pub cursor: Cursor,
+    /// Text shown when the content is empty.
+    pub placeholder: Option<String>,
     pub on_change: Option<Callback<InputChange>>,
+    /// Called with the content, without the trailing newline, when enter is
+    /// pressed.
+    pub on_submit: Option<Callback<String>>,
     pub focused: bool,
 }
 
@@ -23,6 +29,8 @@ pub struct InputProperties {
 pub struct InputStyle {
     pub content: Style,
     pub cursor: Style,
+    pub selection: Style,
+    pub placeholder: Style,
 }
 
 impl Default for InputStyle {
@@ -30,10 +38,25 @@ impl Default for InputStyle {
         const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
         const LIGHT2: Colour = Colour::rgb(213, 196, 161);
         const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+        const DARK3: Colour = Colour::rgb(102, 92, 84);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
 
         Self {
             content: Style::normal(DARK0_SOFT, LIGHT2),
             cursor: Style::normal(BRIGHT_BLUE, DARK0_SOFT),
+            selection: Style::normal(DARK3, LIGHT2),
+            placeholder: Style::normal(DARK0_SOFT, GRAY_245),
+        }
+    }
+}
+
+impl From<&Theme> for InputStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            content: theme.background,
+            cursor: Style::normal(theme.background.foreground, theme.background.background),
+            selection: theme.selection,
+            placeholder: theme.muted,
         }
     }
 }
@@ -47,6 +70,8 @@ pub struct InputChange {
 pub struct Input {
     properties: InputProperties,
     frame: Rect,
+    /// Number of columns scrolled to the right to keep the cursor visible.
+    scroll_offset: usize,
 }
 
 impl Component for Input {
@@ -56,12 +81,19 @@ impl Component for Input {
     fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
         let mut content = properties.content.clone();
         cursor::ensure_trailing_newline_with_content(&mut content);
-        Self { properties, frame }
+        let mut input = Self {
+            properties,
+            frame,
+            scroll_offset: 0,
+        };
+        input.scroll_to_cursor();
+        input
     }
 
     fn change(&mut self, properties: Self::Properties) -> ShouldRender {
         if self.properties != properties {
             self.properties = properties;
+            self.scroll_to_cursor();
             ShouldRender::Yes
         } else {
             ShouldRender::No
@@ -70,6 +102,7 @@ impl Component for Input {
 
     fn resize(&mut self, frame: Rect) -> ShouldRender {
         self.frame = frame;
+        self.scroll_to_cursor();
         ShouldRender::Yes
     }
 
@@ -91,20 +124,59 @@ impl Component for Input {
             }
             Message::InsertChar(character) => {
                 let mut new_content = self.properties.content.clone();
+                if cursor.has_selection() {
+                    cursor.delete_selection(&mut new_content);
+                }
                 cursor.insert_char(&mut new_content, character);
                 cursor.move_right(&new_content);
                 content_change = Some(new_content);
             }
+            Message::InsertText(text) => {
+                let mut new_content = self.properties.content.clone();
+                if cursor.has_selection() {
+                    cursor.delete_selection(&mut new_content);
+                }
+                cursor.insert_chars(&mut new_content, text.chars());
+                cursor.move_right_n(&new_content, text.graphemes(true).count());
+                content_change = Some(new_content);
+            }
             Message::DeleteBackward => {
                 let mut new_content = self.properties.content.clone();
-                cursor.backspace(&mut new_content);
+                if cursor.has_selection() {
+                    cursor.delete_selection(&mut new_content);
+                } else {
+                    cursor.backspace(&mut new_content);
+                }
                 content_change = Some(new_content);
             }
             Message::DeleteForward => {
                 let mut new_content = self.properties.content.clone();
-                cursor.delete(&mut new_content);
+                if cursor.has_selection() {
+                    cursor.delete_selection(&mut new_content);
+                } else {
+                    cursor.delete(&mut new_content);
+                }
                 content_change = Some(new_content);
             }
+            Message::BeginSelection => {
+                cursor.begin_selection();
+            }
+            Message::ClearSelection => {
+                cursor.clear_selection();
+            }
+            Message::SelectAll => {
+                cursor.select_all(&self.properties.content);
+            }
+            Message::Submit => {
+                if let Some(on_submit) = self.properties.on_submit.as_ref() {
+                    let mut content = self.properties.content.to_string();
+                    if content.ends_with('\n') {
+                        content.pop();
+                    }
+                    on_submit.emit(content);
+                }
+                return ShouldRender::No;
+            }
         }
 
         if let Some(on_change) = self.properties.on_change.as_mut() {
@@ -124,14 +196,32 @@ impl Component for Input {
                     ref content,
                     ref cursor,
                     ref style,
+                    ref placeholder,
                     ..
                 },
+            scroll_offset,
             ..
         } = *self;
 
         let mut canvas = Canvas::new(self.frame.size);
         canvas.clear(style.content);
 
+        match placeholder {
+            Some(placeholder) if content.len_chars() <= 1 => {
+                canvas.draw_str(0, 0, style.placeholder, placeholder);
+                if let Some(textel) = canvas.textel_mut(0, 0) {
+                    textel.style = style.cursor;
+                }
+                return canvas.into();
+            }
+            _ => {}
+        }
+
+        let selection = if cursor.has_selection() {
+            cursor.selection()
+        } else {
+            CharIndex(0)..CharIndex(0)
+        };
         let mut char_offset = 0;
         let mut visual_offset = 0;
         for grapheme in content.graphemes() {
@@ -140,16 +230,21 @@ impl Component for Input {
             let grapheme = grapheme.as_str().unwrap();
             let grapheme_width = UnicodeWidthStr::width(grapheme);
 
-            canvas.draw_str(
-                visual_offset,
-                0,
-                if cursor.range().contains(&CharIndex(char_offset)) {
-                    style.cursor
-                } else {
-                    style.content
-                },
-                if grapheme_width > 0 { grapheme } else { " " },
-            );
+            // Graphemes scrolled out of view on the left are skipped
+            if visual_offset >= scroll_offset {
+                canvas.draw_str(
+                    visual_offset - scroll_offset,
+                    0,
+                    if cursor.range().contains(&CharIndex(char_offset)) {
+                        style.cursor
+                    } else if selection.contains(&CharIndex(char_offset)) {
+                        style.selection
+                    } else {
+                        style.content
+                    },
+                    if grapheme_width > 0 { grapheme } else { " " },
+                );
+            }
             visual_offset += grapheme_width;
             char_offset += len_chars;
         }
@@ -161,6 +256,27 @@ impl Component for Input {
         self.properties.focused
     }
 
+    fn paste_binding(&self, text: &str) -> Option<Self::Message> {
+        // The input is a single line, drop any line breaks or tabs
+        let text: String = text
+            .chars()
+            .filter(|character| !matches!(character, '\n' | '\r' | '\t'))
+            .collect();
+        if text.is_empty() {
+            None
+        } else {
+            Some(Message::InsertText(text))
+        }
+    }
+
+    fn cursor_position(&self) -> Option<Position> {
+        Some(Position::new(
+            self.cursor_visual_offset()
+                .saturating_sub(self.scroll_offset),
+            0,
+        ))
+    }
+
     fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
         let mut transition = BindingTransition::Clear;
         let message = match pressed {
@@ -173,8 +289,12 @@ impl Component for Input {
             {
                 Some(Message::InsertChar(character))
             }
+            &[Key::Char('\n')] => Some(Message::Submit),
             &[Key::Ctrl('d')] | &[Key::Delete] => Some(Message::DeleteForward),
             &[Key::Backspace] => Some(Message::DeleteBackward),
+            &[Key::Ctrl(' ')] => Some(Message::BeginSelection),
+            &[Key::Ctrl('g')] => Some(Message::ClearSelection),
+            &[Key::Ctrl('x'), Key::Char('h')] => Some(Message::SelectAll),
             &[Key::Ctrl('x')] => {
                 transition = BindingTransition::Continue;
                 None
@@ -188,13 +308,151 @@ impl Component for Input {
     }
 }
 
+impl Input {
+    /// The column of the cursor, ignoring scrolling.
+    fn cursor_visual_offset(&self) -> usize {
+        let cursor_start = self.properties.cursor.range().start;
+        let mut char_offset = 0;
+        let mut visual_offset = 0;
+        for grapheme in self.properties.content.graphemes() {
+            if CharIndex(char_offset) >= cursor_start {
+                break;
+            }
+            char_offset += grapheme.len_chars();
+            visual_offset += grapheme.as_str().map(UnicodeWidthStr::width).unwrap_or(1);
+        }
+        visual_offset
+    }
+
+    /// Scrolls horizontally by as little as possible for the cursor to be
+    /// inside the frame.
+    fn scroll_to_cursor(&mut self) {
+        let width = self.frame.size.width;
+        let cursor_offset = self.cursor_visual_offset();
+        if width == 0 {
+            self.scroll_offset = 0;
+        } else if cursor_offset < self.scroll_offset {
+            self.scroll_offset = cursor_offset;
+        } else if cursor_offset >= self.scroll_offset + width {
+            self.scroll_offset = cursor_offset + 1 - width;
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Message {
     CursorLeft,
     CursorRight,
     InsertChar(char),
+    InsertText(String),
     DeleteBackward,
     DeleteForward,
     StartOfLine,
     EndOfLine,
+    BeginSelection,
+    ClearSelection,
+    SelectAll,
+    Submit,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::Size;
+
+    fn new_input(
+        content: &str,
+        cursor: Cursor,
+        width: usize,
+    ) -> (Input, Rc<RefCell<Vec<InputChange>>>) {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let properties = InputProperties {
+            style: InputStyle::default(),
+            content: Rope::from_str(content),
+            cursor,
+            placeholder: None,
+            on_change: Some({
+                let changes = changes.clone();
+                (move |change| changes.borrow_mut().push(change)).into()
+            }),
+            on_submit: None,
+            focused: true,
+        };
+        let mut input = Input {
+            properties,
+            frame: Rect::new(Position::new(0, 0), Size::new(width, 1)),
+            scroll_offset: 0,
+        };
+        input.scroll_to_cursor();
+        (input, changes)
+    }
+
+    fn end_of_buffer(content: &Rope) -> Cursor {
+        let mut cursor = Cursor::new();
+        cursor.move_to_end_of_buffer(content);
+        cursor
+    }
+
+    #[test]
+    fn cursor_kept_in_view() {
+        let content = Rope::from_str("abcdefghij\n");
+        let (mut input, _) = new_input("abcdefghij\n", end_of_buffer(&content), 4);
+        // The cursor is after the last character, in the last column
+        assert_eq!(input.scroll_offset, 7);
+        assert_eq!(input.cursor_position(), Some(Position::new(3, 0)));
+
+        // Moving left within the frame doesn't scroll
+        let mut properties = input.properties.clone();
+        properties.cursor.move_left(&content);
+        properties.cursor.move_left(&content);
+        input.change(properties.clone());
+        assert_eq!(input.scroll_offset, 7);
+        assert_eq!(input.cursor_position(), Some(Position::new(1, 0)));
+
+        // Moving past the left edge scrolls back just enough
+        properties.cursor.move_left(&content);
+        properties.cursor.move_left(&content);
+        input.change(properties.clone());
+        assert_eq!(input.scroll_offset, 6);
+        assert_eq!(input.cursor_position(), Some(Position::new(0, 0)));
+
+        // Widening the frame doesn't scroll
+        input.resize(Rect::new(Position::new(0, 0), Size::new(20, 1)));
+        assert_eq!(input.scroll_offset, 6);
+        properties.cursor.move_to_start_of_buffer(&content);
+        input.change(properties);
+        assert_eq!(input.scroll_offset, 0);
+
+        // Wide graphemes take two columns
+        let content = Rope::from_str("日本語\n");
+        let (input, _) = new_input("日本語\n", end_of_buffer(&content), 4);
+        assert_eq!(input.scroll_offset, 3);
+        assert_eq!(input.cursor_position(), Some(Position::new(3, 0)));
+    }
+
+    #[test]
+    fn delete_selection() {
+        let content = Rope::from_str("hello world\n");
+        let mut cursor = Cursor::new();
+        cursor.begin_selection();
+        cursor.move_right_n(&content, 6);
+
+        for message in [Message::DeleteBackward, Message::DeleteForward] {
+            let (mut input, changes) = new_input("hello world\n", cursor.clone(), 20);
+            input.update(message);
+            let change = changes.borrow_mut().pop().unwrap();
+            assert_eq!(change.content.unwrap().to_string(), "world\n");
+            assert_eq!(change.cursor.range(), &(CharIndex(0)..CharIndex(1)));
+            assert!(!change.cursor.has_selection());
+        }
+
+        // Typing replaces the selection
+        let (mut input, changes) = new_input("hello world\n", cursor, 20);
+        input.update(Message::InsertChar('a'));
+        let change = changes.borrow_mut().pop().unwrap();
+        assert_eq!(change.content.unwrap().to_string(), "aworld\n");
+        assert_eq!(change.cursor.range(), &(CharIndex(1)..CharIndex(2)));
+    }
 }
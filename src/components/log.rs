@@ -0,0 +1,700 @@
+use log::Level;
+use std::{cell::RefCell, cmp, collections::VecDeque, rc::Rc};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    layout::Layout, BindingMatch, BindingTransition, Canvas, Colour, Component, ComponentLink, Key,
+    Line, MouseEvent, MouseEventKind, Position, Rect, ShouldRender, Size, Span, Style, Theme,
+};
+
+struct LogRecord {
+    level: Level,
+    message: String,
+}
+
+struct LogRecords {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    /// The index of the oldest record kept, counting from the first record
+    /// ever pushed.
+    first: u64,
+}
+
+impl LogRecords {
+    /// One past the index of the newest record.
+    fn end(&self) -> u64 {
+        self.first + self.records.len() as u64
+    }
+
+    fn get(&self, index: u64) -> Option<&LogRecord> {
+        index
+            .checked_sub(self.first)
+            .and_then(|offset| self.records.get(offset as usize))
+    }
+}
+
+/// A bounded buffer of log records, shared between the code producing them and
+/// a [`LogView`](struct.LogView.html) showing them. When full, the oldest
+/// record is dropped for each new one.
+///
+/// Cloning the buffer returns a handle to the same records.
+///
+/// ```
+/// # use zi::components::log::LogBuffer;
+/// let buffer = LogBuffer::new(2);
+/// buffer.push(log::Level::Info, "starting");
+/// buffer.push(log::Level::Warn, "disk almost full");
+/// buffer.clone().push(log::Level::Error, "disk full");
+/// assert_eq!(buffer.len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct LogBuffer(Rc<RefCell<LogRecords>>);
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "the capacity of a log buffer must be positive"
+        );
+        Self(Rc::new(RefCell::new(LogRecords {
+            records: VecDeque::with_capacity(capacity),
+            capacity,
+            first: 0,
+        })))
+    }
+
+    pub fn push(&self, level: Level, message: impl Into<String>) {
+        let mut records = self.0.borrow_mut();
+        if records.records.len() == records.capacity {
+            records.records.pop_front();
+            records.first += 1;
+        }
+        records.records.push_back(LogRecord {
+            level,
+            message: message.into(),
+        });
+    }
+
+    pub fn clear(&self) {
+        let mut records = self.0.borrow_mut();
+        records.first = records.end();
+        records.records.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.borrow().records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().records.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.0.borrow().capacity
+    }
+
+    /// The range of indices of the records kept, it changes if and only if
+    /// records are pushed or cleared.
+    fn bounds(&self) -> (u64, u64) {
+        let records = self.0.borrow();
+        (records.first, records.end())
+    }
+}
+
+impl PartialEq for LogBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogViewStyle {
+    pub message: Style,
+    /// Colours of the level tags. Warnings and errors are drawn entirely in
+    /// their colour.
+    pub error: Colour,
+    pub warn: Colour,
+    pub info: Colour,
+    pub debug: Colour,
+    pub trace: Colour,
+    /// Style of the matches of the search query.
+    pub matched: Style,
+    pub status: Style,
+}
+
+impl LogViewStyle {
+    fn level(&self, level: Level) -> Colour {
+        match level {
+            Level::Error => self.error,
+            Level::Warn => self.warn,
+            Level::Info => self.info,
+            Level::Debug => self.debug,
+            Level::Trace => self.trace,
+        }
+    }
+}
+
+impl Default for LogViewStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const DARK1: Colour = Colour::rgb(60, 56, 54);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+        const BRIGHT_GREEN: Colour = Colour::rgb(184, 187, 38);
+        const BRIGHT_YELLOW: Colour = Colour::rgb(250, 189, 47);
+        const BRIGHT_RED: Colour = Colour::rgb(251, 73, 52);
+
+        Self {
+            message: Style::normal(DARK0_SOFT, LIGHT2),
+            error: BRIGHT_RED,
+            warn: BRIGHT_YELLOW,
+            info: BRIGHT_GREEN,
+            debug: BRIGHT_BLUE,
+            trace: GRAY_245,
+            matched: Style::bold(BRIGHT_YELLOW, DARK0_SOFT),
+            status: Style::normal(DARK1, LIGHT2),
+        }
+    }
+}
+
+impl From<&Theme> for LogViewStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            message: theme.background,
+            error: theme.error.foreground,
+            warn: theme.warning.foreground,
+            info: theme.success.foreground,
+            debug: theme.accent.foreground,
+            trace: theme.muted.foreground,
+            matched: Style::bold(theme.warning.foreground, theme.background.background),
+            status: theme.surface,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct LogViewProperties {
+    pub style: LogViewStyle,
+    /// The records shown. The view is redrawn when records were pushed since
+    /// it was last rendered, so the parent needs to re-render after pushing.
+    pub buffer: LogBuffer,
+    /// Whether the view starts following the newest records. Afterwards it's
+    /// toggled by the user.
+    pub follow: bool,
+    /// Whether long records start wrapped. Afterwards it's toggled by the
+    /// user.
+    pub wrap: bool,
+    pub focused: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Backward,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    ScrollUp(usize),
+    ScrollDown(usize),
+    ScrollPageUp,
+    ScrollPageDown,
+    Top,
+    /// Scrolls to the newest record and follows the new ones.
+    Bottom,
+    ToggleFollow,
+    ToggleWrap,
+    /// Starts a search or, if already searching, moves to the next match.
+    Search(Direction),
+    SearchChar(char),
+    SearchBackspace,
+    /// Stops editing the query, leaving the matches highlighted.
+    EndSearch,
+    CancelSearch,
+}
+
+struct Search {
+    query: String,
+    direction: Direction,
+    editing: bool,
+    /// The index of the record containing the current match, `None` if the
+    /// query isn't found.
+    current: Option<u64>,
+}
+
+/// A row of the view, a line of a record or part of it when wrapping.
+struct Row {
+    index: u64,
+    level: Level,
+    /// Whether this is the first row of the record, the one with its level.
+    first: bool,
+    text: String,
+}
+
+/// A view of the records in a [`LogBuffer`](struct.LogBuffer.html), newest at
+/// the bottom.
+///
+/// Only the records on screen are laid out and drawn, the buffer can hold
+/// hundreds of thousands of them. When following, the view stays at the
+/// bottom as records are pushed, otherwise it stays on the same records until
+/// they are dropped from the buffer.
+///
+/// Besides the usual scrolling keys, `f` toggles following, `w` toggles
+/// wrapping and `C-s` and `C-r` search forward and backward incrementally,
+/// like Emacs' isearch.
+pub struct LogView {
+    properties: LogViewProperties,
+    frame: Rect,
+    /// The bounds of the buffer when last rendered.
+    bounds: (u64, u64),
+    following: bool,
+    wrap: bool,
+    /// The index of the first record shown, when not following.
+    top: u64,
+    search: Option<Search>,
+}
+
+impl LogView {
+    fn height(&self) -> usize {
+        self.frame.size.height.saturating_sub(1)
+    }
+
+    fn rows(&self, record: &LogRecord, index: u64) -> Vec<Row> {
+        let width = self.frame.size.width.saturating_sub(LEVEL_WIDTH);
+        let mut texts = Vec::new();
+        for line in record.message.lines() {
+            if !self.wrap || line.width() <= width || width == 0 {
+                texts.push(line);
+                continue;
+            }
+            let (mut start, mut row_width) = (0, 0);
+            for (offset, grapheme) in line.grapheme_indices(true) {
+                let grapheme_width = grapheme.width();
+                if row_width + grapheme_width > width && offset > start {
+                    texts.push(&line[start..offset]);
+                    start = offset;
+                    row_width = 0;
+                }
+                row_width += grapheme_width;
+            }
+            texts.push(&line[start..]);
+        }
+        if texts.is_empty() {
+            texts.push("");
+        }
+        texts
+            .into_iter()
+            .enumerate()
+            .map(|(row_index, text)| Row {
+                index,
+                level: record.level,
+                first: row_index == 0,
+                text: text.to_owned(),
+            })
+            .collect()
+    }
+
+    /// Lays out the rows on screen.
+    fn visible_rows(&self) -> Vec<Row> {
+        let height = self.height();
+        let records = self.properties.buffer.0.borrow();
+        let mut rows = Vec::new();
+        if self.following {
+            let mut index = records.end();
+            while rows.len() < height && index > records.first {
+                index -= 1;
+                if let Some(record) = records.get(index) {
+                    let mut record_rows = self.rows(record, index);
+                    record_rows.append(&mut rows);
+                    rows = record_rows;
+                }
+            }
+            let num_hidden = rows.len().saturating_sub(height);
+            rows.drain(..num_hidden);
+        } else {
+            let mut index = cmp::max(self.top, records.first);
+            while rows.len() < height {
+                match records.get(index) {
+                    Some(record) => rows.extend(self.rows(record, index)),
+                    None => break,
+                }
+                index += 1;
+            }
+            rows.truncate(height);
+        }
+        rows
+    }
+
+    /// Stops following, keeping the same records on screen.
+    fn unfollow(&mut self) {
+        if self.following {
+            self.top = self
+                .visible_rows()
+                .first()
+                .map_or(self.properties.buffer.bounds().0, |row| row.index);
+            self.following = false;
+        }
+    }
+
+    fn scroll_to(&mut self, index: u64) {
+        let (first, end) = self.properties.buffer.bounds();
+        self.following = false;
+        self.top = index.clamp(first, cmp::max(end, first + 1) - 1);
+    }
+
+    /// Finds the next record containing the query, starting with `from`.
+    fn find(&self, query: &str, from: u64, direction: Direction) -> Option<u64> {
+        let records = self.properties.buffer.0.borrow();
+        let contains = |index: &u64| {
+            records
+                .get(*index)
+                .is_some_and(|record| record.message.contains(query))
+        };
+        match direction {
+            Direction::Forward => (cmp::max(from, records.first)..records.end()).find(contains),
+            Direction::Backward => (records.first..cmp::min(from + 1, records.end()))
+                .rev()
+                .find(contains),
+        }
+    }
+
+    /// Searches for the query again, starting with the current match, e.g.
+    /// after it was changed.
+    fn research(&mut self, skip_current: bool) -> ShouldRender {
+        let search = match self.search.as_ref() {
+            Some(search) => search,
+            None => return ShouldRender::No,
+        };
+        let (first, end) = self.properties.buffer.bounds();
+        let from = match (search.current, search.direction, skip_current) {
+            (Some(current), Direction::Forward, true) => current + 1,
+            (Some(current), Direction::Backward, true) => match current.checked_sub(1) {
+                Some(from) => from,
+                None => return ShouldRender::No,
+            },
+            (Some(current), _, false) => current,
+            (None, Direction::Forward, _) if self.following => first,
+            (None, Direction::Forward, _) => self.top,
+            (None, Direction::Backward, _) if self.following => end.saturating_sub(1),
+            // From the last record on screen
+            (None, Direction::Backward, _) => (self.top + self.height() as u64).saturating_sub(1),
+        };
+        let found = self.find(&search.query, from, search.direction);
+        if let Some(index) = found {
+            self.scroll_to(index);
+        }
+        if let Some(search) = self.search.as_mut() {
+            search.current = found.or(if skip_current { search.current } else { None });
+        }
+        ShouldRender::Yes
+    }
+
+    fn draw_row(&self, canvas: &mut Canvas, y: usize, row: &Row) {
+        let style = &self.properties.style;
+        let row_style = if row.level <= Level::Warn {
+            Style {
+                foreground: style.level(row.level),
+                ..style.message
+            }
+        } else {
+            style.message
+        };
+        if row.first {
+            let tag_style = Style::bold(style.message.background, style.level(row.level));
+            canvas.draw_str(0, y, tag_style, LEVEL_TAGS[row.level as usize - 1]);
+        }
+
+        let query = self
+            .search
+            .as_ref()
+            .map(|search| search.query.as_str())
+            .filter(|query| !query.is_empty());
+        let mut line = Line::new();
+        let mut start = 0;
+        if let Some(query) = query {
+            for (offset, matched) in row.text.match_indices(query) {
+                line.push(Span::new(&row.text[start..offset], row_style));
+                line.push(Span::new(matched, style.matched));
+                start = offset + matched.len();
+            }
+        }
+        line.push(Span::new(&row.text[start..], row_style));
+        canvas.draw_line(LEVEL_WIDTH, y, &line);
+    }
+
+    fn draw_status(&self, canvas: &mut Canvas, y: usize) {
+        let style = self.properties.style.status;
+        canvas.clear_region(
+            Rect::new(Position::new(0, y), Size::new(self.frame.size.width, 1)),
+            style,
+        );
+        let mut status = format!(" {} records", self.properties.buffer.len());
+        if self.following {
+            status.push_str("  following");
+        }
+        if self.wrap {
+            status.push_str("  wrapping");
+        }
+        if let Some(search) = self.search.as_ref() {
+            status.push_str(match (search.current, search.direction) {
+                (None, _) => "  failing I-search",
+                (Some(_), Direction::Forward) => "  I-search",
+                (Some(_), Direction::Backward) => "  I-search backward",
+            });
+            status.push_str(": ");
+            status.push_str(&search.query);
+        }
+        canvas.draw_str(0, y, style, &status);
+    }
+}
+
+impl Component for LogView {
+    type Message = Message;
+    type Properties = LogViewProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        let bounds = properties.buffer.bounds();
+        Self {
+            following: properties.follow,
+            wrap: properties.wrap,
+            top: bounds.0,
+            bounds,
+            properties,
+            frame,
+            search: None,
+        }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        let bounds = properties.buffer.bounds();
+        if self.properties != properties || self.bounds != bounds {
+            if properties.buffer != self.properties.buffer {
+                self.top = bounds.0;
+                self.search = None;
+            }
+            self.properties = properties;
+            self.bounds = bounds;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let first = self.properties.buffer.bounds().0;
+        let page = cmp::max(self.height(), 1);
+        match message {
+            Message::ScrollUp(num_records) => {
+                self.unfollow();
+                self.scroll_to(cmp::max(self.top, first).saturating_sub(num_records as u64));
+            }
+            Message::ScrollDown(num_records) => {
+                if self.following {
+                    return ShouldRender::No;
+                }
+                self.scroll_to(self.top + num_records as u64);
+            }
+            Message::ScrollPageUp => return self.update(Message::ScrollUp(page)),
+            Message::ScrollPageDown => return self.update(Message::ScrollDown(page)),
+            Message::Top => self.scroll_to(first),
+            Message::Bottom => self.following = true,
+            Message::ToggleFollow if self.following => self.unfollow(),
+            Message::ToggleFollow => self.following = true,
+            Message::ToggleWrap => self.wrap = !self.wrap,
+            Message::Search(direction) => match self.search.as_mut() {
+                Some(search) if search.editing || search.direction != direction => {
+                    search.editing = true;
+                    search.direction = direction;
+                    return self.research(true);
+                }
+                _ => {
+                    self.search = Some(Search {
+                        query: String::new(),
+                        direction,
+                        editing: true,
+                        current: None,
+                    });
+                }
+            },
+            Message::SearchChar(character) => {
+                if let Some(search) = self.search.as_mut() {
+                    search.query.push(character);
+                }
+                return self.research(false);
+            }
+            Message::SearchBackspace => {
+                if let Some(search) = self.search.as_mut() {
+                    search.query.pop();
+                    search.current = None;
+                }
+                return self.research(false);
+            }
+            Message::EndSearch => {
+                if let Some(search) = self.search.as_mut() {
+                    search.editing = false;
+                }
+            }
+            Message::CancelSearch => self.search = None,
+        }
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(self.properties.style.message);
+        for (y, row) in self.visible_rows().iter().enumerate() {
+            self.draw_row(&mut canvas, y, row);
+        }
+        if self.frame.size.height > 0 {
+            self.draw_status(&mut canvas, self.frame.size.height - 1);
+        }
+        canvas.into()
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let editing = self.search.as_ref().is_some_and(|search| search.editing);
+        let message = match pressed {
+            [Key::Ctrl('s')] => Some(Message::Search(Direction::Forward)),
+            [Key::Ctrl('r')] => Some(Message::Search(Direction::Backward)),
+            [Key::Ctrl('g')] | [Key::Esc] if self.search.is_some() => Some(Message::CancelSearch),
+            [Key::Char('\n')] if editing => Some(Message::EndSearch),
+            [Key::Backspace] if editing => Some(Message::SearchBackspace),
+            [Key::Char(character)] if editing => Some(Message::SearchChar(*character)),
+            [Key::Ctrl('n')] | [Key::Down] => Some(Message::ScrollDown(1)),
+            [Key::Ctrl('p')] | [Key::Up] => Some(Message::ScrollUp(1)),
+            [Key::Ctrl('v')] | [Key::PageDown] => Some(Message::ScrollPageDown),
+            [Key::Alt('v')] | [Key::PageUp] => Some(Message::ScrollPageUp),
+            [Key::Alt('<')] | [Key::Home] => Some(Message::Top),
+            [Key::Alt('>')] | [Key::End] => Some(Message::Bottom),
+            [Key::Char('f')] => Some(Message::ToggleFollow),
+            [Key::Char('w')] => Some(Message::ToggleWrap),
+            _ => None,
+        };
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message,
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::ScrollUp => Some(Message::ScrollUp(3)),
+            MouseEventKind::ScrollDown => Some(Message::ScrollDown(3)),
+            _ => None,
+        }
+    }
+}
+
+const LEVEL_TAGS: [&str; 5] = ["ERROR", "WARN ", "INFO ", "DEBUG", "TRACE"];
+/// The width of the level tags and the space after them.
+const LEVEL_WIDTH: usize = 6;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_view(buffer: &LogBuffer, width: usize, height: usize, wrap: bool) -> LogView {
+        let properties = LogViewProperties {
+            style: LogViewStyle::default(),
+            buffer: buffer.clone(),
+            follow: true,
+            wrap,
+            focused: true,
+        };
+        let frame = Rect::new(Position::zero(), Size::new(LEVEL_WIDTH + width, height + 1));
+        LogView::create(properties, frame, ComponentLink::detached())
+    }
+
+    fn texts(view: &LogView) -> Vec<String> {
+        view.visible_rows()
+            .into_iter()
+            .map(|row| row.text)
+            .collect()
+    }
+
+    fn buffer(capacity: usize, messages: &[&str]) -> LogBuffer {
+        let buffer = LogBuffer::new(capacity);
+        for message in messages {
+            buffer.push(Level::Info, *message);
+        }
+        buffer
+    }
+
+    #[test]
+    fn follow_and_scroll() {
+        let buffer = buffer(5, &["0", "1", "2", "3", "4", "5", "6"]);
+        let mut view = log_view(&buffer, 4, 3, false);
+        assert_eq!(texts(&view), ["4", "5", "6"]);
+
+        // Scrolling up stops following, the view then stays on the same
+        // records as new ones are pushed
+        view.update(Message::ScrollUp(1));
+        assert!(!view.following);
+        assert_eq!(texts(&view), ["3", "4", "5"]);
+        buffer.push(Level::Info, "7");
+        assert_eq!(texts(&view), ["3", "4", "5"]);
+        // Until they are dropped from the buffer
+        buffer.push(Level::Info, "8");
+        buffer.push(Level::Info, "9");
+        assert_eq!(texts(&view), ["5", "6", "7"]);
+
+        view.update(Message::Bottom);
+        assert_eq!(texts(&view), ["7", "8", "9"]);
+        view.update(Message::Top);
+        assert_eq!(texts(&view), ["5", "6", "7"]);
+    }
+
+    #[test]
+    fn wrap_long_records() {
+        let buffer = buffer(5, &["abcdefghij"]);
+        let mut view = log_view(&buffer, 4, 2, true);
+        // The newest rows are shown when following, even if part of a record
+        assert_eq!(texts(&view), ["efgh", "ij"]);
+        view.update(Message::ScrollUp(0));
+        assert_eq!(texts(&view), ["abcd", "efgh"]);
+        view.update(Message::ToggleWrap);
+        assert_eq!(texts(&view), ["abcdefghij"]);
+    }
+
+    #[test]
+    fn incremental_search() {
+        let buffer = buffer(10, &["apple", "banana", "cherry", "banana split"]);
+        let mut view = log_view(&buffer, 20, 2, false);
+        let current = |view: &LogView| view.search.as_ref().and_then(|search| search.current);
+
+        view.update(Message::Search(Direction::Forward));
+        view.update(Message::SearchChar('b'));
+        assert_eq!(current(&view), Some(1));
+        assert_eq!(texts(&view), ["banana", "cherry"]);
+        // Extending the query keeps the match if it still matches
+        view.update(Message::SearchChar('a'));
+        assert_eq!(current(&view), Some(1));
+
+        // Searching again moves to the next match, staying on the last one
+        view.update(Message::Search(Direction::Forward));
+        assert_eq!(current(&view), Some(3));
+        view.update(Message::Search(Direction::Forward));
+        assert_eq!(current(&view), Some(3));
+        view.update(Message::Search(Direction::Backward));
+        assert_eq!(current(&view), Some(1));
+
+        view.update(Message::SearchChar('x'));
+        assert_eq!(current(&view), None);
+        // Searches again from the records on screen
+        view.update(Message::SearchBackspace);
+        assert_eq!(current(&view), Some(1));
+        view.update(Message::CancelSearch);
+        assert!(view.search.is_none());
+    }
+}
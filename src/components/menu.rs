@@ -0,0 +1,820 @@
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    layout::{self, Layer, Layout},
+    BindingMatch, BindingTransition, Callback, Canvas, Colour, Component, ComponentExt,
+    ComponentLink, Key, MouseButton, MouseEvent, MouseEventKind, Position, Rect, ShouldRender,
+    Size, Style, Theme,
+};
+
+/// An entry in a menu.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MenuItem {
+    Action {
+        /// Passed to `on_activate` when the action is chosen.
+        id: String,
+        label: String,
+        /// Describes the key binding of the action, e.g. `C-x C-s`. Only
+        /// displayed, the action isn't triggered by it.
+        accelerator: Option<String>,
+    },
+    Submenu {
+        label: String,
+        items: Vec<MenuItem>,
+    },
+    Separator,
+}
+
+impl MenuItem {
+    pub fn action(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::Action {
+            id: id.into(),
+            label: label.into(),
+            accelerator: None,
+        }
+    }
+
+    pub fn submenu(label: impl Into<String>, items: Vec<MenuItem>) -> Self {
+        Self::Submenu {
+            label: label.into(),
+            items,
+        }
+    }
+
+    /// Sets the accelerator of an action. Does nothing for other items.
+    pub fn accelerator(mut self, accelerator: impl Into<String>) -> Self {
+        if let Self::Action {
+            accelerator: ref mut current,
+            ..
+        } = self
+        {
+            *current = Some(accelerator.into());
+        }
+        self
+    }
+
+    fn is_separator(&self) -> bool {
+        matches!(self, Self::Separator)
+    }
+}
+
+/// A top level menu of a [`MenuBar`](struct.MenuBar.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Menu {
+    pub title: String,
+    pub items: Vec<MenuItem>,
+}
+
+impl Menu {
+    pub fn new(title: impl Into<String>, items: Vec<MenuItem>) -> Self {
+        Self {
+            title: title.into(),
+            items,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MenuStyle {
+    pub bar: Style,
+    /// Style of the title of the open (or focused) menu in the bar.
+    pub title: Style,
+    pub item: Style,
+    pub highlighted: Style,
+    /// Style of accelerators, submenu arrows and separators.
+    pub secondary: Style,
+}
+
+impl Default for MenuStyle {
+    fn default() -> Self {
+        const DARK0: Colour = Colour::rgb(40, 40, 40);
+        const DARK1: Colour = Colour::rgb(60, 56, 54);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+
+        Self {
+            bar: Style::normal(DARK0, LIGHT2),
+            title: Style::bold(BRIGHT_BLUE, DARK0),
+            item: Style::normal(DARK1, LIGHT2),
+            highlighted: Style::normal(BRIGHT_BLUE, DARK0),
+            secondary: Style::normal(DARK1, GRAY_245),
+        }
+    }
+}
+
+impl From<&Theme> for MenuStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            bar: theme.background,
+            title: Style::bold(theme.accent.foreground, theme.background.background),
+            item: theme.surface,
+            highlighted: theme.selection,
+            secondary: Style::normal(theme.surface.background, theme.muted.foreground),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    NextItem,
+    PreviousItem,
+    /// Opens the highlighted submenu, or for a menu bar, the next menu.
+    Right,
+    /// Closes the innermost submenu, or for a menu bar, opens the previous
+    /// menu.
+    Left,
+    /// Opens the highlighted submenu or activates the highlighted action.
+    Enter,
+    /// Closes the innermost submenu, or the menu.
+    Back,
+    /// Clicked the item with the given index, in the given level of nesting.
+    Click(usize, usize),
+    /// Moved the pointer over the item with the given index, in the given
+    /// level of nesting.
+    Hover(usize, usize),
+    /// Clicked or pressed enter on the title of a menu in the bar.
+    ToggleMenu(usize),
+}
+
+/// The highlighted item in each level of nested submenus.
+#[derive(Debug, Default)]
+struct Navigation {
+    path: Vec<usize>,
+}
+
+impl Navigation {
+    fn open(&mut self, items: &[MenuItem]) {
+        self.path = vec![next_selectable(items, 0, 1).unwrap_or(0)];
+    }
+
+    fn level_items<'a>(&self, mut items: &'a [MenuItem], level: usize) -> &'a [MenuItem] {
+        for &index in &self.path[..level] {
+            if let Some(MenuItem::Submenu {
+                items: ref submenu, ..
+            }) = items.get(index)
+            {
+                items = submenu;
+            }
+        }
+        items
+    }
+
+    fn highlighted<'a>(&self, items: &'a [MenuItem]) -> Option<&'a MenuItem> {
+        let level = self.path.len().checked_sub(1)?;
+        self.level_items(items, level).get(self.path[level])
+    }
+
+    fn move_by(&mut self, items: &[MenuItem], delta: isize) {
+        if let Some(level) = self.path.len().checked_sub(1) {
+            let level_items = self.level_items(items, level);
+            let start = self.path[level] as isize + delta;
+            if let Some(index) = next_selectable(level_items, start, delta) {
+                self.path[level] = index;
+            }
+        }
+    }
+
+    /// Opens the highlighted submenu, returns whether there was one.
+    fn open_submenu(&mut self, items: &[MenuItem]) -> bool {
+        match self.highlighted(items) {
+            Some(MenuItem::Submenu {
+                items: ref submenu, ..
+            }) => {
+                self.path.push(next_selectable(submenu, 0, 1).unwrap_or(0));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Opens the highlighted submenu or returns the id of the highlighted action.
+    fn enter(&mut self, items: &[MenuItem]) -> Option<String> {
+        match self.highlighted(items) {
+            Some(MenuItem::Action { ref id, .. }) => Some(id.clone()),
+            _ => {
+                self.open_submenu(items);
+                None
+            }
+        }
+    }
+
+    /// Closes the innermost submenu, returns false if there was none.
+    fn back(&mut self) -> bool {
+        if self.path.len() > 1 {
+            self.path.pop();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn highlight(&mut self, level: usize, index: usize) {
+        self.path.truncate(level + 1);
+        if let Some(highlighted) = self.path.get_mut(level) {
+            *highlighted = index;
+        }
+    }
+
+    /// The overlay layers with the open menu and submenus, the first one at
+    /// `anchor`.
+    fn layers(
+        &self,
+        style: &MenuStyle,
+        items: &[MenuItem],
+        anchor: Position,
+        link: &ComponentLink<impl Component<Message = Message>>,
+    ) -> Vec<Layer> {
+        let mut origin = anchor;
+        let mut layers = Vec::with_capacity(self.path.len());
+        for (level, &highlighted) in self.path.iter().enumerate() {
+            let level_items = self.level_items(items, level);
+            let size = Size::new(popup_width(level_items), level_items.len());
+            layers.push(Layer::at(
+                Rect::new(origin, size),
+                MenuPopup::with(MenuPopupProperties {
+                    style: style.clone(),
+                    items: level_items.to_vec(),
+                    highlighted,
+                    on_click: link.callback(move |index| Message::Click(level, index)),
+                    on_hover: link.callback(move |index| Message::Hover(level, index)),
+                }),
+            ));
+            origin = Position::new(origin.x + size.width, origin.y + highlighted);
+        }
+        layers
+    }
+}
+
+/// The first item that isn't a separator, starting at `start` and moving by
+/// `delta`.
+fn next_selectable(items: &[MenuItem], start: isize, delta: isize) -> Option<usize> {
+    let mut index = start;
+    while index >= 0 && (index as usize) < items.len() {
+        if !items[index as usize].is_separator() {
+            return Some(index as usize);
+        }
+        index += delta;
+    }
+    None
+}
+
+fn popup_width(items: &[MenuItem]) -> usize {
+    items
+        .iter()
+        .map(|item| match item {
+            MenuItem::Action {
+                label, accelerator, ..
+            } => label.width() + accelerator.as_ref().map_or(0, |text| text.width() + 2),
+            MenuItem::Submenu { label, .. } => label.width() + 3,
+            MenuItem::Separator => 0,
+        })
+        .max()
+        .unwrap_or(0)
+        + 2
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuBarProperties {
+    pub style: MenuStyle,
+    pub menus: Vec<Menu>,
+    pub focused: bool,
+    /// Called with the id of the activated action.
+    pub on_activate: Option<Callback<String>>,
+}
+
+/// A horizontal bar of menus with nested submenus, which open in overlay
+/// layers below the bar.
+///
+/// When focused, enter or down opens the highlighted menu, the arrow keys
+/// navigate and escape closes the innermost submenu.
+pub struct MenuBar {
+    properties: MenuBarProperties,
+    frame: Rect,
+    link: ComponentLink<Self>,
+    /// The highlighted menu in the bar.
+    current: usize,
+    open: bool,
+    navigation: Navigation,
+}
+
+impl MenuBar {
+    fn title_x(&self, menu: usize) -> usize {
+        self.properties.menus[..menu]
+            .iter()
+            .map(|menu| menu.title.width() + 2)
+            .sum()
+    }
+
+    fn items(&self) -> &[MenuItem] {
+        self.properties
+            .menus
+            .get(self.current)
+            .map_or(&[], |menu| &menu.items)
+    }
+
+    fn open_menu(&mut self, menu: usize) {
+        self.current = menu;
+        self.open = true;
+        let mut navigation = Navigation::default();
+        navigation.open(self.items());
+        self.navigation = navigation;
+    }
+
+    fn activate(&mut self, id: String) {
+        self.open = false;
+        if let Some(on_activate) = self.properties.on_activate.as_ref() {
+            on_activate.emit(id);
+        }
+    }
+}
+
+impl Component for MenuBar {
+    type Message = Message;
+    type Properties = MenuBarProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, link: ComponentLink<Self>) -> Self {
+        Self {
+            properties,
+            frame,
+            link,
+            current: 0,
+            open: false,
+            navigation: Navigation::default(),
+        }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            if self.properties.menus != properties.menus {
+                self.open = false;
+                self.current = 0;
+            }
+            self.properties = properties;
+            if !self.properties.focused {
+                self.open = false;
+            }
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let num_menus = self.properties.menus.len();
+        if num_menus == 0 {
+            return ShouldRender::No;
+        }
+        let next_menu = (self.current + 1) % num_menus;
+        let previous_menu = (self.current + num_menus - 1) % num_menus;
+        match message {
+            Message::ToggleMenu(menu) if self.open && menu == self.current => self.open = false,
+            Message::ToggleMenu(menu) => self.open_menu(menu),
+            Message::NextItem | Message::Enter if !self.open => self.open_menu(self.current),
+            Message::Right if !self.open => self.current = next_menu,
+            Message::Left if !self.open => self.current = previous_menu,
+            Message::PreviousItem | Message::Back | Message::Click(..) | Message::Hover(..)
+                if !self.open => {}
+            Message::NextItem => {
+                let items = &self.properties.menus[self.current].items;
+                self.navigation.move_by(items, 1);
+            }
+            Message::PreviousItem => {
+                let items = &self.properties.menus[self.current].items;
+                self.navigation.move_by(items, -1);
+            }
+            Message::Right => {
+                let items = &self.properties.menus[self.current].items;
+                if !self.navigation.open_submenu(items) {
+                    self.open_menu(next_menu);
+                }
+            }
+            Message::Left => {
+                if !self.navigation.back() {
+                    self.open_menu(previous_menu);
+                }
+            }
+            Message::Enter => {
+                let items = &self.properties.menus[self.current].items;
+                if let Some(id) = self.navigation.enter(items) {
+                    self.activate(id);
+                }
+            }
+            Message::Back => {
+                if !self.navigation.back() {
+                    self.open = false;
+                }
+            }
+            Message::Click(level, index) => {
+                self.navigation.highlight(level, index);
+                let items = &self.properties.menus[self.current].items;
+                if let Some(id) = self.navigation.enter(items) {
+                    self.activate(id);
+                }
+            }
+            Message::Hover(level, index) => {
+                if self.navigation.path.get(level) == Some(&index) {
+                    return ShouldRender::No;
+                }
+                self.navigation.highlight(level, index);
+            }
+        }
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        let MenuBarProperties {
+            ref style,
+            ref menus,
+            focused,
+            ..
+        } = self.properties;
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style.bar);
+        let mut x = 0;
+        for (index, menu) in menus.iter().enumerate() {
+            let title_style = if index == self.current && (self.open || focused) {
+                style.title
+            } else {
+                style.bar
+            };
+            x += canvas.draw_str(x, 0, title_style, " ");
+            x += canvas.draw_str(x, 0, title_style, &menu.title);
+            x += canvas.draw_str(x, 0, title_style, " ");
+        }
+
+        if !self.open {
+            return canvas.into();
+        }
+        let anchor = Position::new(self.title_x(self.current), 1);
+        layout::overlay(
+            canvas.into(),
+            self.navigation
+                .layers(style, self.items(), anchor, &self.link),
+        )
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message: menu_binding(pressed, self.open),
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::Press(MouseButton::Left) if event.position.y == 0 => {
+                (0..self.properties.menus.len())
+                    .find(|&menu| {
+                        let start = self.title_x(menu);
+                        let end = start + self.properties.menus[menu].title.width() + 2;
+                        (start..end).contains(&event.position.x)
+                    })
+                    .map(Message::ToggleMenu)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextMenuProperties {
+    pub style: MenuStyle,
+    pub items: Vec<MenuItem>,
+    /// Where the menu opens, relative to the frame of the component.
+    pub position: Position,
+    pub focused: bool,
+    /// Called with the id of the activated action.
+    pub on_activate: Option<Callback<String>>,
+    /// Called when the menu should be closed, after an action was activated
+    /// or when escape was pressed.
+    pub on_close: Option<Callback<()>>,
+}
+
+/// A popup menu with nested submenus, e.g. shown on right click.
+///
+/// The component doesn't draw anything in its own frame, only the popups in
+/// overlay layers. It's meant to be laid out over the area `position` is
+/// relative to, for instance as a [`Layer`](../../layout/struct.Layer.html)
+/// covering the parent.
+pub struct ContextMenu {
+    properties: ContextMenuProperties,
+    link: ComponentLink<Self>,
+    navigation: Navigation,
+}
+
+impl ContextMenu {
+    fn close(&self) {
+        if let Some(on_close) = self.properties.on_close.as_ref() {
+            on_close.emit(());
+        }
+    }
+
+    fn activate(&self, id: String) {
+        if let Some(on_activate) = self.properties.on_activate.as_ref() {
+            on_activate.emit(id);
+        }
+        self.close();
+    }
+}
+
+impl Component for ContextMenu {
+    type Message = Message;
+    type Properties = ContextMenuProperties;
+
+    fn create(properties: Self::Properties, _frame: Rect, link: ComponentLink<Self>) -> Self {
+        let mut navigation = Navigation::default();
+        navigation.open(&properties.items);
+        Self {
+            properties,
+            link,
+            navigation,
+        }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            if self.properties.items != properties.items {
+                self.navigation.open(&properties.items);
+            }
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let items = &self.properties.items;
+        match message {
+            Message::NextItem => self.navigation.move_by(items, 1),
+            Message::PreviousItem => self.navigation.move_by(items, -1),
+            Message::Right => {
+                self.navigation.open_submenu(items);
+            }
+            Message::Left => {
+                self.navigation.back();
+            }
+            Message::Enter | Message::ToggleMenu(_) => {
+                if let Some(id) = self.navigation.enter(items) {
+                    self.activate(id);
+                }
+            }
+            Message::Back => {
+                if !self.navigation.back() {
+                    self.close();
+                }
+            }
+            Message::Click(level, index) => {
+                self.navigation.highlight(level, index);
+                if let Some(id) = self.navigation.enter(items) {
+                    self.activate(id);
+                }
+            }
+            Message::Hover(level, index) => {
+                if self.navigation.path.get(level) == Some(&index) {
+                    return ShouldRender::No;
+                }
+                self.navigation.highlight(level, index);
+            }
+        }
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        layout::overlay(
+            layout::column([]),
+            self.navigation.layers(
+                &self.properties.style,
+                &self.properties.items,
+                self.properties.position,
+                &self.link,
+            ),
+        )
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message: menu_binding(pressed, true),
+        }
+    }
+}
+
+fn menu_binding(pressed: &[Key], open: bool) -> Option<Message> {
+    match pressed {
+        [Key::Ctrl('n')] | [Key::Down] => Some(Message::NextItem),
+        [Key::Ctrl('p')] | [Key::Up] if open => Some(Message::PreviousItem),
+        [Key::Ctrl('f')] | [Key::Right] => Some(Message::Right),
+        [Key::Ctrl('b')] | [Key::Left] => Some(Message::Left),
+        [Key::Char('\n')] | [Key::F(10)] => Some(Message::Enter),
+        [Key::Esc] | [Key::Ctrl('g')] if open => Some(Message::Back),
+        _ => None,
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct MenuPopupProperties {
+    style: MenuStyle,
+    items: Vec<MenuItem>,
+    highlighted: usize,
+    on_click: Callback<usize>,
+    on_hover: Callback<usize>,
+}
+
+/// One level of an open menu. It's a separate component so that it receives
+/// the mouse events in its layer.
+struct MenuPopup {
+    properties: MenuPopupProperties,
+    frame: Rect,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PopupMessage {
+    Click(usize),
+    Hover(usize),
+}
+
+impl Component for MenuPopup {
+    type Message = PopupMessage;
+    type Properties = MenuPopupProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties, frame }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        match message {
+            PopupMessage::Click(index) => self.properties.on_click.emit(index),
+            PopupMessage::Hover(index) => self.properties.on_hover.emit(index),
+        }
+        ShouldRender::No
+    }
+
+    fn view(&self) -> Layout {
+        let MenuPopupProperties {
+            ref style,
+            ref items,
+            highlighted,
+            ..
+        } = self.properties;
+        let width = self.frame.size.width;
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style.item);
+
+        for (y, item) in items.iter().enumerate() {
+            let (label, secondary) = match item {
+                MenuItem::Action {
+                    label, accelerator, ..
+                } => (label.as_str(), accelerator.as_deref().unwrap_or("")),
+                MenuItem::Submenu { label, .. } => (label.as_str(), "▸"),
+                MenuItem::Separator => {
+                    canvas.draw_str(0, y, style.secondary, &"─".repeat(width));
+                    continue;
+                }
+            };
+            let (item_style, secondary_style) = if y == highlighted {
+                canvas.clear_region(
+                    Rect::new(Position::new(0, y), Size::new(width, 1)),
+                    style.highlighted,
+                );
+                (style.highlighted, style.highlighted)
+            } else {
+                (style.item, style.secondary)
+            };
+            canvas.draw_str(1, y, item_style, label);
+            let x = width.saturating_sub(secondary.width() + 1);
+            canvas.draw_str(x, y, secondary_style, secondary);
+        }
+
+        canvas.into()
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        let index = event.position.y;
+        if self
+            .properties
+            .items
+            .get(index)
+            .is_none_or(MenuItem::is_separator)
+        {
+            return None;
+        }
+        match event.kind {
+            MouseEventKind::Press(MouseButton::Left) => Some(PopupMessage::Click(index)),
+            MouseEventKind::Move => Some(PopupMessage::Hover(index)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    #[test]
+    fn submenu_navigation() {
+        let activated = Rc::new(RefCell::new(Vec::new()));
+        let properties = MenuBarProperties {
+            style: MenuStyle::default(),
+            menus: vec![
+                Menu::new(
+                    "File",
+                    vec![
+                        MenuItem::action("new", "New"),
+                        MenuItem::Separator,
+                        MenuItem::submenu(
+                            "Recent",
+                            vec![
+                                MenuItem::action("one", "One"),
+                                MenuItem::Separator,
+                                MenuItem::action("two", "Two"),
+                            ],
+                        ),
+                        MenuItem::action("quit", "Quit"),
+                    ],
+                ),
+                Menu::new("Edit", vec![MenuItem::action("undo", "Undo")]),
+            ],
+            focused: true,
+            on_activate: Some({
+                let activated = activated.clone();
+                (move |id| activated.borrow_mut().push(id)).into()
+            }),
+        };
+        let frame = Rect::new(Position::new(0, 0), Size::new(20, 1));
+        let mut bar = MenuBar::create(properties, frame, ComponentLink::detached());
+
+        // Separators are skipped, right opens the highlighted submenu and
+        // left closes it
+        bar.update(Message::Enter);
+        assert!(bar.open);
+        assert_eq!(bar.navigation.path, [0]);
+        bar.update(Message::NextItem);
+        assert_eq!(bar.navigation.path, [2]);
+        bar.update(Message::Right);
+        assert_eq!(bar.navigation.path, [2, 0]);
+        bar.update(Message::NextItem);
+        assert_eq!(bar.navigation.path, [2, 2]);
+        bar.update(Message::NextItem);
+        assert_eq!(bar.navigation.path, [2, 2]);
+        bar.update(Message::Left);
+        assert_eq!(bar.navigation.path, [2]);
+
+        // Hovering over the parent menu closes the submenus below it
+        bar.update(Message::Enter);
+        assert_eq!(bar.navigation.path, [2, 0]);
+        bar.update(Message::Hover(0, 3));
+        assert_eq!(bar.navigation.path, [3]);
+
+        // Right on an action and left at the top level switch menus
+        bar.update(Message::Right);
+        assert_eq!((bar.current, bar.navigation.path.as_slice()), (1, &[0][..]));
+        bar.update(Message::Left);
+        assert_eq!((bar.current, bar.navigation.path.as_slice()), (0, &[0][..]));
+
+        // Clicking an item in a submenu activates it and closes the menu
+        bar.update(Message::Click(0, 2));
+        assert_eq!(bar.navigation.path, [2, 0]);
+        bar.update(Message::Click(1, 2));
+        assert_eq!(*activated.borrow(), ["two"]);
+        assert!(!bar.open);
+
+        // Back closes the innermost submenu, then the menu
+        bar.update(Message::ToggleMenu(0));
+        bar.update(Message::Click(0, 2));
+        bar.update(Message::Back);
+        assert_eq!(bar.navigation.path, [2]);
+        bar.update(Message::Back);
+        assert!(!bar.open);
+    }
+}
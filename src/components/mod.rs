@@ -1,6 +1,27 @@
 //! A collection of reusable components useful as building blocks.
 
 pub mod border;
+pub mod calendar;
+pub mod chart;
+pub mod checkbox;
+pub mod code;
+pub mod drawing;
+pub mod dropdown;
+pub mod image;
 pub mod input;
+pub mod log;
+pub mod menu;
+pub mod paragraph;
+pub mod picker;
+pub mod progress;
+pub mod radio;
+pub mod scrollbar;
 pub mod select;
+pub mod spinner;
+pub mod split;
+pub mod table;
+pub mod tabs;
 pub mod text;
+pub mod textarea;
+pub mod tree;
+pub mod viewport;
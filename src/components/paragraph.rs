@@ -0,0 +1,389 @@
+use std::mem;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use super::text::{TextAlign, TextWrap};
+use crate::{layout::Layout, Canvas, Component, ComponentLink, Line, Rect, ShouldRender, Style};
+
+const ELLIPSIS: &str = "…";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParagraphProperties {
+    /// Style of the empty cells and of the ellipsis.
+    pub style: Style,
+    /// The lines of the paragraph. Newlines in the spans also start new
+    /// lines.
+    pub content: Vec<Line>,
+    pub align: TextAlign,
+    pub wrap: TextWrap,
+    /// Whether to end the last visible line with an ellipsis when the text
+    /// doesn't fit.
+    pub ellipsis: bool,
+}
+
+impl ParagraphProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn content(mut self, content: impl IntoIterator<Item = Line>) -> Self {
+        self.content = content.into_iter().collect();
+        self
+    }
+
+    /// Appends a line to the content.
+    pub fn line(mut self, line: impl Into<Line>) -> Self {
+        self.content.push(line.into());
+        self
+    }
+
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn wrap(mut self, wrap: TextWrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn ellipsis(mut self, ellipsis: bool) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+}
+
+/// A block of styled text, wrapped at word boundaries and aligned to the
+/// width of the component.
+///
+/// Long words are broken when wrapping. Unless wrapping, lines wider than the
+/// component are cut off, like the lines past its height.
+pub struct Paragraph {
+    properties: ParagraphProperties,
+    frame: Rect,
+}
+
+impl Component for Paragraph {
+    type Message = ();
+    type Properties = ParagraphProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties, frame }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        if self.frame != frame {
+            self.frame = frame;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn view(&self) -> Layout {
+        self.draw().into()
+    }
+}
+
+impl Paragraph {
+    fn draw(&self) -> Canvas {
+        let ParagraphProperties {
+            style,
+            ref content,
+            align,
+            wrap,
+            ellipsis,
+        } = self.properties;
+        let size = self.frame.size;
+        let mut canvas = Canvas::new(size);
+        canvas.clear(style);
+
+        // Every row is tagged with whether it ends a line, those aren't
+        // justified
+        let rows: Vec<(Vec<Token>, bool)> = tokenize(content)
+            .into_iter()
+            .flat_map(|line| {
+                let rows = match wrap {
+                    TextWrap::None => vec![line],
+                    TextWrap::Word => wrap_line(line, size.width),
+                };
+                let num_rows = rows.len();
+                rows.into_iter()
+                    .enumerate()
+                    .map(move |(index, row)| (row, index + 1 == num_rows))
+            })
+            .collect();
+
+        let overflows = rows.len() > size.height;
+        for (y, (row, ends_line)) in rows.iter().take(size.height).enumerate() {
+            let last = y + 1 == size.height;
+            let ellipsis = ellipsis && (last && overflows || row_width(row) > size.width);
+            let row = DrawRow {
+                tokens: row,
+                align,
+                justify: align == TextAlign::Justify && !ends_line && !ellipsis,
+                ellipsis,
+            };
+            row.draw(&mut canvas, y, style);
+        }
+
+        canvas
+    }
+}
+
+/// A word or a run of whitespace, made of pieces of spans with their style.
+#[derive(Clone, Debug, Default)]
+struct Token {
+    pieces: Vec<(String, Style)>,
+    width: usize,
+    space: bool,
+}
+
+impl Token {
+    fn push(&mut self, grapheme: &str, style: Style) {
+        // Whitespace is drawn as spaces, one per grapheme
+        let grapheme = if self.space { " " } else { grapheme };
+        match self.pieces.last_mut() {
+            Some((text, piece_style)) if *piece_style == style => text.push_str(grapheme),
+            _ => self.pieces.push((grapheme.into(), style)),
+        }
+        self.width += grapheme.width();
+    }
+
+    fn graphemes(&self) -> impl Iterator<Item = (&str, Style)> {
+        self.pieces
+            .iter()
+            .flat_map(|(text, style)| text.graphemes(true).map(move |grapheme| (grapheme, *style)))
+    }
+
+    /// Splits the token into pieces at most `width` columns wide.
+    fn split(self, width: usize) -> Vec<Token> {
+        let mut parts = vec![];
+        let mut part = Token::default();
+        for (grapheme, style) in self.graphemes() {
+            if part.width + grapheme.width() > width && part.width > 0 {
+                parts.push(mem::take(&mut part));
+            }
+            part.push(grapheme, style);
+        }
+        parts.push(part);
+        parts
+    }
+}
+
+fn row_width(row: &[Token]) -> usize {
+    row.iter().map(|token| token.width).sum()
+}
+
+/// Splits the lines into tokens, breaking them further at newlines.
+fn tokenize(content: &[Line]) -> Vec<Vec<Token>> {
+    content.iter().flat_map(tokenize_line).collect()
+}
+
+fn tokenize_line(content: &Line) -> Vec<Vec<Token>> {
+    let mut lines = vec![Vec::new()];
+    let mut current: Option<Token> = None;
+    for span in &content.spans {
+        for grapheme in span.content.graphemes(true) {
+            let line = lines.last_mut().expect("there's at least one line");
+            if grapheme == "\n" || grapheme == "\r\n" {
+                line.extend(current.take());
+                lines.push(Vec::new());
+                continue;
+            }
+            let space = grapheme.chars().all(char::is_whitespace);
+            if current.as_ref().map(|token| token.space) != Some(space) {
+                line.extend(current.take());
+            }
+            current
+                .get_or_insert_with(|| Token {
+                    space,
+                    ..Token::default()
+                })
+                .push(grapheme, span.style);
+        }
+    }
+    lines
+        .last_mut()
+        .expect("there's at least one line")
+        .extend(current);
+    lines
+}
+
+/// Breaks a line into rows at most `width` wide, dropping the whitespace at
+/// the breaks.
+fn wrap_line(line: Vec<Token>, width: usize) -> Vec<Vec<Token>> {
+    let mut rows = vec![];
+    let mut row: Vec<Token> = vec![];
+    let mut current_width = 0;
+    let end_row = |row: &mut Vec<Token>, rows: &mut Vec<Vec<Token>>| {
+        while row.last().is_some_and(|token| token.space) {
+            row.pop();
+        }
+        rows.push(mem::take(row));
+    };
+    for token in line {
+        if token.space && row.is_empty() && !rows.is_empty() {
+            continue;
+        }
+        if !token.space && current_width + token.width > width && !row.is_empty() {
+            end_row(&mut row, &mut rows);
+            current_width = 0;
+        }
+        if !token.space && token.width > width {
+            let mut parts = token.split(width.max(1));
+            let last = parts.pop().expect("split returns at least one part");
+            for part in parts {
+                row.push(part);
+                end_row(&mut row, &mut rows);
+            }
+            current_width = last.width;
+            row.push(last);
+        } else {
+            current_width += token.width;
+            row.push(token);
+        }
+    }
+    end_row(&mut row, &mut rows);
+    rows
+}
+
+struct DrawRow<'a> {
+    tokens: &'a [Token],
+    align: TextAlign,
+    justify: bool,
+    ellipsis: bool,
+}
+
+impl DrawRow<'_> {
+    fn draw(&self, canvas: &mut Canvas, y: usize, style: Style) {
+        let width = canvas.size().width;
+        let content_width = row_width(self.tokens);
+        let mut x = match self.align {
+            TextAlign::Left | TextAlign::Justify => 0,
+            TextAlign::Centre => width.saturating_sub(content_width) / 2,
+            TextAlign::Right => width.saturating_sub(content_width),
+        };
+        let limit = if self.ellipsis {
+            width.saturating_sub(ELLIPSIS.width())
+        } else {
+            width
+        };
+
+        // Extra spaces to add to the gaps between words when justifying, the
+        // first gaps get one more
+        let num_gaps = self.tokens.iter().filter(|token| token.space).count();
+        let extra = width.saturating_sub(content_width);
+        let mut gap = 0;
+
+        'tokens: for token in self.tokens {
+            if token.space && self.justify {
+                let spaces = token.width + extra / num_gaps + usize::from(gap < extra % num_gaps);
+                gap += 1;
+                for _ in 0..spaces {
+                    if x + 1 > limit {
+                        break 'tokens;
+                    }
+                    x += canvas.draw_str(x, y, token.pieces[0].1, " ");
+                }
+                continue;
+            }
+            for (grapheme, grapheme_style) in token.graphemes() {
+                if x + grapheme.width() > limit {
+                    break 'tokens;
+                }
+                x += canvas.draw_str(x, y, grapheme_style, grapheme);
+            }
+        }
+
+        if self.ellipsis {
+            canvas.draw_str(x, y, style, ELLIPSIS);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Colour, Position, Size};
+
+    fn draw(properties: ParagraphProperties, size: Size) -> Vec<String> {
+        let paragraph = Paragraph {
+            properties,
+            frame: Rect::new(Position::zero(), size),
+        };
+        let canvas = paragraph.draw();
+        (0..size.height)
+            .map(|y| {
+                (0..size.width)
+                    .filter_map(|x| canvas.textel(x, y).as_ref())
+                    .map(|textel| textel.grapheme.as_str())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn wraps_words() {
+        let properties = ParagraphProperties::new()
+            .line(
+                Line::new()
+                    .span("the quick ", Style::default())
+                    .span("brown", Style::bold(Colour::black(), Colour::white()))
+                    .span(" fox jumps", Style::default()),
+            )
+            .wrap(TextWrap::Word);
+        assert_eq!(
+            draw(properties.clone(), Size::new(10, 3)),
+            ["the quick", "brown fox", "jumps"]
+        );
+        assert_eq!(
+            draw(properties.clone().align(TextAlign::Right), Size::new(10, 3)),
+            [" the quick", " brown fox", "     jumps"]
+        );
+        assert_eq!(
+            draw(
+                properties.clone().align(TextAlign::Justify),
+                Size::new(11, 2)
+            ),
+            ["the   quick", "brown   fox"]
+        );
+        assert_eq!(
+            draw(properties.ellipsis(true), Size::new(10, 2)),
+            ["the quick", "brown fox…"]
+        );
+    }
+
+    #[test]
+    fn breaks_long_words() {
+        let properties = ParagraphProperties::new()
+            .line(Line::styled("a abcdefgh", Style::default()))
+            .wrap(TextWrap::Word);
+        assert_eq!(draw(properties, Size::new(4, 3)), ["a", "abcd", "efgh"]);
+    }
+
+    #[test]
+    fn truncates_lines() {
+        let properties = ParagraphProperties::new()
+            .line(Line::styled("a long line", Style::default()))
+            .line(Line::styled("short", Style::default()))
+            .ellipsis(true);
+        assert_eq!(draw(properties, Size::new(6, 2)), ["a lon…", "short"]);
+    }
+}
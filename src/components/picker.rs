@@ -0,0 +1,582 @@
+use ropey::Rope;
+use std::{cmp, rc::Rc};
+use unicode_width::UnicodeWidthStr;
+
+use super::input::{Cursor, Input, InputChange, InputProperties, InputStyle};
+use crate::{
+    layout::{self, Layout},
+    BindingMatch, BindingTransition, Callback, Canvas, Colour, Component, ComponentExt,
+    ComponentLink, Key, Line, MouseButton, MouseEvent, MouseEventKind, Position, Rect,
+    ShouldRender, Size, Span, Style, Theme,
+};
+
+/// A fuzzy match of a pattern in a candidate string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better, matches at word boundaries and consecutive matches
+    /// score more.
+    pub score: i64,
+    /// The indices of the matched characters in the candidate.
+    pub positions: Vec<usize>,
+}
+
+/// Matches candidates containing the characters of a pattern in order, not
+/// necessarily consecutively, like fzf.
+///
+/// Matching ignores case unless the pattern contains an uppercase character.
+/// It's linear in the length of the candidate and doesn't allocate unless the
+/// positions are requested, so that large lists can be filtered as the user
+/// types.
+///
+/// ```
+/// # use zi::components::picker::FuzzyMatcher;
+/// let matcher = FuzzyMatcher::new("fb");
+/// assert_eq!(matcher.positions("foo_bar").unwrap().positions, vec![0, 4]);
+/// assert!(matcher.score("foo_bar") > matcher.score("afoobar"));
+/// assert_eq!(matcher.score("bar"), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FuzzyMatcher {
+    pattern: Vec<char>,
+    ignore_case: bool,
+}
+
+impl FuzzyMatcher {
+    pub fn new(pattern: &str) -> Self {
+        let ignore_case = !pattern.chars().any(char::is_uppercase);
+        Self {
+            pattern: pattern
+                .chars()
+                .map(|character| {
+                    if ignore_case {
+                        lowercase(character)
+                    } else {
+                        character
+                    }
+                })
+                .collect(),
+            ignore_case,
+        }
+    }
+
+    /// Returns the score of the candidate, `None` if it doesn't match.
+    pub fn score(&self, candidate: &str) -> Option<i64> {
+        self.find(candidate, false).map(|found| found.score)
+    }
+
+    /// Returns the score of the candidate and the positions of the matched
+    /// characters, `None` if it doesn't match.
+    pub fn positions(&self, candidate: &str) -> Option<FuzzyMatch> {
+        self.find(candidate, true)
+    }
+
+    fn matches(&self, pattern_index: usize, character: char) -> bool {
+        let character = if self.ignore_case {
+            lowercase(character)
+        } else {
+            character
+        };
+        self.pattern[pattern_index] == character
+    }
+
+    fn find(&self, candidate: &str, with_positions: bool) -> Option<FuzzyMatch> {
+        if self.pattern.is_empty() {
+            return Some(FuzzyMatch {
+                score: 0,
+                positions: Vec::new(),
+            });
+        }
+
+        // Find the first occurrence of the pattern, then the shortest one
+        // ending there by scanning backwards
+        let mut pattern_index = 0;
+        let mut end = None;
+        for (offset, character) in candidate.char_indices() {
+            if self.matches(pattern_index, character) {
+                pattern_index += 1;
+                if pattern_index == self.pattern.len() {
+                    end = Some(offset + character.len_utf8());
+                    break;
+                }
+            }
+        }
+        let end = end?;
+        let mut start = 0;
+        for (offset, character) in candidate[..end].char_indices().rev() {
+            if self.matches(pattern_index - 1, character) {
+                pattern_index -= 1;
+                if pattern_index == 0 {
+                    start = offset;
+                    break;
+                }
+            }
+        }
+
+        // Score the occurrence
+        let mut previous = candidate[..start].chars().next_back();
+        let first_index = if with_positions {
+            candidate[..start].chars().count()
+        } else {
+            0
+        };
+        let mut positions = Vec::new();
+        let mut score = 0;
+        let mut consecutive = 0;
+        let mut in_gap = false;
+        for (char_index, character) in (first_index..).zip(candidate[start..end].chars()) {
+            if pattern_index < self.pattern.len() && self.matches(pattern_index, character) {
+                let bonus = boundary_bonus(previous, character);
+                consecutive += 1;
+                score += SCORE_MATCH
+                    + if consecutive > 1 {
+                        cmp::max(bonus, BONUS_CONSECUTIVE)
+                    } else if pattern_index == 0 {
+                        2 * bonus
+                    } else {
+                        bonus
+                    };
+                if with_positions {
+                    positions.push(char_index);
+                }
+                pattern_index += 1;
+                in_gap = false;
+            } else {
+                consecutive = 0;
+                score -= if in_gap {
+                    PENALTY_GAP_EXTENSION
+                } else {
+                    PENALTY_GAP_START
+                };
+                in_gap = true;
+            }
+            previous = Some(character);
+        }
+
+        Some(FuzzyMatch { score, positions })
+    }
+}
+
+fn lowercase(character: char) -> char {
+    if character.is_ascii() {
+        character.to_ascii_lowercase()
+    } else {
+        character.to_lowercase().next().unwrap_or(character)
+    }
+}
+
+fn boundary_bonus(previous: Option<char>, character: char) -> i64 {
+    match previous {
+        None => BONUS_BOUNDARY,
+        Some(previous) if !previous.is_alphanumeric() && character.is_alphanumeric() => {
+            BONUS_BOUNDARY
+        }
+        Some(previous) if previous.is_lowercase() && character.is_uppercase() => BONUS_CAMEL_CASE,
+        _ => 0,
+    }
+}
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_BOUNDARY: i64 = 8;
+const BONUS_CAMEL_CASE: i64 = 7;
+const BONUS_CONSECUTIVE: i64 = 4;
+const PENALTY_GAP_START: i64 = 3;
+const PENALTY_GAP_EXTENSION: i64 = 1;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PickerStyle {
+    pub input: InputStyle,
+    pub prompt: Style,
+    pub status: Style,
+    pub item: Style,
+    pub highlighted: Style,
+    /// Colour of the matched characters.
+    pub matched: Colour,
+}
+
+impl Default for PickerStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const DARK1: Colour = Colour::rgb(60, 56, 54);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+        const BRIGHT_ORANGE: Colour = Colour::rgb(254, 128, 25);
+
+        Self {
+            input: InputStyle::default(),
+            prompt: Style::bold(DARK0_SOFT, BRIGHT_BLUE),
+            status: Style::normal(DARK0_SOFT, GRAY_245),
+            item: Style::normal(DARK0_SOFT, LIGHT2),
+            highlighted: Style::bold(DARK1, LIGHT2),
+            matched: BRIGHT_ORANGE,
+        }
+    }
+}
+
+impl From<&Theme> for PickerStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            input: theme.into(),
+            prompt: theme.accent,
+            status: theme.muted,
+            item: theme.background,
+            highlighted: theme.selection,
+            matched: theme.warning.foreground,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PickerProperties {
+    pub style: PickerStyle,
+    /// The candidates to pick from. Compared by identity, replace the `Rc` to
+    /// change them.
+    pub candidates: Rc<Vec<String>>,
+    pub prompt: String,
+    pub focused: bool,
+    /// Called with the index of the picked candidate, when enter is pressed
+    /// or a candidate is clicked.
+    pub on_select: Option<Callback<usize>>,
+    /// Called when escape is pressed.
+    pub on_cancel: Option<Callback<()>>,
+}
+
+impl PartialEq for PickerProperties {
+    fn eq(&self, other: &Self) -> bool {
+        self.style == other.style
+            && Rc::ptr_eq(&self.candidates, &other.candidates)
+            && self.prompt == other.prompt
+            && self.focused == other.focused
+            && self.on_select == other.on_select
+            && self.on_cancel == other.on_cancel
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Query(InputChange),
+    NextItem,
+    PreviousItem,
+    NextPage,
+    PreviousPage,
+    Accept,
+    Cancel,
+    /// Picks the candidate in the given row of the list.
+    Click(usize),
+}
+
+/// A fuzzy finder: an input filtering a list of candidates, ordered by how
+/// well they match.
+///
+/// Only the visible candidates are drawn. When the query is extended only the
+/// previous matches are filtered again, so typing stays responsive with
+/// hundreds of thousands of candidates.
+///
+/// ```text
+/// > fb
+///   2/5
+/// ▌ foo_bar.rs
+///   src/foobar.rs
+/// ```
+pub struct Picker {
+    properties: PickerProperties,
+    frame: Rect,
+    link: ComponentLink<Self>,
+    query: Rope,
+    cursor: Cursor,
+    /// The query `matches` were computed for.
+    matched_query: String,
+    /// Indices of the matching candidates and their scores, best first.
+    matches: Vec<(usize, i64)>,
+    highlighted: usize,
+    offset: usize,
+}
+
+impl Picker {
+    fn query(&self) -> String {
+        let mut query = self.query.to_string();
+        if query.ends_with('\n') {
+            query.pop();
+        }
+        query
+    }
+
+    fn update_matches(&mut self, refilter: bool) {
+        let query = self.query();
+        let matcher = FuzzyMatcher::new(&query);
+        let candidates = &self.properties.candidates;
+        if refilter && query.starts_with(&self.matched_query) {
+            self.matches.retain_mut(|(index, score)| {
+                matcher
+                    .score(&candidates[*index])
+                    .map(|new_score| *score = new_score)
+                    .is_some()
+            });
+        } else {
+            self.matches = candidates
+                .iter()
+                .enumerate()
+                .filter_map(|(index, candidate)| {
+                    matcher.score(candidate).map(|score| (index, score))
+                })
+                .collect();
+        }
+        // Prefer shorter candidates among equally good matches
+        self.matches.sort_unstable_by_key(|&(index, score)| {
+            (cmp::Reverse(score), candidates[index].len(), index)
+        });
+        self.matched_query = query;
+        self.highlighted = 0;
+        self.offset = 0;
+    }
+
+    fn list_height(&self) -> usize {
+        self.frame.size.height.saturating_sub(2)
+    }
+
+    fn highlight(&mut self, index: usize) -> ShouldRender {
+        let index = cmp::min(index, self.matches.len().saturating_sub(1));
+        self.highlighted = index;
+        let height = self.list_height();
+        if index < self.offset {
+            self.offset = index;
+        } else if height > 0 && index >= self.offset + height {
+            self.offset = index + 1 - height;
+        }
+        ShouldRender::Yes
+    }
+
+    fn accept(&self, index: usize) {
+        if let (Some(on_select), Some(&(candidate, _))) =
+            (self.properties.on_select.as_ref(), self.matches.get(index))
+        {
+            on_select.emit(candidate);
+        }
+    }
+
+    fn draw_list(&self) -> Canvas {
+        let style = &self.properties.style;
+        let size = Size::new(self.frame.size.width, self.list_height());
+        let mut canvas = Canvas::new(size);
+        canvas.clear(style.item);
+
+        let matcher = FuzzyMatcher::new(&self.matched_query);
+        for (y, &(index, _)) in self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(size.height)
+            .map(|(row, item)| (row - self.offset, item))
+        {
+            let (row_style, marker) = if self.offset + y == self.highlighted {
+                canvas.clear_region(
+                    Rect::new(Position::new(0, y), Size::new(size.width, 1)),
+                    style.highlighted,
+                );
+                (style.highlighted, "▌ ")
+            } else {
+                (style.item, "  ")
+            };
+            let matched_style = Style {
+                foreground: style.matched,
+                ..row_style
+            };
+
+            let candidate = &self.properties.candidates[index];
+            let positions = matcher
+                .positions(candidate)
+                .map(|found| found.positions)
+                .unwrap_or_default();
+            let mut line = Line::styled(
+                marker,
+                Style {
+                    foreground: style.matched,
+                    ..row_style
+                },
+            );
+            let mut positions = positions.into_iter().peekable();
+            for (char_index, character) in candidate.chars().enumerate() {
+                let matched = positions.next_if_eq(&char_index).is_some();
+                let span_style = if matched { matched_style } else { row_style };
+                match line.spans.last_mut() {
+                    Some(span) if span.style == span_style => span.content.push(character),
+                    _ => line.push(Span::new(character, span_style)),
+                }
+            }
+            canvas.draw_line(0, y, &line);
+        }
+
+        canvas
+    }
+}
+
+impl Component for Picker {
+    type Message = Message;
+    type Properties = PickerProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, link: ComponentLink<Self>) -> Self {
+        let mut picker = Self {
+            properties,
+            frame,
+            link,
+            query: Rope::new(),
+            cursor: Cursor::new(),
+            matched_query: String::new(),
+            matches: Vec::new(),
+            highlighted: 0,
+            offset: 0,
+        };
+        picker.update_matches(false);
+        picker
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            let same_candidates = Rc::ptr_eq(&self.properties.candidates, &properties.candidates);
+            self.properties = properties;
+            if !same_candidates {
+                self.update_matches(false);
+            }
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        self.highlight(self.highlighted)
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let page = cmp::max(self.list_height(), 1);
+        match message {
+            Message::Query(InputChange { content, cursor }) => {
+                self.cursor = cursor;
+                if let Some(content) = content {
+                    self.query = content;
+                    self.update_matches(true);
+                }
+                ShouldRender::Yes
+            }
+            Message::NextItem => self.highlight(self.highlighted + 1),
+            Message::PreviousItem => self.highlight(self.highlighted.saturating_sub(1)),
+            Message::NextPage => self.highlight(self.highlighted + page),
+            Message::PreviousPage => self.highlight(self.highlighted.saturating_sub(page)),
+            Message::Accept => {
+                self.accept(self.highlighted);
+                ShouldRender::No
+            }
+            Message::Cancel => {
+                if let Some(on_cancel) = self.properties.on_cancel.as_ref() {
+                    on_cancel.emit(());
+                }
+                ShouldRender::No
+            }
+            Message::Click(row) => {
+                let index = self.offset + row;
+                if index < self.matches.len() {
+                    self.highlight(index);
+                    self.accept(index);
+                }
+                ShouldRender::Yes
+            }
+        }
+    }
+
+    fn view(&self) -> Layout {
+        let style = &self.properties.style;
+        let width = self.frame.size.width;
+
+        let prompt_width = self.properties.prompt.width();
+        let mut prompt = Canvas::new(Size::new(prompt_width, 1));
+        prompt.clear(style.prompt);
+        prompt.draw_str(0, 0, style.prompt, &self.properties.prompt);
+
+        let mut status = Canvas::new(Size::new(width, 1));
+        status.clear(style.status);
+        status.draw_str(
+            2,
+            0,
+            style.status,
+            &format!(
+                "{}/{}",
+                self.matches.len(),
+                self.properties.candidates.len()
+            ),
+        );
+
+        layout::column([
+            layout::fixed(
+                1,
+                layout::row([
+                    layout::fixed(prompt_width, prompt.into()),
+                    layout::auto(Input::with(InputProperties {
+                        style: style.input.clone(),
+                        content: self.query.clone(),
+                        cursor: self.cursor.clone(),
+                        placeholder: None,
+                        on_change: Some(self.link.callback(Message::Query)),
+                        on_submit: Some(self.link.callback(|_| Message::Accept)),
+                        focused: self.properties.focused,
+                    })),
+                ]),
+            ),
+            layout::fixed(1, status.into()),
+            layout::auto(self.draw_list().into()),
+        ])
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let message = match pressed {
+            [Key::Ctrl('n')] | [Key::Down] => Some(Message::NextItem),
+            [Key::Ctrl('p')] | [Key::Up] => Some(Message::PreviousItem),
+            [Key::Ctrl('v')] | [Key::PageDown] => Some(Message::NextPage),
+            [Key::Alt('v')] | [Key::PageUp] => Some(Message::PreviousPage),
+            [Key::Esc] | [Key::Ctrl('g')] => Some(Message::Cancel),
+            _ => None,
+        };
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message,
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::Press(MouseButton::Left) if event.position.y >= 2 => {
+                Some(Message::Click(event.position.y - 2))
+            }
+            MouseEventKind::ScrollDown => Some(Message::NextItem),
+            MouseEventKind::ScrollUp => Some(Message::PreviousItem),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_match() {
+        let matcher = FuzzyMatcher::new("abc");
+        assert_eq!(matcher.score("acb"), None);
+        // The shortest occurrence is scored
+        assert_eq!(
+            matcher.positions("a_abxc").unwrap().positions,
+            vec![2, 3, 5]
+        );
+        // Consecutive matches and matches at word boundaries are better
+        assert!(matcher.score("abc") > matcher.score("axbxc"));
+        assert!(matcher.score("x_abc") > matcher.score("xxabc"));
+        // Smart case
+        assert!(FuzzyMatcher::new("ABC").score("abc").is_none());
+        assert!(matcher.score("ABC").is_some());
+    }
+}
@@ -0,0 +1,223 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    layout::Layout, Canvas, Colour, Component, ComponentLink, Rect, ShouldRender, Style, Theme,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgressBarStyle {
+    /// Style of the completed part of the bar. The label is drawn with this
+    /// style where it overlaps it.
+    pub filled: Style,
+    pub unfilled: Style,
+}
+
+impl Default for ProgressBarStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const DARK1: Colour = Colour::rgb(60, 56, 54);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+
+        Self {
+            filled: Style::bold(BRIGHT_BLUE, DARK0_SOFT),
+            unfilled: Style::normal(DARK1, LIGHT2),
+        }
+    }
+}
+
+impl From<&Theme> for ProgressBarStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            filled: Style::bold(theme.accent.foreground, theme.background.background),
+            unfilled: theme.surface,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProgressBarProperties {
+    pub style: ProgressBarStyle,
+    /// Fraction of the work that is done, between 0 and 1. If `None`, the
+    /// progress is indeterminate and an animated block bounces along the bar.
+    pub progress: Option<f64>,
+    /// Text shown in the middle of the bar.
+    pub label: Option<String>,
+    /// Whether to append the percentage to the label. Ignored when the
+    /// progress is indeterminate.
+    pub show_percentage: bool,
+}
+
+impl ProgressBarProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn style(mut self, style: ProgressBarStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn progress(mut self, progress: f64) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    pub fn indeterminate(mut self) -> Self {
+        self.progress = None;
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn show_percentage(mut self, show_percentage: bool) -> Self {
+        self.show_percentage = show_percentage;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Message;
+
+/// A progress bar.
+///
+/// ```text
+/// ██████████████▌      42%
+/// ```
+#[derive(Debug)]
+pub struct ProgressBar {
+    properties: ProgressBarProperties,
+    frame: Rect,
+    /// Position of the bouncing block when the progress is indeterminate, in
+    /// cells. Counts up to twice the free width and back to 0.
+    animation: usize,
+}
+
+impl ProgressBar {
+    /// Partially filled cells, in eighths.
+    const EIGHTHS: [&'static str; 8] = [" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+    fn label(&self) -> String {
+        let ProgressBarProperties {
+            ref label,
+            progress,
+            show_percentage,
+            ..
+        } = self.properties;
+        let label = label.as_deref().unwrap_or("");
+        match progress {
+            Some(progress) if show_percentage => {
+                let percentage = format!("{:.0}%", progress.clamp(0.0, 1.0) * 100.0);
+                if label.is_empty() {
+                    percentage
+                } else {
+                    format!("{} {}", label, percentage)
+                }
+            }
+            _ => label.to_owned(),
+        }
+    }
+
+    /// The filled range of the bar, in eighths of a cell.
+    fn filled_range(&self) -> (usize, usize) {
+        let width = self.frame.size.width;
+        match self.properties.progress {
+            Some(progress) => (
+                0,
+                (progress.clamp(0.0, 1.0) * (8 * width) as f64).round() as usize,
+            ),
+            None => {
+                let block_width = (width / 5).max(1);
+                let free_width = width.saturating_sub(block_width);
+                let start = if self.animation <= free_width {
+                    self.animation
+                } else {
+                    2 * free_width - self.animation
+                };
+                (8 * start, 8 * (start + block_width))
+            }
+        }
+    }
+}
+
+impl Component for ProgressBar {
+    type Message = Message;
+    type Properties = ProgressBarProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self {
+            properties,
+            frame,
+            animation: 0,
+        }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        self.animation = 0;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, _message: Self::Message) -> ShouldRender {
+        let width = self.frame.size.width;
+        let free_width = width.saturating_sub((width / 5).max(1));
+        self.animation = (self.animation + 1) % (2 * free_width).max(1);
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        let ProgressBarStyle {
+            filled: filled_style,
+            unfilled: unfilled_style,
+        } = self.properties.style;
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(unfilled_style);
+
+        let (start, end) = self.filled_range();
+        let is_filled = |x: usize| start <= 8 * x && 8 * x + 8 <= end;
+        for x in 0..self.frame.size.width {
+            let cell_start = 8 * x;
+            for y in 0..self.frame.size.height {
+                if is_filled(x) {
+                    canvas.draw_str(x, y, filled_style, " ");
+                } else if (cell_start..cell_start + 8).contains(&end) && end > start {
+                    // The end of the filled range falls inside this cell
+                    let partial_style =
+                        Style::normal(unfilled_style.background, filled_style.background);
+                    canvas.draw_str(x, y, partial_style, Self::EIGHTHS[end - cell_start]);
+                }
+            }
+        }
+
+        let label = self.label();
+        let mut x = self.frame.size.width.saturating_sub(label.width()) / 2;
+        let y = self.frame.size.height / 2;
+        for grapheme in label.graphemes(true) {
+            let style = if is_filled(x) {
+                filled_style
+            } else {
+                unfilled_style
+            };
+            x += canvas.draw_str(x, y, style, grapheme);
+        }
+
+        canvas.into()
+    }
+
+    fn tick(&self) -> Option<Self::Message> {
+        self.properties.progress.is_none().then_some(Message)
+    }
+}
@@ -0,0 +1,142 @@
+use std::cmp;
+
+use super::checkbox::{draw_control_line, ControlStyle};
+use crate::{
+    layout::Layout, BindingMatch, BindingTransition, Callback, Canvas, Component, ComponentLink,
+    Key, MouseButton, MouseEvent, MouseEventKind, Rect, ShouldRender,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadioGroupProperties {
+    pub style: ControlStyle,
+    pub options: Vec<String>,
+    /// Index of the selected option.
+    pub selected: Option<usize>,
+    pub focused: bool,
+    /// Called with the index of the newly selected option.
+    pub on_change: Option<Callback<usize>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    NextOption,
+    PreviousOption,
+    SelectOption(usize),
+}
+
+/// A group of radio buttons, one per line, of which only one can be
+/// selected. The arrow keys move the selection.
+///
+/// ```text
+/// ( ) Small
+/// (•) Medium
+/// ( ) Large
+/// ```
+pub struct RadioGroup {
+    properties: RadioGroupProperties,
+    frame: Rect,
+}
+
+impl RadioGroup {
+    fn select(&self, index: usize) {
+        if let Some(on_change) = self.properties.on_change.as_ref() {
+            if index < self.properties.options.len() && Some(index) != self.properties.selected {
+                on_change.emit(index);
+            }
+        }
+    }
+}
+
+impl Component for RadioGroup {
+    type Message = Message;
+    type Properties = RadioGroupProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { properties, frame }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let num_options = self.properties.options.len();
+        match (message, self.properties.selected) {
+            (Message::NextOption, Some(selected)) => {
+                self.select(cmp::min(selected + 1, num_options.saturating_sub(1)))
+            }
+            (Message::PreviousOption, Some(selected)) => self.select(selected.saturating_sub(1)),
+            (Message::NextOption, None) | (Message::PreviousOption, None) => self.select(0),
+            (Message::SelectOption(index), _) => self.select(index),
+        }
+        ShouldRender::No
+    }
+
+    fn view(&self) -> Layout {
+        let RadioGroupProperties {
+            ref style,
+            ref options,
+            selected,
+            focused,
+            ..
+        } = self.properties;
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style.normal);
+        for (y, option) in options.iter().enumerate().take(self.frame.size.height) {
+            let is_selected = selected == Some(y);
+            let (mark, mark_style) = if is_selected {
+                ("(•)", style.checked)
+            } else {
+                ("( )", style.unchecked)
+            };
+            draw_control_line(
+                &mut canvas,
+                y,
+                style,
+                focused && (is_selected || (selected.is_none() && y == 0)),
+                mark,
+                mark_style,
+                option,
+            );
+        }
+        canvas.into()
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let message = match pressed {
+            [Key::Ctrl('n')] | [Key::Down] | [Key::Right] => Some(Message::NextOption),
+            [Key::Ctrl('p')] | [Key::Up] | [Key::Left] => Some(Message::PreviousOption),
+            [Key::Char(' ')] | [Key::Char('\n')] if self.properties.selected.is_none() => {
+                Some(Message::SelectOption(0))
+            }
+            _ => None,
+        };
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message,
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::Press(MouseButton::Left) => {
+                Some(Message::SelectOption(event.position.y))
+            }
+            _ => None,
+        }
+    }
+}
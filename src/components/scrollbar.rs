@@ -0,0 +1,290 @@
+use std::ops::Range;
+
+use crate::{
+    layout::Layout, Callback, Canvas, Colour, Component, ComponentLink, MouseButton, MouseEvent,
+    MouseEventKind, Position, Rect, ShouldRender, Style, Theme,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Vertical,
+    Horizontal,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ScrollbarStyle {
+    pub track: Style,
+    pub thumb: Style,
+}
+
+impl Default for ScrollbarStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const DARK2: Colour = Colour::rgb(80, 73, 69);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+
+        Self {
+            track: Style::normal(DARK0_SOFT, DARK2),
+            thumb: Style::normal(DARK0_SOFT, GRAY_245),
+        }
+    }
+}
+
+impl From<&Theme> for ScrollbarStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            track: theme.border,
+            thumb: theme.muted,
+        }
+    }
+}
+
+/// The scroll state of some content, e.g. the lines of a list.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScrollExtent {
+    /// Length of the whole content.
+    pub content: usize,
+    /// Length of the visible part of the content.
+    pub viewport: usize,
+    /// Offset of the visible part, between 0 and `content - viewport`.
+    pub offset: usize,
+}
+
+impl ScrollExtent {
+    pub fn new(content: usize, viewport: usize, offset: usize) -> Self {
+        Self {
+            content,
+            viewport,
+            offset,
+        }
+    }
+
+    /// The largest valid offset.
+    pub fn max_offset(&self) -> usize {
+        self.content.saturating_sub(self.viewport)
+    }
+
+    /// Whether the content doesn't fit in the viewport.
+    pub fn is_scrollable(&self) -> bool {
+        self.content > self.viewport
+    }
+
+    /// The cells covered by the thumb on a track of the given length. The
+    /// length of the thumb is proportional to the visible part of the content.
+    pub fn thumb(&self, track_length: usize) -> Range<usize> {
+        if !self.is_scrollable() || track_length == 0 {
+            return 0..track_length;
+        }
+        let thumb_length = (track_length * self.viewport + self.content / 2) / self.content;
+        let thumb_length = thumb_length.clamp(1, track_length);
+        let max_offset = self.max_offset();
+        let free_length = track_length - thumb_length;
+        let start =
+            (free_length * self.offset.min(max_offset) + max_offset / 2) / max_offset.max(1);
+        start..start + thumb_length
+    }
+
+    /// The offset that puts the start of the thumb at the given position on
+    /// a track of the given length.
+    pub fn offset_at(&self, track_length: usize, thumb_start: usize) -> usize {
+        let thumb = self.thumb(track_length);
+        let free_length = track_length.saturating_sub(thumb.len());
+        if free_length == 0 {
+            return 0;
+        }
+        let max_offset = self.max_offset();
+        (thumb_start.min(free_length) * max_offset + free_length / 2) / free_length
+    }
+}
+
+/// Draws a scrollbar in the region of the canvas, for components that draw
+/// their content in a single canvas and want to show a scroll indicator.
+pub fn draw_scrollbar(
+    canvas: &mut Canvas,
+    region: Rect,
+    orientation: Orientation,
+    extent: ScrollExtent,
+    style: ScrollbarStyle,
+) {
+    let (track_length, track, thumb) = match orientation {
+        Orientation::Vertical => (region.size.height, "│", "┃"),
+        Orientation::Horizontal => (region.size.width, "─", "━"),
+    };
+    let thumb_range = extent.thumb(track_length);
+    for index in 0..track_length {
+        let (cell_style, content) = if thumb_range.contains(&index) {
+            (style.thumb, thumb)
+        } else {
+            (style.track, track)
+        };
+        let position = match orientation {
+            Orientation::Vertical => Position::new(region.origin.x, region.origin.y + index),
+            Orientation::Horizontal => Position::new(region.origin.x + index, region.origin.y),
+        };
+        canvas.draw_str(position.x, position.y, cell_style, content);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScrollbarProperties {
+    pub style: ScrollbarStyle,
+    pub orientation: Orientation,
+    pub extent: ScrollExtent,
+    /// Called with the new offset when the user scrolls, clicks or drags the
+    /// scrollbar.
+    pub on_scroll: Option<Callback<usize>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    ScrollBy(isize),
+    /// Pressed the mouse at the given position on the track.
+    Press(usize),
+    /// Dragged the mouse to the given position on the track.
+    Drag(usize),
+    Release,
+}
+
+/// A scrollbar, to be laid out next to scrollable content. It doesn't scroll
+/// anything by itself, the new offset is reported via `on_scroll`.
+///
+/// Clicking the track moves the thumb under the pointer and the thumb can be
+/// dragged while the pointer stays on the scrollbar.
+pub struct Scrollbar {
+    properties: ScrollbarProperties,
+    frame: Rect,
+    /// When dragging the thumb, where it was grabbed relative to its start.
+    grabbed: Option<usize>,
+}
+
+impl Scrollbar {
+    fn track_length(&self) -> usize {
+        match self.properties.orientation {
+            Orientation::Vertical => self.frame.size.height,
+            Orientation::Horizontal => self.frame.size.width,
+        }
+    }
+
+    fn scroll_to(&self, offset: usize) {
+        let extent = self.properties.extent;
+        let offset = offset.min(extent.max_offset());
+        if let Some(on_scroll) = self.properties.on_scroll.as_ref() {
+            if offset != extent.offset {
+                on_scroll.emit(offset);
+            }
+        }
+    }
+
+    fn drag_thumb(&self, position: usize, grabbed: usize) {
+        let extent = self.properties.extent;
+        let thumb_start = position.saturating_sub(grabbed);
+        self.scroll_to(extent.offset_at(self.track_length(), thumb_start));
+    }
+}
+
+impl Component for Scrollbar {
+    type Message = Message;
+    type Properties = ScrollbarProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self {
+            properties,
+            frame,
+            grabbed: None,
+        }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let extent = self.properties.extent;
+        match message {
+            Message::ScrollBy(delta) => {
+                self.scroll_to(extent.offset.saturating_add_signed(delta));
+            }
+            Message::Press(position) => {
+                let thumb = extent.thumb(self.track_length());
+                let grabbed = if thumb.contains(&position) {
+                    position - thumb.start
+                } else {
+                    // Centre the thumb under the pointer
+                    thumb.len() / 2
+                };
+                self.grabbed = Some(grabbed);
+                self.drag_thumb(position, grabbed);
+            }
+            Message::Drag(position) => {
+                if let Some(grabbed) = self.grabbed {
+                    self.drag_thumb(position, grabbed);
+                }
+            }
+            Message::Release => {
+                self.grabbed = None;
+            }
+        }
+        ShouldRender::No
+    }
+
+    fn view(&self) -> Layout {
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(self.properties.style.track);
+        draw_scrollbar(
+            &mut canvas,
+            Rect::new(Position::new(0, 0), self.frame.size),
+            self.properties.orientation,
+            self.properties.extent,
+            self.properties.style,
+        );
+        canvas.into()
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        let position = match self.properties.orientation {
+            Orientation::Vertical => event.position.y,
+            Orientation::Horizontal => event.position.x,
+        };
+        match event.kind {
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollRight => Some(Message::ScrollBy(1)),
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollLeft => Some(Message::ScrollBy(-1)),
+            MouseEventKind::Press(MouseButton::Left) => Some(Message::Press(position)),
+            MouseEventKind::Drag(MouseButton::Left) => Some(Message::Drag(position)),
+            MouseEventKind::Release(MouseButton::Left) => Some(Message::Release),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thumb_is_proportional() {
+        let extent = ScrollExtent::new(100, 25, 0);
+        assert_eq!(extent.thumb(20), 0..5);
+        assert_eq!(
+            ScrollExtent {
+                offset: 75,
+                ..extent
+            }
+            .thumb(20),
+            15..20
+        );
+        assert_eq!(extent.offset_at(20, 15), 75);
+        assert_eq!(ScrollExtent::new(10, 20, 0).thumb(8), 0..8);
+        // The thumb is never empty
+        assert_eq!(ScrollExtent::new(10_000, 1, 0).thumb(8).len(), 1);
+    }
+}
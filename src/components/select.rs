@@ -3,7 +3,7 @@ use std::{cmp, iter};
 use super::text::{Text, TextProperties};
 use crate::{
     layout, BindingMatch, BindingTransition, Callback, Component, ComponentLink, FlexDirection,
-    Item, Key, Layout, Rect, ShouldRender, Style,
+    Item, Key, Layout, MouseButton, MouseEvent, MouseEventKind, Rect, ShouldRender, Style,
 };
 
 #[derive(Clone, PartialEq)]
@@ -14,8 +14,15 @@ pub struct SelectProperties {
     pub item_at: Callback<usize, Item>,
     pub num_items: usize,
     pub item_size: usize,
+    /// Index of the selected item. When items are inserted or removed before
+    /// it, update it to keep pointing at the same item, the visible items
+    /// then stay in place.
     pub selected: usize,
     pub on_change: Option<Callback<usize>>,
+    /// Called with the selected item when space is pressed. Together with
+    /// `item_at` this makes it possible to mark multiple items, the marked
+    /// set being owned by the parent.
+    pub on_toggle: Option<Callback<usize>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -26,6 +33,8 @@ pub enum Message {
     LastItem,
     NextPage,
     PreviousPage,
+    SelectItem(usize),
+    ToggleItem,
 }
 
 pub struct Select {
@@ -39,7 +48,12 @@ impl Select {
         let selected = self.properties.selected;
         let num_visible_items = self.frame.size.height / self.properties.item_size;
 
-        // Compute offset
+        // Compute offset. Keep it as is if the selected item is still visible,
+        // but don't leave empty space at the end when items are removed.
+        self.offset = cmp::min(
+            self.offset,
+            self.properties.num_items.saturating_sub(num_visible_items),
+        );
         self.offset = cmp::min(self.offset, selected);
         if selected - self.offset >= num_visible_items.saturating_sub(1) {
             self.offset = selected + 1 - num_visible_items;
@@ -65,6 +79,16 @@ impl Component for Select {
 
     fn change(&mut self, properties: Self::Properties) -> ShouldRender {
         if self.properties != properties {
+            // Keep the selected item on the same row when items are inserted or
+            // removed before it
+            if properties.num_items != self.properties.num_items {
+                let (previous, selected) = (self.properties.selected, properties.selected);
+                self.offset = if selected >= previous {
+                    self.offset + (selected - previous)
+                } else {
+                    self.offset.saturating_sub(previous - selected)
+                };
+            }
             self.properties = properties;
             self.ensure_selected_item_in_view();
             ShouldRender::Yes
@@ -100,6 +124,15 @@ impl Component for Select {
             (Message::PreviousPage, false) | (Message::NextPage, true) => {
                 current_selected.saturating_sub(self.frame.size.height)
             }
+            (Message::SelectItem(index), _) => {
+                cmp::min(index, self.properties.num_items.saturating_sub(1))
+            }
+            (Message::ToggleItem, _) => {
+                if let Some(on_toggle) = self.properties.on_toggle.as_ref() {
+                    on_toggle.emit(current_selected);
+                }
+                return ShouldRender::No;
+            }
         };
         if current_selected != new_selected {
             if let Some(on_change) = self.properties.on_change.as_mut() {
@@ -142,6 +175,7 @@ impl Component for Select {
             [Key::Alt('>')] => Some(Message::LastItem),
             [Key::Ctrl('v')] | [Key::PageDown] => Some(Message::NextPage),
             [Key::Alt('v')] | [Key::PageUp] => Some(Message::PreviousPage),
+            [Key::Char(' ')] if self.properties.on_toggle.is_some() => Some(Message::ToggleItem),
             [Key::Ctrl('x')] => {
                 transition = BindingTransition::Continue;
                 None
@@ -153,6 +187,27 @@ impl Component for Select {
             message,
         }
     }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::ScrollDown => Some(Message::NextItem),
+            MouseEventKind::ScrollUp => Some(Message::PreviousItem),
+            MouseEventKind::Press(MouseButton::Left) => {
+                let row = if self.is_reversed() {
+                    self.frame.size.height.checked_sub(event.position.y + 1)?
+                } else {
+                    event.position.y
+                };
+                let index = self.offset + row / self.properties.item_size;
+                if index < self.properties.num_items {
+                    Some(Message::SelectItem(index))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
 }
 
 impl Select {
@@ -160,3 +215,35 @@ impl Select {
         self.properties.direction.is_reversed()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComponentExt, Position, Size};
+
+    #[test]
+    fn scroll_position_kept_when_items_are_inserted() {
+        let properties = SelectProperties {
+            background: Style::default(),
+            direction: FlexDirection::Column,
+            focused: false,
+            item_at: (|_| layout::auto(Text::with(TextProperties::new()))).into(),
+            num_items: 100,
+            item_size: 1,
+            selected: 48,
+            on_change: None,
+            on_toggle: None,
+        };
+        let mut select = Select {
+            properties: properties.clone(),
+            frame: Rect::new(Position::new(0, 0), Size::new(10, 5)),
+            offset: 46,
+        };
+        select.change(SelectProperties {
+            num_items: 110,
+            selected: 58,
+            ..properties
+        });
+        assert_eq!(select.offset, 56);
+    }
+}
@@ -0,0 +1,164 @@
+use std::time::{Duration, Instant};
+
+use crate::{layout::Layout, Canvas, Component, ComponentLink, Rect, ShouldRender, Style};
+
+/// The animation of a [`Spinner`](struct.Spinner.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SpinnerFrames {
+    pub frames: &'static [&'static str],
+    /// How long each frame is shown for.
+    pub interval: Duration,
+}
+
+impl SpinnerFrames {
+    pub const DOTS: Self = Self {
+        frames: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+        interval: Duration::from_millis(80),
+    };
+
+    pub const LINE: Self = Self {
+        frames: &["-", "\\", "|", "/"],
+        interval: Duration::from_millis(130),
+    };
+
+    pub const ARC: Self = Self {
+        frames: &["◜", "◠", "◝", "◞", "◡", "◟"],
+        interval: Duration::from_millis(100),
+    };
+
+    pub const BOUNCE: Self = Self {
+        frames: &["⠁", "⠂", "⠄", "⠂"],
+        interval: Duration::from_millis(120),
+    };
+
+    pub const CIRCLE: Self = Self {
+        frames: &["◐", "◓", "◑", "◒"],
+        interval: Duration::from_millis(120),
+    };
+}
+
+impl Default for SpinnerFrames {
+    fn default() -> Self {
+        Self::DOTS
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpinnerProperties {
+    pub style: Style,
+    pub frames: SpinnerFrames,
+    /// Text shown after the spinner.
+    pub label: Option<String>,
+}
+
+impl SpinnerProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn frames(mut self, frames: SpinnerFrames) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Message;
+
+/// An animated spinner, for showing that work is in progress.
+///
+/// The spinner is ticked by the app (see
+/// [`Component::tick`](../../trait.Component.html#method.tick)) and picks the
+/// frame from the time elapsed since it was created, so the animation runs at
+/// the speed given by `SpinnerFrames::interval` regardless of the tick rate.
+#[derive(Debug)]
+pub struct Spinner {
+    properties: SpinnerProperties,
+    frame: Rect,
+    started: Instant,
+    /// Index of the frame currently shown.
+    current: usize,
+}
+
+impl Spinner {
+    fn current_frame(&self) -> usize {
+        let SpinnerFrames { frames, interval } = self.properties.frames;
+        let elapsed = self.started.elapsed().as_millis();
+        let index = elapsed
+            .checked_div(interval.as_millis())
+            .unwrap_or_default();
+        (index % frames.len().max(1) as u128) as usize
+    }
+}
+
+impl Component for Spinner {
+    type Message = Message;
+    type Properties = SpinnerProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self {
+            properties,
+            frame,
+            started: Instant::now(),
+            current: 0,
+        }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            self.current = self.current_frame();
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, _message: Self::Message) -> ShouldRender {
+        let current = self.current_frame();
+        if current != self.current {
+            self.current = current;
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn view(&self) -> Layout {
+        let SpinnerProperties {
+            style,
+            frames: SpinnerFrames { frames, .. },
+            ref label,
+        } = self.properties;
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style);
+        let mut x = frames
+            .get(self.current)
+            .map(|frame| canvas.draw_str(0, 0, style, frame))
+            .unwrap_or(0);
+        if let Some(label) = label {
+            x += canvas.draw_str(x, 0, style, " ");
+            canvas.draw_str(x, 0, style, label);
+        }
+        canvas.into()
+    }
+
+    fn tick(&self) -> Option<Self::Message> {
+        Some(Message)
+    }
+}
@@ -0,0 +1,299 @@
+use super::scrollbar::Orientation;
+use crate::{
+    layout::{self, Layout},
+    BindingMatch, BindingTransition, Callback, Canvas, Colour, Component, ComponentLink, Key,
+    MouseButton, MouseEvent, MouseEventKind, Rect, ShouldRender, Size, Style, Theme,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitPaneStyle {
+    pub divider: Style,
+    /// Style of the divider when the split pane is focused or the divider is
+    /// being dragged.
+    pub focused: Style,
+}
+
+impl Default for SplitPaneStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+
+        Self {
+            divider: Style::normal(DARK0_SOFT, GRAY_245),
+            focused: Style::normal(DARK0_SOFT, BRIGHT_BLUE),
+        }
+    }
+}
+
+impl From<&Theme> for SplitPaneStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            divider: theme.border,
+            focused: Style::normal(theme.border.background, theme.accent.foreground),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SplitPaneProperties {
+    pub style: SplitPaneStyle,
+    /// `Horizontal` places the panes side by side, `Vertical` one above the
+    /// other.
+    pub orientation: Orientation,
+    pub first: Layout,
+    pub second: Layout,
+    /// Fraction of the space given to the first pane, between 0 and 1. The
+    /// split pane keeps its own ratio once the divider is moved, until this
+    /// changes.
+    pub ratio: f64,
+    /// The minimum size of either pane, in cells.
+    pub min_size: usize,
+    pub focused: bool,
+    /// Called with the new ratio when the user moves the divider, e.g. to
+    /// persist it.
+    pub on_resize: Option<Callback<f64>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Message {
+    Grow,
+    Shrink,
+    Balance,
+    Press(usize),
+    Drag(usize),
+    Release,
+}
+
+/// Two panes separated by a divider which can be dragged with the mouse, or
+/// moved with `C-x }` and `C-x {` when the split pane is focused. `C-x +`
+/// splits the space evenly.
+pub struct SplitPane {
+    properties: SplitPaneProperties,
+    frame: Rect,
+    ratio: f64,
+    dragging: bool,
+}
+
+impl SplitPane {
+    /// The space shared by the panes, along the orientation of the split.
+    fn available(&self) -> usize {
+        let length = match self.properties.orientation {
+            Orientation::Horizontal => self.frame.size.width,
+            Orientation::Vertical => self.frame.size.height,
+        };
+        length.saturating_sub(1)
+    }
+
+    fn first_size(&self) -> usize {
+        let available = self.available();
+        let min_size = self.properties.min_size.min(available / 2);
+        ((self.ratio * available as f64).round() as usize).clamp(min_size, available - min_size)
+    }
+
+    /// Moves the divider such that the first pane has the given size.
+    fn resize_first(&mut self, size: usize) -> ShouldRender {
+        let available = self.available();
+        if available == 0 {
+            return ShouldRender::No;
+        }
+        let min_size = self.properties.min_size.min(available / 2);
+        let size = size.clamp(min_size, available - min_size);
+        if size == self.first_size() {
+            return ShouldRender::No;
+        }
+        self.set_ratio(size as f64 / available as f64)
+    }
+
+    fn set_ratio(&mut self, ratio: f64) -> ShouldRender {
+        self.ratio = ratio;
+        if let Some(on_resize) = self.properties.on_resize.as_ref() {
+            on_resize.emit(ratio);
+        }
+        ShouldRender::Yes
+    }
+
+    fn draw_divider(&self) -> Canvas {
+        let style = if self.properties.focused || self.dragging {
+            self.properties.style.focused
+        } else {
+            self.properties.style.divider
+        };
+        let (size, stroke) = match self.properties.orientation {
+            Orientation::Horizontal => (Size::new(1, self.frame.size.height), "│"),
+            Orientation::Vertical => (Size::new(self.frame.size.width, 1), "─"),
+        };
+        let mut canvas = Canvas::new(size);
+        canvas.clear_with(style, stroke);
+        canvas
+    }
+}
+
+impl Component for SplitPane {
+    type Message = Message;
+    type Properties = SplitPaneProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self {
+            ratio: properties.ratio,
+            properties,
+            frame,
+            dragging: false,
+        }
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if properties.ratio != self.properties.ratio {
+            self.ratio = properties.ratio;
+        }
+        self.properties = properties;
+        ShouldRender::Yes
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        match message {
+            Message::Grow => self.resize_first(self.first_size() + 1),
+            Message::Shrink => self.resize_first(self.first_size().saturating_sub(1)),
+            Message::Balance => self.resize_first(self.available() / 2),
+            Message::Press(position) if position == self.first_size() => {
+                self.dragging = true;
+                ShouldRender::Yes
+            }
+            Message::Drag(position) if self.dragging => self.resize_first(position),
+            Message::Release if self.dragging => {
+                self.dragging = false;
+                ShouldRender::Yes
+            }
+            Message::Press(_) | Message::Drag(_) | Message::Release => ShouldRender::No,
+        }
+    }
+
+    fn view(&self) -> Layout {
+        let first = layout::fixed(self.first_size(), self.properties.first.clone());
+        let divider = layout::fixed(1, self.draw_divider().into());
+        let second = layout::auto(self.properties.second.clone());
+        match self.properties.orientation {
+            Orientation::Horizontal => layout::row([first, divider, second]),
+            Orientation::Vertical => layout::column([first, divider, second]),
+        }
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let mut transition = BindingTransition::Clear;
+        let message = match pressed {
+            [Key::Ctrl('x')] => {
+                transition = BindingTransition::Continue;
+                None
+            }
+            [Key::Ctrl('x'), Key::Char('}')] => Some(Message::Grow),
+            [Key::Ctrl('x'), Key::Char('{')] => Some(Message::Shrink),
+            [Key::Ctrl('x'), Key::Char('+')] => Some(Message::Balance),
+            _ => None,
+        };
+        BindingMatch {
+            transition,
+            message,
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        let position = match self.properties.orientation {
+            Orientation::Horizontal => event.position.x,
+            Orientation::Vertical => event.position.y,
+        };
+        match event.kind {
+            MouseEventKind::Press(MouseButton::Left) => Some(Message::Press(position)),
+            MouseEventKind::Drag(MouseButton::Left) => Some(Message::Drag(position)),
+            MouseEventKind::Release(MouseButton::Left) => Some(Message::Release),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Position;
+
+    fn split_pane(width: usize, ratio: f64, min_size: usize) -> SplitPane {
+        let properties = SplitPaneProperties {
+            style: SplitPaneStyle::default(),
+            orientation: Orientation::Horizontal,
+            first: Canvas::new(Size::zero()).into(),
+            second: Canvas::new(Size::zero()).into(),
+            ratio,
+            min_size,
+            focused: true,
+            on_resize: None,
+        };
+        SplitPane {
+            ratio: properties.ratio,
+            properties,
+            frame: Rect::new(Position::new(0, 0), Size::new(width, 1)),
+            dragging: false,
+        }
+    }
+
+    #[test]
+    fn panes_keep_min_size() {
+        // 20 cells are shared by the panes, the divider takes the other one
+        let mut pane = split_pane(21, 0.5, 3);
+        assert_eq!(pane.first_size(), 10);
+        pane.resize_first(1);
+        assert_eq!(pane.first_size(), 3);
+        pane.resize_first(19);
+        assert_eq!(pane.first_size(), 17);
+        assert_eq!(pane.ratio, 17.0 / 20.0);
+
+        // A ratio out of bounds is clamped too
+        assert_eq!(split_pane(21, 0.0, 3).first_size(), 3);
+        // Panes too small for their minimum size split the space evenly
+        assert_eq!(split_pane(5, 0.0, 3).first_size(), 2);
+    }
+
+    #[test]
+    fn drag_divider() {
+        let mut pane = split_pane(21, 0.5, 0);
+        pane.update(Message::Press(4));
+        pane.update(Message::Drag(6));
+        assert_eq!(pane.first_size(), 10);
+        pane.update(Message::Press(10));
+        pane.update(Message::Drag(6));
+        pane.update(Message::Release);
+        pane.update(Message::Drag(8));
+        assert_eq!(pane.first_size(), 6);
+    }
+
+    #[test]
+    fn key_bindings() {
+        let mut pane = split_pane(21, 0.5, 0);
+        let ctrl_x = |key| {
+            pane.input_binding(&[Key::Ctrl('x'), Key::Char(key)])
+                .message
+        };
+        assert_eq!(ctrl_x('}'), Some(Message::Grow));
+        assert_eq!(ctrl_x('{'), Some(Message::Shrink));
+        assert_eq!(ctrl_x('+'), Some(Message::Balance));
+        assert_eq!(
+            pane.input_binding(&[Key::Ctrl('x')]).transition,
+            BindingTransition::Continue
+        );
+
+        pane.update(Message::Grow);
+        pane.update(Message::Grow);
+        assert_eq!(pane.first_size(), 12);
+        pane.update(Message::Shrink);
+        assert_eq!(pane.first_size(), 11);
+        pane.update(Message::Balance);
+        assert_eq!(pane.first_size(), 10);
+    }
+}
@@ -0,0 +1,568 @@
+use std::{borrow::Cow, cmp, cmp::Ordering, rc::Rc};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use super::text::TextAlign;
+use crate::{
+    layout::Layout, BindingMatch, BindingTransition, Callback, Canvas, Colour, Component,
+    ComponentLink, Key, MouseButton, MouseEvent, MouseEventKind, Position, Rect, ShouldRender,
+    Size, Style, Theme,
+};
+
+/// Row data displayed by a [`Table`](struct.Table.html).
+pub trait TableRow {
+    /// The text of the cell in the given column.
+    fn cell(&self, column: usize) -> Cow<'_, str>;
+
+    /// Compares two rows by the given column when sorting. Compares the cell
+    /// text by default.
+    fn compare(&self, other: &Self, column: usize) -> Ordering {
+        self.cell(column).cmp(&other.cell(column))
+    }
+}
+
+/// How the width of a column is computed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// A fixed number of cells.
+    Fixed(usize),
+    /// A share of the width left after fixed columns, proportional to the
+    /// given weight.
+    Proportional(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Column {
+    pub title: String,
+    pub width: ColumnWidth,
+    pub align: TextAlign,
+    pub sortable: bool,
+}
+
+impl Column {
+    pub fn new(title: impl Into<String>, width: ColumnWidth) -> Self {
+        Self {
+            title: title.into(),
+            width,
+            align: TextAlign::Left,
+            sortable: false,
+        }
+    }
+
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableStyle {
+    pub header: Style,
+    pub row: Style,
+    pub selected: Style,
+}
+
+impl Default for TableStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const DARK1: Colour = Colour::rgb(60, 56, 54);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+
+        Self {
+            header: Style::bold(DARK1, LIGHT2),
+            row: Style::normal(DARK0_SOFT, LIGHT2),
+            selected: Style::normal(BRIGHT_BLUE, DARK0_SOFT),
+        }
+    }
+}
+
+impl From<&Theme> for TableStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            header: Style::bold(theme.surface.background, theme.surface.foreground),
+            row: theme.background,
+            selected: theme.selection,
+        }
+    }
+}
+
+pub struct TableProperties<RowT> {
+    pub style: TableStyle,
+    pub columns: Vec<Column>,
+    /// The rows, in their original order. Compared by pointer when deciding
+    /// whether the table needs re-rendering.
+    pub rows: Rc<[RowT]>,
+    /// Index in `rows` of the selected row.
+    pub selected: usize,
+    /// The column to sort by and the order, if any.
+    pub sort: Option<(usize, SortOrder)>,
+    pub focused: bool,
+    /// Called with the index in `rows` of the newly selected row.
+    pub on_change: Option<Callback<usize>>,
+    /// Called when the header of a sortable column is clicked. Clicking the
+    /// column the table is already sorted by reverses the order.
+    pub on_sort: Option<Callback<(usize, SortOrder)>>,
+}
+
+impl<RowT> Clone for TableProperties<RowT> {
+    fn clone(&self) -> Self {
+        Self {
+            style: self.style.clone(),
+            columns: self.columns.clone(),
+            rows: self.rows.clone(),
+            selected: self.selected,
+            sort: self.sort,
+            focused: self.focused,
+            on_change: self.on_change.clone(),
+            on_sort: self.on_sort.clone(),
+        }
+    }
+}
+
+impl<RowT> PartialEq for TableProperties<RowT> {
+    fn eq(&self, other: &Self) -> bool {
+        self.style == other.style
+            && self.columns == other.columns
+            && Rc::ptr_eq(&self.rows, &other.rows)
+            && self.selected == other.selected
+            && self.sort == other.sort
+            && self.focused == other.focused
+            && self.on_change == other.on_change
+            && self.on_sort == other.on_sort
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    NextRow,
+    PreviousRow,
+    FirstRow,
+    LastRow,
+    NextPage,
+    PreviousPage,
+    /// Selects the row displayed at the given position, after sorting.
+    SelectRow(usize),
+    SortBy(usize),
+}
+
+/// A table with a header row, sortable columns and row selection.
+pub struct Table<RowT> {
+    properties: TableProperties<RowT>,
+    frame: Rect,
+    /// Indices in `rows` in display order.
+    order: Vec<usize>,
+    /// Index in `order` of the first visible row.
+    offset: usize,
+}
+
+impl<RowT: TableRow> Table<RowT> {
+    fn sort_rows(&mut self) {
+        let rows = &self.properties.rows;
+        self.order = (0..rows.len()).collect();
+        if let Some((column, order)) = self.properties.sort {
+            self.order.sort_by(|&left, &right| {
+                let ordering = rows[left].compare(&rows[right], column);
+                match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            });
+        }
+    }
+
+    fn selected_position(&self) -> usize {
+        self.order
+            .iter()
+            .position(|&index| index == self.properties.selected)
+            .unwrap_or(0)
+    }
+
+    fn num_visible_rows(&self) -> usize {
+        self.frame.size.height.saturating_sub(1)
+    }
+
+    fn ensure_selected_row_in_view(&mut self) {
+        let selected = self.selected_position();
+        let num_visible_rows = self.num_visible_rows();
+        self.offset = cmp::min(
+            self.offset,
+            self.order.len().saturating_sub(num_visible_rows),
+        );
+        if selected < self.offset {
+            self.offset = selected;
+        } else if num_visible_rows > 0 && selected >= self.offset + num_visible_rows {
+            self.offset = selected + 1 - num_visible_rows;
+        }
+    }
+
+    /// The horizontal offset and width of every column.
+    fn column_frames(&self) -> Vec<(usize, usize)> {
+        let columns = &self.properties.columns;
+        let separators = columns.len().saturating_sub(1);
+        let fixed: usize = columns
+            .iter()
+            .map(|column| match column.width {
+                ColumnWidth::Fixed(width) => width,
+                ColumnWidth::Proportional(_) => 0,
+            })
+            .sum();
+        let total_weight: usize = columns
+            .iter()
+            .map(|column| match column.width {
+                ColumnWidth::Fixed(_) => 0,
+                ColumnWidth::Proportional(weight) => weight,
+            })
+            .sum();
+        let available = self.frame.size.width.saturating_sub(fixed + separators);
+
+        let mut frames = Vec::with_capacity(columns.len());
+        let mut x = 0;
+        let mut remaining_weight = total_weight;
+        let mut remaining_width = available;
+        for column in columns.iter() {
+            let width = match column.width {
+                ColumnWidth::Fixed(width) => width,
+                // The last proportional column takes any rounding leftovers
+                ColumnWidth::Proportional(weight) if weight == remaining_weight => remaining_width,
+                ColumnWidth::Proportional(weight) => available * weight / total_weight.max(1),
+            };
+            if let ColumnWidth::Proportional(weight) = column.width {
+                remaining_weight -= weight;
+                remaining_width = remaining_width.saturating_sub(width);
+            }
+            frames.push((x, width));
+            x += width + 1;
+        }
+        frames
+    }
+
+    fn draw_cell(
+        canvas: &mut Canvas,
+        (x, width): (usize, usize),
+        y: usize,
+        style: Style,
+        align: TextAlign,
+        text: &str,
+    ) {
+        let (text, text_width) = truncate(text, width);
+        let offset = match align {
+            TextAlign::Left | TextAlign::Justify => 0,
+            TextAlign::Centre => (width - text_width) / 2,
+            TextAlign::Right => width - text_width,
+        };
+        canvas.draw_str(x + offset, y, style, text);
+    }
+}
+
+impl<RowT: TableRow + 'static> Component for Table<RowT> {
+    type Message = Message;
+    type Properties = TableProperties<RowT>;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        let mut table = Self {
+            properties,
+            frame,
+            order: Vec::new(),
+            offset: 0,
+        };
+        table.sort_rows();
+        table.ensure_selected_row_in_view();
+        table
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            let resort = !Rc::ptr_eq(&self.properties.rows, &properties.rows)
+                || self.properties.sort != properties.sort;
+            self.properties = properties;
+            if resort {
+                self.sort_rows();
+            }
+            self.ensure_selected_row_in_view();
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        self.ensure_selected_row_in_view();
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let last = self.order.len().saturating_sub(1);
+        let current = self.selected_position();
+        let page = self.num_visible_rows().max(1);
+        let new_position = match message {
+            Message::NextRow => cmp::min(current + 1, last),
+            Message::PreviousRow => current.saturating_sub(1),
+            Message::FirstRow => 0,
+            Message::LastRow => last,
+            Message::NextPage => cmp::min(current + page, last),
+            Message::PreviousPage => current.saturating_sub(page),
+            Message::SelectRow(position) => cmp::min(position, last),
+            Message::SortBy(column) => {
+                let order = match self.properties.sort {
+                    Some((sorted_column, order)) if sorted_column == column => order.reverse(),
+                    _ => SortOrder::Ascending,
+                };
+                if let Some(on_sort) = self.properties.on_sort.as_ref() {
+                    on_sort.emit((column, order));
+                }
+                return ShouldRender::No;
+            }
+        };
+        if new_position != current && !self.order.is_empty() {
+            if let Some(on_change) = self.properties.on_change.as_ref() {
+                on_change.emit(self.order[new_position]);
+            }
+        }
+        ShouldRender::No
+    }
+
+    fn view(&self) -> Layout {
+        let TableProperties {
+            ref style,
+            ref columns,
+            ref rows,
+            selected,
+            sort,
+            ..
+        } = self.properties;
+
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style.row);
+        let column_frames = self.column_frames();
+
+        // Header
+        canvas.clear_region(
+            Rect::new(Position::new(0, 0), Size::new(self.frame.size.width, 1)),
+            style.header,
+        );
+        for (index, (column, &frame)) in columns.iter().zip(column_frames.iter()).enumerate() {
+            let title = match sort {
+                Some((sorted_column, SortOrder::Ascending)) if sorted_column == index => {
+                    Cow::Owned(format!("{} ▲", column.title))
+                }
+                Some((sorted_column, SortOrder::Descending)) if sorted_column == index => {
+                    Cow::Owned(format!("{} ▼", column.title))
+                }
+                _ => Cow::Borrowed(column.title.as_str()),
+            };
+            Self::draw_cell(&mut canvas, frame, 0, style.header, column.align, &title);
+        }
+
+        // Rows
+        for (y, &row_index) in (1..self.frame.size.height).zip(self.order.iter().skip(self.offset))
+        {
+            let row_style = if row_index == selected {
+                canvas.clear_region(
+                    Rect::new(Position::new(0, y), Size::new(self.frame.size.width, 1)),
+                    style.selected,
+                );
+                style.selected
+            } else {
+                style.row
+            };
+            let row = &rows[row_index];
+            for (index, (column, &frame)) in columns.iter().zip(column_frames.iter()).enumerate() {
+                Self::draw_cell(
+                    &mut canvas,
+                    frame,
+                    y,
+                    row_style,
+                    column.align,
+                    &row.cell(index),
+                );
+            }
+        }
+
+        canvas.into()
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let message = match pressed {
+            [Key::Ctrl('n')] | [Key::Down] => Some(Message::NextRow),
+            [Key::Ctrl('p')] | [Key::Up] => Some(Message::PreviousRow),
+            [Key::Alt('<')] | [Key::Home] => Some(Message::FirstRow),
+            [Key::Alt('>')] | [Key::End] => Some(Message::LastRow),
+            [Key::Ctrl('v')] | [Key::PageDown] => Some(Message::NextPage),
+            [Key::Alt('v')] | [Key::PageUp] => Some(Message::PreviousPage),
+            _ => None,
+        };
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message,
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::ScrollDown => Some(Message::NextRow),
+            MouseEventKind::ScrollUp => Some(Message::PreviousRow),
+            MouseEventKind::Press(MouseButton::Left) if event.position.y == 0 => self
+                .column_frames()
+                .iter()
+                .position(|&(x, width)| (x..x + width).contains(&event.position.x))
+                .filter(|&column| self.properties.columns[column].sortable)
+                .map(Message::SortBy),
+            MouseEventKind::Press(MouseButton::Left) => {
+                let position = self.offset + event.position.y - 1;
+                if position < self.order.len() {
+                    Some(Message::SelectRow(position))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The longest prefix of `text` at most `width` cells wide, and its width.
+fn truncate(text: &str, width: usize) -> (&str, usize) {
+    let mut text_width = 0;
+    for (offset, grapheme) in text.grapheme_indices(true) {
+        let grapheme_width = UnicodeWidthStr::width(grapheme);
+        if text_width + grapheme_width > width {
+            return (&text[..offset], text_width);
+        }
+        text_width += grapheme_width;
+    }
+    (text, text_width)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    impl TableRow for (&'static str, usize) {
+        fn cell(&self, column: usize) -> Cow<'_, str> {
+            match column {
+                0 => Cow::Borrowed(self.0),
+                _ => Cow::Owned(self.1.to_string()),
+            }
+        }
+
+        fn compare(&self, other: &Self, column: usize) -> Ordering {
+            match column {
+                0 => self.0.cmp(other.0),
+                _ => self.1.cmp(&other.1),
+            }
+        }
+    }
+
+    type Row = (&'static str, usize);
+
+    fn table(width: usize, columns: Vec<Column>, sort: Option<(usize, SortOrder)>) -> Table<Row> {
+        let properties = TableProperties {
+            style: TableStyle::default(),
+            columns,
+            rows: vec![("b", 2), ("c", 10), ("a", 1)].into(),
+            selected: 0,
+            sort,
+            focused: true,
+            on_change: None,
+            on_sort: None,
+        };
+        let mut table = Table {
+            properties,
+            frame: Rect::new(Position::new(0, 0), Size::new(width, 5)),
+            order: Vec::new(),
+            offset: 0,
+        };
+        table.sort_rows();
+        table
+    }
+
+    #[test]
+    fn column_widths_fill_frame() {
+        let columns = vec![
+            Column::new("name", ColumnWidth::Proportional(1)),
+            Column::new("size", ColumnWidth::Fixed(4)),
+            Column::new("kind", ColumnWidth::Proportional(1)),
+            Column::new("path", ColumnWidth::Proportional(1)),
+        ];
+        for width in 7..40 {
+            let frames = table(width, columns.clone(), None).column_frames();
+            let (x, last_width) = *frames.last().unwrap();
+            assert_eq!(x + last_width, width, "{:?}", frames);
+            for pair in frames.windows(2) {
+                assert_eq!(pair[0].0 + pair[0].1 + 1, pair[1].0);
+            }
+        }
+        assert_eq!(
+            table(20, columns, None).column_frames(),
+            vec![(0, 4), (5, 4), (10, 4), (15, 5)]
+        );
+    }
+
+    #[test]
+    fn sort_by_header() {
+        let columns = vec![
+            Column::new("name", ColumnWidth::Proportional(1)).sortable(true),
+            Column::new("size", ColumnWidth::Proportional(1)),
+        ];
+        let sorted = Rc::new(RefCell::new(Vec::new()));
+        let mut table = table(21, columns, Some((0, SortOrder::Ascending)));
+        table.properties.on_sort = Some({
+            let sorted = sorted.clone();
+            (move |sort| sorted.borrow_mut().push(sort)).into()
+        });
+        assert_eq!(table.order, vec![2, 0, 1]);
+
+        let click = |x| MouseEvent {
+            kind: MouseEventKind::Press(MouseButton::Left),
+            position: Position::new(x, 0),
+            modifiers: Default::default(),
+        };
+        // The second column isn't sortable and the separator isn't a column
+        assert_eq!(table.mouse_binding(&click(15)), None);
+        assert_eq!(table.mouse_binding(&click(10)), None);
+        assert_eq!(table.mouse_binding(&click(3)), Some(Message::SortBy(0)));
+
+        // Sorting by the current column reverses the order, by another one
+        // sorts ascending
+        table.update(Message::SortBy(0));
+        table.update(Message::SortBy(1));
+        assert_eq!(
+            *sorted.borrow(),
+            vec![(0, SortOrder::Descending), (1, SortOrder::Ascending)]
+        );
+
+        // Cells are compared with `TableRow::compare`, not as text
+        let mut properties = table.properties.clone();
+        properties.sort = Some((1, SortOrder::Descending));
+        table.change(properties);
+        assert_eq!(table.order, vec![1, 0, 2]);
+    }
+}
@@ -0,0 +1,453 @@
+use std::ops::Range;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    layout::{self, Layout},
+    BindingMatch, BindingTransition, Callback, Canvas, Colour, Component, ComponentExt,
+    ComponentKey, ComponentLink, FlexDirection, Key, MouseButton, MouseEvent, MouseEventKind,
+    Position, Rect, ShouldRender, Size, Style, Theme,
+};
+
+/// A page in a [`Tabs`](struct.Tabs.html) container.
+#[derive(Clone)]
+pub struct Tab {
+    /// Identifies the tab across renders. The state of the components in a
+    /// tab is preserved as long as its key doesn't change, even when tabs are
+    /// reordered.
+    pub key: ComponentKey,
+    pub title: String,
+    pub content: Layout,
+}
+
+impl Tab {
+    pub fn new(key: impl Into<ComponentKey>, title: impl Into<String>, content: Layout) -> Self {
+        Self {
+            key: key.into(),
+            title: title.into(),
+            content,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TabsStyle {
+    pub bar: Style,
+    pub active: Style,
+    pub inactive: Style,
+    /// Style of the markers shown when some tabs don't fit in the bar.
+    pub overflow: Style,
+}
+
+impl Default for TabsStyle {
+    fn default() -> Self {
+        const DARK0: Colour = Colour::rgb(40, 40, 40);
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const DARK1: Colour = Colour::rgb(60, 56, 54);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+
+        Self {
+            bar: Style::normal(DARK0, LIGHT2),
+            active: Style::bold(BRIGHT_BLUE, DARK0_SOFT),
+            inactive: Style::normal(DARK1, LIGHT2),
+            overflow: Style::normal(DARK0, GRAY_245),
+        }
+    }
+}
+
+impl From<&Theme> for TabsStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            bar: theme.background,
+            active: Style::bold(theme.accent.foreground, theme.background.background),
+            inactive: theme.surface,
+            overflow: theme.muted,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct TabsProperties {
+    pub style: TabsStyle,
+    pub tabs: Vec<Tab>,
+    /// Index of the active tab.
+    pub selected: usize,
+    pub focused: bool,
+    /// Called with the index of the newly selected tab.
+    pub on_change: Option<Callback<usize>>,
+    /// Called with the index of a tab the user wants to close. When set, a
+    /// close button is shown on every tab.
+    pub on_close: Option<Callback<usize>>,
+    /// Called with the old and new index of a tab the user wants to move.
+    pub on_move: Option<Callback<(usize, usize)>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    NextTab,
+    PreviousTab,
+    SelectTab(usize),
+    CloseTab(usize),
+    MoveTabLeft,
+    MoveTabRight,
+}
+
+/// A tab bar with a content area showing the active tab.
+///
+/// The content of inactive tabs stays mounted (with an empty frame), so
+/// their components keep their state when switching tabs. Components in
+/// inactive tabs should not be focused.
+///
+/// ```text
+///  one  two × three × ›
+/// ```
+pub struct Tabs {
+    properties: TabsProperties,
+    frame: Rect,
+    /// Index of the first tab visible in the bar.
+    offset: usize,
+}
+
+impl Tabs {
+    const CLOSE_BUTTON: &'static str = "× ";
+    const LEFT_OVERFLOW: &'static str = "‹";
+    const RIGHT_OVERFLOW: &'static str = "›";
+
+    fn title_width(&self, tab: &Tab) -> usize {
+        let close_width = if self.properties.on_close.is_some() {
+            Self::CLOSE_BUTTON.width()
+        } else {
+            0
+        };
+        tab.title.width() + 2 + close_width
+    }
+
+    /// The visible tabs with their horizontal extent in the bar, and whether
+    /// there are more tabs to the right.
+    fn title_spans(&self) -> (Vec<(usize, Range<usize>)>, bool) {
+        let width = self.frame.size.width;
+        let mut x = if self.offset > 0 {
+            Self::LEFT_OVERFLOW.width()
+        } else {
+            0
+        };
+        let mut spans = Vec::new();
+        for (index, tab) in self.properties.tabs.iter().enumerate().skip(self.offset) {
+            if x >= width {
+                return (spans, true);
+            }
+            let end = x + self.title_width(tab);
+            spans.push((index, x..end));
+            x = end;
+        }
+        (spans, x > width)
+    }
+
+    fn ensure_selected_tab_in_view(&mut self) {
+        let selected = self.properties.selected;
+        self.offset = self
+            .offset
+            .min(self.properties.tabs.len().saturating_sub(1));
+        if selected < self.offset {
+            self.offset = selected;
+        }
+        let width = self
+            .frame
+            .size
+            .width
+            .saturating_sub(Self::RIGHT_OVERFLOW.width());
+        while self.offset < selected {
+            let (spans, _) = self.title_spans();
+            match spans.iter().find(|(index, _)| *index == selected) {
+                Some((_, span)) if span.end <= width => break,
+                _ => self.offset += 1,
+            }
+        }
+    }
+
+    fn select(&self, index: usize) {
+        if let Some(on_change) = self.properties.on_change.as_ref() {
+            if index != self.properties.selected && index < self.properties.tabs.len() {
+                on_change.emit(index);
+            }
+        }
+    }
+
+    fn move_tab(&self, to: usize) {
+        let from = self.properties.selected;
+        if let Some(on_move) = self.properties.on_move.as_ref() {
+            if from != to && to < self.properties.tabs.len() {
+                on_move.emit((from, to));
+            }
+        }
+    }
+
+    fn draw_bar(&self) -> Canvas {
+        let TabsProperties {
+            ref style,
+            ref tabs,
+            selected,
+            ..
+        } = self.properties;
+        let mut canvas = Canvas::new(Size::new(self.frame.size.width, 1));
+        canvas.clear(style.bar);
+
+        let (spans, overflow_right) = self.title_spans();
+        for (index, span) in spans {
+            let tab_style = if index == selected {
+                style.active
+            } else {
+                style.inactive
+            };
+            canvas.clear_region(
+                Rect::new(Position::new(span.start, 0), Size::new(span.len(), 1)),
+                tab_style,
+            );
+            let mut x = span.start + 1;
+            x += canvas.draw_str(x, 0, tab_style, &tabs[index].title) + 1;
+            if self.properties.on_close.is_some() {
+                canvas.draw_str(x, 0, tab_style, Self::CLOSE_BUTTON);
+            }
+        }
+
+        if self.offset > 0 {
+            canvas.draw_str(0, 0, style.overflow, Self::LEFT_OVERFLOW);
+        }
+        if overflow_right {
+            let x = self
+                .frame
+                .size
+                .width
+                .saturating_sub(Self::RIGHT_OVERFLOW.width());
+            canvas.draw_str(x, 0, style.overflow, Self::RIGHT_OVERFLOW);
+        }
+        canvas
+    }
+}
+
+impl Component for Tabs {
+    type Message = Message;
+    type Properties = TabsProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        let mut tabs = Self {
+            properties,
+            frame,
+            offset: 0,
+        };
+        tabs.ensure_selected_tab_in_view();
+        tabs
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        // The content of the tabs can't be compared, always re-render
+        self.properties = properties;
+        self.ensure_selected_tab_in_view();
+        ShouldRender::Yes
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        self.ensure_selected_tab_in_view();
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let selected = self.properties.selected;
+        match message {
+            Message::NextTab => self.select(selected + 1),
+            Message::PreviousTab => self.select(selected.wrapping_sub(1)),
+            Message::SelectTab(index) => self.select(index),
+            Message::CloseTab(index) => {
+                if let Some(on_close) = self.properties.on_close.as_ref() {
+                    if index < self.properties.tabs.len() {
+                        on_close.emit(index);
+                    }
+                }
+            }
+            Message::MoveTabLeft => self.move_tab(selected.wrapping_sub(1)),
+            Message::MoveTabRight => self.move_tab(selected + 1),
+        }
+        ShouldRender::No
+    }
+
+    fn view(&self) -> Layout {
+        let selected = self.properties.selected;
+        let panes = self.properties.tabs.iter().enumerate().map(|(index, tab)| {
+            let pane = TabPane::with_key(tab.key, tab.content.clone());
+            if index == selected {
+                layout::auto(pane)
+            } else {
+                layout::fixed(0, pane)
+            }
+        });
+        layout::column([
+            layout::fixed(1, self.draw_bar().into()),
+            layout::auto(layout::container_iter(FlexDirection::Column, panes)),
+        ])
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let mut transition = BindingTransition::Clear;
+        let message = match pressed {
+            [Key::Alt(']')] => Some(Message::NextTab),
+            [Key::Alt('[')] => Some(Message::PreviousTab),
+            [Key::Alt('}')] => Some(Message::MoveTabRight),
+            [Key::Alt('{')] => Some(Message::MoveTabLeft),
+            [Key::Ctrl('x')] => {
+                transition = BindingTransition::Continue;
+                None
+            }
+            [Key::Ctrl('x'), Key::Char('k')] => Some(Message::CloseTab(self.properties.selected)),
+            _ => None,
+        };
+        BindingMatch {
+            transition,
+            message,
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        if event.position.y != 0 {
+            return None;
+        }
+        match event.kind {
+            MouseEventKind::ScrollDown => Some(Message::NextTab),
+            MouseEventKind::ScrollUp => Some(Message::PreviousTab),
+            MouseEventKind::Press(MouseButton::Left) => {
+                let x = event.position.x;
+                let (spans, overflow_right) = self.title_spans();
+                if self.offset > 0 && x < Self::LEFT_OVERFLOW.width() {
+                    return Some(Message::SelectTab(self.offset - 1));
+                }
+                let right_overflow_x = self
+                    .frame
+                    .size
+                    .width
+                    .saturating_sub(Self::RIGHT_OVERFLOW.width());
+                if overflow_right && x >= right_overflow_x {
+                    // The first tab hidden by the marker, or the one after
+                    // the visible ones
+                    return spans
+                        .iter()
+                        .find(|(_, span)| span.end > right_overflow_x)
+                        .or_else(|| spans.last())
+                        .map(|&(index, ref span)| {
+                            if span.end > right_overflow_x {
+                                Message::SelectTab(index)
+                            } else {
+                                Message::SelectTab(index + 1)
+                            }
+                        });
+                }
+                let (index, span) = spans.into_iter().find(|(_, span)| span.contains(&x))?;
+                if self.properties.on_close.is_some() && x + Self::CLOSE_BUTTON.width() >= span.end
+                {
+                    Some(Message::CloseTab(index))
+                } else {
+                    Some(Message::SelectTab(index))
+                }
+            }
+            MouseEventKind::Press(MouseButton::Middle) => {
+                let (spans, _) = self.title_spans();
+                spans
+                    .into_iter()
+                    .find(|(_, span)| span.contains(&event.position.x))
+                    .map(|(index, _)| Message::CloseTab(index))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Wraps the content of a tab, giving its components an identity that
+/// doesn't depend on the position of the tab.
+struct TabPane {
+    content: Layout,
+}
+
+impl Component for TabPane {
+    type Message = ();
+    type Properties = Layout;
+
+    fn create(content: Self::Properties, _frame: Rect, _link: ComponentLink<Self>) -> Self {
+        Self { content }
+    }
+
+    fn change(&mut self, content: Self::Properties) -> ShouldRender {
+        self.content = content;
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        self.content.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tabs(titles: &[&str], width: usize) -> Tabs {
+        let properties = TabsProperties {
+            style: TabsStyle::default(),
+            tabs: titles
+                .iter()
+                .enumerate()
+                .map(|(index, title)| Tab::new(index, *title, Canvas::new(Size::zero()).into()))
+                .collect(),
+            selected: 0,
+            focused: true,
+            on_change: None,
+            on_close: None,
+            on_move: None,
+        };
+        Tabs {
+            properties,
+            frame: Rect::new(Position::new(0, 0), Size::new(width, 5)),
+            offset: 0,
+        }
+    }
+
+    fn select(tabs: &mut Tabs, selected: usize) -> usize {
+        let mut properties = tabs.properties.clone();
+        properties.selected = selected;
+        tabs.change(properties);
+        tabs.offset
+    }
+
+    #[test]
+    fn selected_tab_in_view() {
+        // The titles are 5, 5, 7, 6 and 6 cells wide
+        let mut tabs = tabs(&["one", "two", "three", "four", "five"], 12);
+        assert_eq!(select(&mut tabs, 1), 0);
+        // Scrolls right until the tab fits left of the overflow marker
+        assert_eq!(select(&mut tabs, 3), 3);
+        assert_eq!(select(&mut tabs, 4), 4);
+        // Scrolls left to show the tab first
+        assert_eq!(select(&mut tabs, 2), 2);
+        assert_eq!(select(&mut tabs, 0), 0);
+
+        // The overflow markers select the tabs next to the visible ones
+        select(&mut tabs, 3);
+        let click = |x| MouseEvent {
+            kind: MouseEventKind::Press(MouseButton::Left),
+            position: Position::new(x, 0),
+            modifiers: Default::default(),
+        };
+        assert_eq!(tabs.mouse_binding(&click(0)), Some(Message::SelectTab(2)));
+        assert_eq!(tabs.mouse_binding(&click(11)), Some(Message::SelectTab(4)));
+        assert_eq!(tabs.mouse_binding(&click(4)), Some(Message::SelectTab(3)));
+        select(&mut tabs, 2);
+        assert_eq!(tabs.mouse_binding(&click(11)), Some(Message::SelectTab(3)));
+
+        // A frame too narrow for any tab shows the selected one first
+        tabs.resize(Rect::new(Position::new(0, 0), Size::new(2, 5)));
+        assert_eq!(select(&mut tabs, 4), 4);
+    }
+}
@@ -1,32 +1,27 @@
+use std::cmp;
 use unicode_width::UnicodeWidthStr;
 
 use crate::{layout::Layout, Canvas, Component, ComponentLink, Rect, ShouldRender, Size, Style};
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum TextAlign {
+    #[default]
     Left,
     Centre,
     Right,
+    /// Stretches the spaces between words so that wrapped lines fill the
+    /// width, only supported by [`Paragraph`](../paragraph/struct.Paragraph.html).
+    /// Otherwise aligned left.
+    Justify,
 }
 
-impl Default for TextAlign {
-    fn default() -> Self {
-        Self::Left
-    }
-}
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum TextWrap {
+    #[default]
     None,
     Word,
 }
 
-impl Default for TextWrap {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct TextProperties {
     pub style: Style,
@@ -93,6 +88,14 @@ impl Component for Text {
         }
     }
 
+    fn measure(properties: &Self::Properties, available: Size) -> Option<Size> {
+        let size = match properties.wrap {
+            TextWrap::None => text_block_size(&properties.content),
+            TextWrap::Word => wrapped_block_size(&properties.content, available.width),
+        };
+        Some(size)
+    }
+
     fn view(&self) -> Layout {
         let Self {
             frame,
@@ -110,7 +113,7 @@ impl Component for Text {
 
         let content_size = text_block_size(content);
         let position_x = match align {
-            TextAlign::Left => 0,
+            TextAlign::Left | TextAlign::Justify => 0,
             TextAlign::Centre => (frame.size.width / 2).saturating_sub(content_size.width / 2),
             TextAlign::Right => frame.size.width.saturating_sub(content_size.width),
         };
@@ -153,3 +156,27 @@ fn text_block_size(text: &str) -> Size {
     let height = text.lines().count();
     Size::new(width, height)
 }
+
+/// The size of the text when wrapped at word boundaries to the given width,
+/// as drawn by `Text::view`.
+fn wrapped_block_size(text: &str, width: usize) -> Size {
+    let mut size = Size::zero();
+    for line in text.lines() {
+        let mut cursor_x = 0;
+        for word in line.split_whitespace() {
+            let word_width = UnicodeWidthStr::width(word);
+            if cursor_x > 0 {
+                if cursor_x >= width || word_width > width.saturating_sub(cursor_x + 1) {
+                    size.height += 1;
+                    cursor_x = 0;
+                } else {
+                    cursor_x += 1;
+                }
+            }
+            cursor_x += word_width;
+            size.width = size.width.max(cmp::min(cursor_x, width));
+        }
+        size.height += 1;
+    }
+    size
+}
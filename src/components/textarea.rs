@@ -0,0 +1,536 @@
+use ropey::Rope;
+use std::{borrow::Cow, cmp, collections::VecDeque, ops::Range};
+use unicode_width::UnicodeWidthStr;
+
+use super::input::{InputChange, InputStyle};
+use crate::{
+    layout::Layout,
+    text::{rope::RopeGraphemes, CharIndex, TextStorage},
+    BindingMatch, BindingTransition, Callback, Canvas, Component, ComponentLink, Key, Position,
+    Rect, ShouldRender,
+};
+
+pub use crate::text::Cursor;
+
+#[derive(Clone, PartialEq)]
+pub struct TextAreaProperties {
+    pub style: InputStyle,
+    pub content: Rope,
+    pub cursor: Cursor,
+    /// Whether lines longer than the frame are wrapped, breaking at
+    /// whitespace where possible. Otherwise the text area scrolls
+    /// horizontally to keep the cursor in view.
+    pub line_wrap: bool,
+    pub on_change: Option<Callback<InputChange>>,
+    pub focused: bool,
+}
+
+/// An editable, multi-line text area.
+///
+/// Like [`Input`](../input/struct.Input.html), the content and cursor are
+/// owned by the parent which is notified of changes via `on_change`. The text
+/// area keeps an undo history of the changes it made.
+pub struct TextArea {
+    properties: TextAreaProperties,
+    frame: Rect,
+    /// Char ranges of the visual rows, after wrapping.
+    rows: Vec<Range<usize>>,
+    /// Index of the first visible row.
+    scroll_offset: usize,
+    /// Number of columns scrolled to the right to keep the cursor visible,
+    /// always 0 when lines are wrapped.
+    column_offset: usize,
+    /// Column to return to when moving vertically through shorter lines.
+    preferred_column: Option<usize>,
+    undo_stack: VecDeque<(Rope, Cursor)>,
+    redo_stack: VecDeque<(Rope, Cursor)>,
+}
+
+impl TextArea {
+    fn update_layout(&mut self) {
+        let width = if self.properties.line_wrap {
+            Some(self.frame.size.width.max(1))
+        } else {
+            None
+        };
+        self.rows = wrap_rows(&self.properties.content, width);
+
+        // Scroll vertically by as little as possible to keep the cursor in view
+        let height = self.frame.size.height;
+        let cursor_row = self.row_at(self.properties.cursor.range().start.0);
+        if height == 0 {
+            self.scroll_offset = 0;
+        } else if cursor_row < self.scroll_offset {
+            self.scroll_offset = cursor_row;
+        } else if cursor_row >= self.scroll_offset + height {
+            self.scroll_offset = cursor_row + 1 - height;
+        }
+
+        // And horizontally, when lines aren't wrapped
+        let width = self.frame.size.width;
+        let cursor_column = self.column_at(self.properties.cursor.range().start.0);
+        if self.properties.line_wrap || width == 0 {
+            self.column_offset = 0;
+        } else if cursor_column < self.column_offset {
+            self.column_offset = cursor_column;
+        } else if cursor_column >= self.column_offset + width {
+            self.column_offset = cursor_column + 1 - width;
+        }
+    }
+
+    /// The index of the visual row containing `char_index`.
+    fn row_at(&self, char_index: usize) -> usize {
+        self.rows
+            .partition_point(|row| row.start <= char_index)
+            .saturating_sub(1)
+    }
+
+    /// The visual column of `char_index` in its row.
+    fn column_at(&self, char_index: usize) -> usize {
+        let row = &self.rows[self.row_at(char_index)];
+        let slice = self.properties.content.slice(row.start..char_index);
+        RopeGraphemes::new(&slice).map(grapheme_width).sum()
+    }
+
+    /// The start of the grapheme at (or closest to) `column` in `row`.
+    fn char_at(&self, row: usize, column: usize) -> usize {
+        let row = &self.rows[row];
+        let slice = self.properties.content.slice(row.start..row.end);
+        let mut char_index = row.start;
+        let mut visual_offset = 0;
+        let mut graphemes = RopeGraphemes::new(&slice).peekable();
+        while let Some(grapheme) = graphemes.next() {
+            visual_offset += grapheme_width(grapheme);
+            // The end of a wrapped row is the start of the next row, stay on
+            // the last grapheme instead.
+            if visual_offset > column || graphemes.peek().is_none() {
+                break;
+            }
+            char_index += grapheme.len_chars();
+        }
+        char_index
+    }
+
+    fn move_vertically(&mut self, cursor: &mut Cursor, num_rows: isize) {
+        let cursor_start = cursor.range().start.0;
+        let current_row = self.row_at(cursor_start);
+        let column = match self.preferred_column {
+            Some(column) => column,
+            None => {
+                let column = self.column_at(cursor_start);
+                self.preferred_column = Some(column);
+                column
+            }
+        };
+        let new_row = cmp::min(
+            (current_row as isize + num_rows).max(0) as usize,
+            self.rows.len().saturating_sub(1),
+        );
+        let char_index = self.char_at(new_row, column);
+        cursor.move_to_char(&self.properties.content, CharIndex(char_index));
+    }
+}
+
+impl Component for TextArea {
+    type Message = Message;
+    type Properties = TextAreaProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        let mut text_area = Self {
+            properties,
+            frame,
+            rows: Vec::new(),
+            scroll_offset: 0,
+            column_offset: 0,
+            preferred_column: None,
+            undo_stack: VecDeque::new(),
+            redo_stack: VecDeque::new(),
+        };
+        text_area.update_layout();
+        text_area
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            self.properties = properties;
+            self.update_layout();
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        self.update_layout();
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let mut cursor = self.properties.cursor.clone();
+        let mut new_content = None;
+        let is_vertical = matches!(
+            message,
+            Message::CursorUp | Message::CursorDown | Message::PageUp | Message::PageDown
+        );
+        let is_history = matches!(message, Message::Undo | Message::Redo);
+        let page_size = self.frame.size.height.max(1) as isize;
+        match message {
+            Message::CursorLeft => cursor.move_left(&self.properties.content),
+            Message::CursorRight => cursor.move_right(&self.properties.content),
+            Message::CursorUp => self.move_vertically(&mut cursor, -1),
+            Message::CursorDown => self.move_vertically(&mut cursor, 1),
+            Message::PageUp => self.move_vertically(&mut cursor, -page_size),
+            Message::PageDown => self.move_vertically(&mut cursor, page_size),
+            Message::StartOfLine => cursor.move_to_start_of_line(&self.properties.content),
+            Message::EndOfLine => cursor.move_to_end_of_line(&self.properties.content),
+            Message::StartOfBuffer => cursor.move_to_start_of_buffer(&self.properties.content),
+            Message::EndOfBuffer => cursor.move_to_end_of_buffer(&self.properties.content),
+            Message::BeginSelection => cursor.begin_selection(),
+            Message::ClearSelection => cursor.clear_selection(),
+            Message::InsertChar(character) => {
+                let mut content = self.properties.content.clone();
+                if cursor.has_selection() {
+                    cursor.delete_selection(&mut content);
+                }
+                cursor.insert_char(&mut content, character);
+                cursor.move_right(&content);
+                new_content = Some(content);
+            }
+            Message::InsertText(text) => {
+                let mut content = self.properties.content.clone();
+                if cursor.has_selection() {
+                    cursor.delete_selection(&mut content);
+                }
+                let start = cursor.range().start;
+                cursor.insert_chars(&mut content, text.chars());
+                cursor.move_to_char(&content, start + CharIndex(text.chars().count()));
+                new_content = Some(content);
+            }
+            Message::DeleteBackward => {
+                let mut content = self.properties.content.clone();
+                if cursor.has_selection() {
+                    cursor.delete_selection(&mut content);
+                } else {
+                    cursor.backspace(&mut content);
+                }
+                new_content = Some(content);
+            }
+            Message::DeleteForward => {
+                let mut content = self.properties.content.clone();
+                if cursor.has_selection() {
+                    cursor.delete_selection(&mut content);
+                } else {
+                    cursor.delete(&mut content);
+                }
+                new_content = Some(content);
+            }
+            Message::Undo | Message::Redo => {
+                let (from, to) = if message == Message::Undo {
+                    (&mut self.undo_stack, &mut self.redo_stack)
+                } else {
+                    (&mut self.redo_stack, &mut self.undo_stack)
+                };
+                match from.pop_back() {
+                    Some((content, previous_cursor)) => {
+                        to.push_back((self.properties.content.clone(), cursor));
+                        cursor = previous_cursor;
+                        new_content = Some(content);
+                    }
+                    None => return ShouldRender::No,
+                }
+            }
+        }
+
+        if !is_vertical {
+            self.preferred_column = None;
+        }
+
+        if let Some(ref content) = new_content {
+            if !is_history && *content != self.properties.content {
+                self.undo_stack.push_back((
+                    self.properties.content.clone(),
+                    self.properties.cursor.clone(),
+                ));
+                if self.undo_stack.len() > UNDO_LIMIT {
+                    self.undo_stack.pop_front();
+                }
+                self.redo_stack.clear();
+            }
+        }
+
+        if let Some(on_change) = self.properties.on_change.as_ref() {
+            on_change.emit(InputChange {
+                cursor,
+                content: new_content,
+            });
+        }
+
+        ShouldRender::Yes
+    }
+
+    fn view(&self) -> Layout {
+        let Self {
+            properties:
+                TextAreaProperties {
+                    ref content,
+                    ref cursor,
+                    ref style,
+                    ..
+                },
+            ref rows,
+            scroll_offset,
+            column_offset,
+            ..
+        } = *self;
+
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style.content);
+
+        let selection = if cursor.has_selection() {
+            cursor.selection()
+        } else {
+            CharIndex(0)..CharIndex(0)
+        };
+        for (y, row) in rows
+            .iter()
+            .skip(scroll_offset)
+            .take(self.frame.size.height)
+            .enumerate()
+        {
+            let mut char_offset = row.start;
+            let mut visual_offset = 0;
+            for grapheme in RopeGraphemes::new(&content.slice(row.start..row.end)) {
+                let len_chars = grapheme.len_chars();
+                let width = grapheme_width(grapheme);
+                let grapheme: Cow<str> = grapheme.into();
+                // Graphemes scrolled out of view on the left are skipped
+                if visual_offset >= column_offset {
+                    canvas.draw_str(
+                        visual_offset - column_offset,
+                        y,
+                        if cursor.range().contains(&CharIndex(char_offset)) {
+                            style.cursor
+                        } else if selection.contains(&CharIndex(char_offset)) {
+                            style.selection
+                        } else {
+                            style.content
+                        },
+                        if width > 0 { &grapheme } else { " " },
+                    );
+                }
+                visual_offset += width;
+                char_offset += len_chars;
+            }
+        }
+
+        canvas.into()
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn cursor_position(&self) -> Option<Position> {
+        let cursor_start = self.properties.cursor.range().start.0;
+        let row = self.row_at(cursor_start).checked_sub(self.scroll_offset)?;
+        Some(Position::new(
+            self.column_at(cursor_start)
+                .saturating_sub(self.column_offset),
+            row,
+        ))
+    }
+
+    fn paste_binding(&self, text: &str) -> Option<Self::Message> {
+        // Tabs are not rendered, replace them with spaces
+        let text = text.replace("\r\n", "\n").replace('\t', "    ");
+        if text.is_empty() {
+            None
+        } else {
+            Some(Message::InsertText(text))
+        }
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let message = match pressed {
+            &[Key::Ctrl('b')] | &[Key::Left] => Some(Message::CursorLeft),
+            &[Key::Ctrl('f')] | &[Key::Right] => Some(Message::CursorRight),
+            &[Key::Ctrl('p')] | &[Key::Up] => Some(Message::CursorUp),
+            &[Key::Ctrl('n')] | &[Key::Down] => Some(Message::CursorDown),
+            &[Key::Alt('v')] | &[Key::PageUp] => Some(Message::PageUp),
+            &[Key::Ctrl('v')] | &[Key::PageDown] => Some(Message::PageDown),
+            &[Key::Ctrl('a')] | &[Key::Home] => Some(Message::StartOfLine),
+            &[Key::Ctrl('e')] | &[Key::End] => Some(Message::EndOfLine),
+            &[Key::Alt('<')] => Some(Message::StartOfBuffer),
+            &[Key::Alt('>')] => Some(Message::EndOfBuffer),
+            &[Key::Ctrl(' ')] => Some(Message::BeginSelection),
+            &[Key::Ctrl('g')] => Some(Message::ClearSelection),
+            &[Key::Ctrl('z')] | &[Key::Ctrl('_')] => Some(Message::Undo),
+            &[Key::Ctrl('y')] => Some(Message::Redo),
+            &[Key::Char(character)] if character != '\t' => {
+                Some(Message::InsertChar(if character == '\r' {
+                    '\n'
+                } else {
+                    character
+                }))
+            }
+            &[Key::Ctrl('d')] | &[Key::Delete] => Some(Message::DeleteForward),
+            &[Key::Backspace] => Some(Message::DeleteBackward),
+            _ => None,
+        };
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    CursorLeft,
+    CursorRight,
+    CursorUp,
+    CursorDown,
+    PageUp,
+    PageDown,
+    StartOfLine,
+    EndOfLine,
+    StartOfBuffer,
+    EndOfBuffer,
+    BeginSelection,
+    ClearSelection,
+    InsertChar(char),
+    InsertText(String),
+    DeleteBackward,
+    DeleteForward,
+    Undo,
+    Redo,
+}
+
+const UNDO_LIMIT: usize = 1000;
+
+/// Splits `content` in visual rows, returning their char ranges. If `width`
+/// is set, lines wider than it are wrapped after the last whitespace that
+/// fits, or mid-word if there is none.
+fn wrap_rows(content: &Rope, width: Option<usize>) -> Vec<Range<usize>> {
+    let mut rows = Vec::new();
+    let mut row_start = 0;
+    let mut row_width = 0;
+    // Char offset after the last whitespace on the row, and the row's width
+    // up to it.
+    let mut last_break: Option<(usize, usize)> = None;
+    let mut char_offset = 0;
+    for grapheme in content.graphemes() {
+        let len_chars = grapheme.len_chars();
+        if grapheme.chars().last() == Some('\n') {
+            char_offset += len_chars;
+            rows.push(row_start..char_offset);
+            row_start = char_offset;
+            row_width = 0;
+            last_break = None;
+            continue;
+        }
+
+        let width_needed = grapheme_width(grapheme);
+        if let Some(width) = width {
+            if row_width > 0 && row_width + width_needed > width {
+                match last_break {
+                    Some((break_offset, break_width)) if break_offset > row_start => {
+                        rows.push(row_start..break_offset);
+                        row_start = break_offset;
+                        row_width -= break_width;
+                    }
+                    _ => {}
+                }
+                // A word longer than the whole row is broken anywhere
+                if row_width > 0 && row_width + width_needed > width {
+                    rows.push(row_start..char_offset);
+                    row_start = char_offset;
+                    row_width = 0;
+                }
+                last_break = None;
+            }
+        }
+
+        row_width += width_needed;
+        char_offset += len_chars;
+        if grapheme.chars().all(char::is_whitespace) {
+            last_break = Some((char_offset, row_width));
+        }
+    }
+    // The last row is empty after a trailing newline, for the cursor to move
+    // past it
+    rows.push(row_start..char_offset);
+    rows
+}
+
+#[inline]
+fn grapheme_width(grapheme: ropey::RopeSlice) -> usize {
+    grapheme.chunks().map(UnicodeWidthStr::width).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(content: &str, width: Option<usize>) -> Vec<String> {
+        let content = Rope::from_str(content);
+        wrap_rows(&content, width)
+            .into_iter()
+            .map(|row| content.slice(row).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn wrap_at_whitespace() {
+        assert_eq!(rows("one two\nthree\n", None), ["one two\n", "three\n", ""]);
+        assert_eq!(rows("one two\nthree", None), ["one two\n", "three"]);
+        assert_eq!(
+            rows("one two three\n", Some(8)),
+            ["one two ", "three\n", ""]
+        );
+        assert_eq!(rows("abcdefghij", Some(4)), ["abcd", "efgh", "ij"]);
+        assert_eq!(rows("", Some(4)), [""]);
+    }
+
+    #[test]
+    fn scroll_long_lines() {
+        let content = Rope::from_str("short\nabcdefghij\n");
+        let mut cursor = Cursor::new();
+        cursor.move_to_char(&content, CharIndex(16));
+        let mut text_area = TextArea {
+            properties: TextAreaProperties {
+                style: InputStyle::default(),
+                content: content.clone(),
+                cursor,
+                line_wrap: false,
+                on_change: None,
+                focused: true,
+            },
+            frame: Rect::new(Position::new(0, 0), crate::Size::new(4, 2)),
+            rows: Vec::new(),
+            scroll_offset: 0,
+            column_offset: 0,
+            preferred_column: None,
+            undo_stack: VecDeque::new(),
+            redo_stack: VecDeque::new(),
+        };
+        text_area.update_layout();
+        // The cursor is after the end of the second line, in the last column
+        assert_eq!(text_area.column_offset, 7);
+        assert_eq!(text_area.cursor_position(), Some(Position::new(3, 1)));
+
+        // Moving back to the left edge scrolls back just enough
+        let mut properties = text_area.properties.clone();
+        properties.cursor.move_to_char(&content, CharIndex(12));
+        text_area.change(properties.clone());
+        assert_eq!(text_area.column_offset, 6);
+        assert_eq!(text_area.cursor_position(), Some(Position::new(0, 1)));
+
+        // Wrapped lines never scroll horizontally
+        properties.line_wrap = true;
+        text_area.change(properties);
+        assert_eq!(text_area.column_offset, 0);
+        assert_eq!(text_area.cursor_position(), Some(Position::new(2, 1)));
+    }
+}
@@ -0,0 +1,466 @@
+use std::{cmp, collections::HashMap};
+
+use crate::{
+    layout::Layout, BindingMatch, BindingTransition, Callback, Canvas, Colour, Component,
+    ComponentLink, Key, MouseButton, MouseEvent, MouseEventKind, Position, Rect, ShouldRender,
+    Size, Style, Theme,
+};
+
+/// A node in a [`Tree`](struct.Tree.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeItem {
+    /// Uniquely identifies the node in the tree.
+    pub key: String,
+    pub label: String,
+    /// Whether the node can be expanded. Its children are only requested
+    /// when it is.
+    pub has_children: bool,
+}
+
+impl TreeItem {
+    pub fn leaf(key: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            has_children: false,
+        }
+    }
+
+    pub fn branch(key: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            has_children: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeStyle {
+    pub item: Style,
+    pub selected: Style,
+    pub guide: Style,
+}
+
+impl Default for TreeStyle {
+    fn default() -> Self {
+        const DARK0_SOFT: Colour = Colour::rgb(50, 48, 47);
+        const LIGHT2: Colour = Colour::rgb(213, 196, 161);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+
+        Self {
+            item: Style::normal(DARK0_SOFT, LIGHT2),
+            selected: Style::normal(BRIGHT_BLUE, DARK0_SOFT),
+            guide: Style::normal(DARK0_SOFT, GRAY_245),
+        }
+    }
+}
+
+impl From<&Theme> for TreeStyle {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            item: theme.background,
+            selected: theme.selection,
+            guide: theme.border,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct TreeProperties {
+    pub style: TreeStyle,
+    pub roots: Vec<TreeItem>,
+    /// Returns the children of the node with the given key. Called lazily,
+    /// when a node is expanded, and again for the expanded nodes when
+    /// `generation` changes.
+    pub children: Callback<String, Vec<TreeItem>>,
+    /// Change it, e.g. increment it, to reload the children of the expanded
+    /// nodes when they may have changed.
+    pub generation: u64,
+    /// Key of the selected node.
+    pub selected: Option<String>,
+    pub focused: bool,
+    /// Called with the key of the newly selected node.
+    pub on_change: Option<Callback<String>>,
+    /// Called with the key of the selected node when enter is pressed.
+    pub on_activate: Option<Callback<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    NextItem,
+    PreviousItem,
+    Expand,
+    Collapse,
+    Toggle,
+    Activate,
+    /// Selects the node displayed on the given row.
+    SelectRow(usize),
+    /// Expands or collapses the node displayed on the given row.
+    ToggleRow(usize),
+}
+
+/// A visible line in the tree.
+struct Row {
+    item: TreeItem,
+    depth: usize,
+    /// For every ancestor, whether it has siblings after it, in which case a
+    /// vertical guide is drawn.
+    guides: Vec<bool>,
+}
+
+/// A tree with collapsible nodes whose children are loaded on demand, e.g.
+/// for file explorers or outlines.
+pub struct Tree {
+    properties: TreeProperties,
+    frame: Rect,
+    /// Children of the expanded nodes.
+    expanded: HashMap<String, Vec<TreeItem>>,
+    rows: Vec<Row>,
+    offset: usize,
+}
+
+impl Tree {
+    fn update_rows(&mut self) {
+        fn flatten(
+            items: &[TreeItem],
+            expanded: &HashMap<String, Vec<TreeItem>>,
+            guides: &mut Vec<bool>,
+            rows: &mut Vec<Row>,
+        ) {
+            for (index, item) in items.iter().enumerate() {
+                rows.push(Row {
+                    item: item.clone(),
+                    depth: guides.len(),
+                    guides: guides.clone(),
+                });
+                if let Some(children) = expanded.get(&item.key) {
+                    guides.push(index + 1 < items.len());
+                    flatten(children, expanded, guides, rows);
+                    guides.pop();
+                }
+            }
+        }
+
+        self.rows.clear();
+        flatten(
+            &self.properties.roots,
+            &self.expanded,
+            &mut Vec::new(),
+            &mut self.rows,
+        );
+        self.ensure_selected_item_in_view();
+    }
+
+    fn selected_row(&self) -> usize {
+        self.properties
+            .selected
+            .as_ref()
+            .and_then(|selected| self.rows.iter().position(|row| row.item.key == *selected))
+            .unwrap_or(0)
+    }
+
+    fn ensure_selected_item_in_view(&mut self) {
+        let selected = self.selected_row();
+        let height = self.frame.size.height;
+        self.offset = cmp::min(self.offset, self.rows.len().saturating_sub(height));
+        if selected < self.offset {
+            self.offset = selected;
+        } else if height > 0 && selected >= self.offset + height {
+            self.offset = selected + 1 - height;
+        }
+    }
+
+    /// Whether the node displayed on the row `descendant` is in the subtree of
+    /// the one displayed on `ancestor`.
+    fn is_descendant(&self, descendant: usize, ancestor: usize) -> bool {
+        let depth = self.rows[ancestor].depth;
+        self.rows[ancestor + 1..=descendant]
+            .iter()
+            .all(|row| row.depth > depth)
+    }
+
+    fn select(&self, row: usize) {
+        if let (Some(on_change), Some(row)) =
+            (self.properties.on_change.as_ref(), self.rows.get(row))
+        {
+            if self.properties.selected.as_ref() != Some(&row.item.key) {
+                on_change.emit(row.item.key.clone());
+            }
+        }
+    }
+
+    fn toggle(&mut self, row: usize) -> ShouldRender {
+        let item = match self.rows.get(row) {
+            Some(row) if row.item.has_children => row.item.clone(),
+            _ => return ShouldRender::No,
+        };
+        let selected = self.selected_row();
+        if self.expanded.remove(&item.key).is_none() {
+            let children = self.properties.children.emit(item.key.clone());
+            self.expanded.insert(item.key, children);
+        } else if selected > row && self.is_descendant(selected, row) {
+            // The selected node is hidden, select its nearest visible ancestor
+            self.select(row);
+        }
+        self.update_rows();
+        ShouldRender::Yes
+    }
+}
+
+impl Component for Tree {
+    type Message = Message;
+    type Properties = TreeProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        let mut tree = Self {
+            properties,
+            frame,
+            expanded: HashMap::new(),
+            rows: Vec::new(),
+            offset: 0,
+        };
+        tree.update_rows();
+        tree
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        if self.properties != properties {
+            let reload_children = self.properties.generation != properties.generation;
+            self.properties = properties;
+            if reload_children {
+                let children = &self.properties.children;
+                for (key, items) in self.expanded.iter_mut() {
+                    *items = children.emit(key.clone());
+                }
+            }
+            self.update_rows();
+            ShouldRender::Yes
+        } else {
+            ShouldRender::No
+        }
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        self.ensure_selected_item_in_view();
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let selected = self.selected_row();
+        match message {
+            Message::NextItem => {
+                self.select(cmp::min(selected + 1, self.rows.len().saturating_sub(1)))
+            }
+            Message::PreviousItem => self.select(selected.saturating_sub(1)),
+            Message::SelectRow(row) => self.select(row),
+            Message::ToggleRow(row) => return self.toggle(row),
+            Message::Toggle => return self.toggle(selected),
+            Message::Expand => match self.rows.get(selected) {
+                Some(row) if self.expanded.contains_key(&row.item.key) => self.select(selected + 1),
+                Some(_) => return self.toggle(selected),
+                None => {}
+            },
+            Message::Collapse => match self.rows.get(selected) {
+                Some(row) if self.expanded.contains_key(&row.item.key) => {
+                    return self.toggle(selected)
+                }
+                // Select the parent
+                Some(row) => {
+                    let depth = row.depth;
+                    if let Some(parent) = self.rows[..selected]
+                        .iter()
+                        .rposition(|row| row.depth < depth)
+                    {
+                        self.select(parent);
+                    }
+                }
+                None => {}
+            },
+            Message::Activate => {
+                if let (Some(on_activate), Some(row)) = (
+                    self.properties.on_activate.as_ref(),
+                    self.rows.get(selected),
+                ) {
+                    on_activate.emit(row.item.key.clone());
+                }
+            }
+        }
+        ShouldRender::No
+    }
+
+    fn view(&self) -> Layout {
+        let style = &self.properties.style;
+        let selected = self.selected_row();
+        let mut canvas = Canvas::new(self.frame.size);
+        canvas.clear(style.item);
+
+        for (y, (index, row)) in self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(self.frame.size.height)
+            .enumerate()
+        {
+            let mut x = 0;
+            for &has_guide in row.guides.iter() {
+                x += canvas.draw_str(x, y, style.guide, if has_guide { "│ " } else { "  " });
+            }
+            let item_style = if index == selected {
+                canvas.clear_region(
+                    Rect::new(
+                        Position::new(x, y),
+                        Size::new(self.frame.size.width.saturating_sub(x), 1),
+                    ),
+                    style.selected,
+                );
+                style.selected
+            } else {
+                style.item
+            };
+            let marker = if !row.item.has_children {
+                "  "
+            } else if self.expanded.contains_key(&row.item.key) {
+                "▾ "
+            } else {
+                "▸ "
+            };
+            x += canvas.draw_str(x, y, item_style, marker);
+            canvas.draw_str(x, y, item_style, &row.item.label);
+        }
+
+        canvas.into()
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let message = match pressed {
+            [Key::Ctrl('n')] | [Key::Down] => Some(Message::NextItem),
+            [Key::Ctrl('p')] | [Key::Up] => Some(Message::PreviousItem),
+            [Key::Ctrl('f')] | [Key::Right] => Some(Message::Expand),
+            [Key::Ctrl('b')] | [Key::Left] => Some(Message::Collapse),
+            [Key::Char('\t')] | [Key::Char(' ')] => Some(Message::Toggle),
+            [Key::Char('\n')] => Some(Message::Activate),
+            _ => None,
+        };
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message,
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::ScrollDown => Some(Message::NextItem),
+            MouseEventKind::ScrollUp => Some(Message::PreviousItem),
+            MouseEventKind::Press(MouseButton::Left) => {
+                let index = self.offset + event.position.y;
+                let row = self.rows.get(index)?;
+                // Clicking the expand marker toggles the node
+                let marker_x = 2 * row.depth;
+                if (marker_x..marker_x + 2).contains(&event.position.x) {
+                    Some(Message::ToggleRow(index))
+                } else {
+                    Some(Message::SelectRow(index))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    /// A tree with `a` and `b` at the root, each with two children. Requests
+    /// for children are logged in `loaded`, and selections in `selected`.
+    fn new_tree(loaded: &Log, selected: &Log) -> Tree {
+        let (loaded, selected) = (loaded.clone(), selected.clone());
+        let properties = TreeProperties {
+            style: TreeStyle::default(),
+            roots: vec![TreeItem::branch("a", "a"), TreeItem::branch("b", "b")],
+            children: (move |key: String| {
+                loaded.borrow_mut().push(key.clone());
+                vec![
+                    TreeItem::leaf(format!("{}/1", key), "1"),
+                    TreeItem::leaf(format!("{}/2", key), "2"),
+                ]
+            })
+            .into(),
+            generation: 0,
+            selected: None,
+            focused: true,
+            on_change: Some((move |key| selected.borrow_mut().push(key)).into()),
+            on_activate: None,
+        };
+        let mut tree = Tree {
+            properties,
+            frame: Rect::new(Position::new(0, 0), Size::new(10, 10)),
+            expanded: HashMap::new(),
+            rows: Vec::new(),
+            offset: 0,
+        };
+        tree.update_rows();
+        tree
+    }
+
+    fn keys(tree: &Tree) -> Vec<&str> {
+        tree.rows.iter().map(|row| row.item.key.as_str()).collect()
+    }
+
+    #[test]
+    fn collapsing_ancestor_selects_it() {
+        let (loaded, selected) = (Log::default(), Log::default());
+        let mut tree = new_tree(&loaded, &selected);
+        tree.update(Message::ToggleRow(0));
+        assert_eq!(keys(&tree), ["a", "a/1", "a/2", "b"]);
+
+        tree.change(TreeProperties {
+            selected: Some("a/2".into()),
+            ..tree.properties.clone()
+        });
+        tree.update(Message::ToggleRow(0));
+        assert_eq!(keys(&tree), ["a", "b"]);
+        assert_eq!(*selected.borrow(), ["a"]);
+
+        // Collapsing another node leaves the selection alone
+        tree.update(Message::ToggleRow(1));
+        tree.update(Message::ToggleRow(1));
+        assert_eq!(*selected.borrow(), ["a"]);
+    }
+
+    #[test]
+    fn children_reloaded_by_generation() {
+        let (loaded, selected) = (Log::default(), Log::default());
+        let mut tree = new_tree(&loaded, &selected);
+        assert!(loaded.borrow().is_empty());
+        tree.update(Message::ToggleRow(0));
+        assert_eq!(*loaded.borrow(), ["a"]);
+
+        // A new callback alone doesn't reload the children
+        let other = new_tree(&loaded, &selected);
+        tree.change(TreeProperties {
+            focused: false,
+            ..other.properties.clone()
+        });
+        assert_eq!(*loaded.borrow(), ["a"]);
+
+        tree.change(TreeProperties {
+            generation: 1,
+            ..tree.properties.clone()
+        });
+        assert_eq!(*loaded.borrow(), ["a", "a"]);
+        assert_eq!(keys(&tree), ["a", "a/1", "a/2", "b"]);
+    }
+}
@@ -0,0 +1,213 @@
+use std::cmp;
+
+use super::scrollbar::ScrollExtent;
+use crate::{
+    component::layout::viewport_content_size,
+    layout::{self, Layout},
+    BindingMatch, BindingTransition, Callback, Component, ComponentLink, Key, MouseEvent,
+    MouseEventKind, Position, Rect, ShouldRender, Size,
+};
+
+/// The scroll state of a viewport, e.g. to show scrollbars next to it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ViewportScroll {
+    pub horizontal: ScrollExtent,
+    pub vertical: ScrollExtent,
+}
+
+impl ViewportScroll {
+    /// The position of the content shown in the top left corner.
+    pub fn offset(&self) -> Position {
+        Position::new(self.horizontal.offset, self.vertical.offset)
+    }
+}
+
+#[derive(Clone)]
+pub struct ViewportProperties {
+    /// The layout to scroll, laid out in its whole size.
+    pub content: Layout,
+    /// The size of the content, measured from the layout if `None` (see
+    /// [`Component::measure`](../../trait.Component.html#method.measure)).
+    pub content_size: Option<Size>,
+    pub focused: bool,
+    /// Called with the new scroll state when the viewport scrolls, or when the
+    /// size of the content or of the viewport changes.
+    pub on_scroll: Option<Callback<ViewportScroll>>,
+}
+
+impl ViewportProperties {
+    pub fn new(content: Layout) -> Self {
+        Self {
+            content,
+            content_size: None,
+            focused: false,
+            on_scroll: None,
+        }
+    }
+
+    pub fn content_size(mut self, content_size: Size) -> Self {
+        self.content_size = Some(content_size);
+        self
+    }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    pub fn on_scroll(mut self, on_scroll: impl Into<Callback<ViewportScroll>>) -> Self {
+        self.on_scroll = Some(on_scroll.into());
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    ScrollBy(isize, isize),
+    ScrollPageDown,
+    ScrollPageUp,
+    Top,
+    Bottom,
+}
+
+/// Makes any layout scrollable, showing the part of it which fits in the
+/// frame of the viewport. The content is drawn in its whole size and clipped,
+/// so the components in it don't have to know they're scrolled.
+///
+/// Scrolls with the mouse wheel and, when focused, with the arrow keys or
+/// `C-n`, `C-p`, `C-f`, `C-b`, `C-v`, `A-v`, `A-<` and `A->`. Components in the
+/// content get mouse events where they're shown.
+pub struct Viewport {
+    properties: ViewportProperties,
+    frame: Rect,
+    offset: Position,
+    /// The scroll state last reported with `on_scroll`.
+    reported: Option<ViewportScroll>,
+}
+
+impl Viewport {
+    fn scroll(&self) -> ViewportScroll {
+        let content_size = viewport_content_size(
+            self.properties.content_size,
+            &self.properties.content,
+            self.frame.size,
+        );
+        let horizontal = ScrollExtent::new(content_size.width, self.frame.size.width, 0);
+        let vertical = ScrollExtent::new(content_size.height, self.frame.size.height, 0);
+        ViewportScroll {
+            horizontal: ScrollExtent {
+                offset: cmp::min(self.offset.x, horizontal.max_offset()),
+                ..horizontal
+            },
+            vertical: ScrollExtent {
+                offset: cmp::min(self.offset.y, vertical.max_offset()),
+                ..vertical
+            },
+        }
+    }
+
+    /// Clamps the offset to the content and reports the scroll state if it
+    /// changed.
+    fn update_scroll(&mut self) {
+        let scroll = self.scroll();
+        self.offset = scroll.offset();
+        if self.reported != Some(scroll) {
+            self.reported = Some(scroll);
+            if let Some(on_scroll) = self.properties.on_scroll.as_ref() {
+                on_scroll.emit(scroll);
+            }
+        }
+    }
+}
+
+impl Component for Viewport {
+    type Message = Message;
+    type Properties = ViewportProperties;
+
+    fn create(properties: Self::Properties, frame: Rect, _link: ComponentLink<Self>) -> Self {
+        let mut viewport = Self {
+            properties,
+            frame,
+            offset: Position::zero(),
+            reported: None,
+        };
+        viewport.update_scroll();
+        viewport
+    }
+
+    fn change(&mut self, properties: Self::Properties) -> ShouldRender {
+        // Layouts can't be compared, the content is assumed to have changed
+        self.properties = properties;
+        self.update_scroll();
+        ShouldRender::Yes
+    }
+
+    fn resize(&mut self, frame: Rect) -> ShouldRender {
+        self.frame = frame;
+        self.update_scroll();
+        ShouldRender::Yes
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let scroll = self.scroll();
+        let offset = scroll.offset();
+        self.offset = match message {
+            Message::ScrollBy(x, y) => Position::new(
+                offset.x.saturating_add_signed(x),
+                offset.y.saturating_add_signed(y),
+            ),
+            Message::ScrollPageDown => Position::new(offset.x, offset.y + scroll.vertical.viewport),
+            Message::ScrollPageUp => {
+                Position::new(offset.x, offset.y.saturating_sub(scroll.vertical.viewport))
+            }
+            Message::Top => Position::new(offset.x, 0),
+            Message::Bottom => Position::new(offset.x, scroll.vertical.max_offset()),
+        };
+        self.update_scroll();
+        (self.offset != offset).into()
+    }
+
+    fn view(&self) -> Layout {
+        layout::viewport(
+            viewport_content_size(
+                self.properties.content_size,
+                &self.properties.content,
+                self.frame.size,
+            ),
+            self.offset,
+            self.properties.content.clone(),
+        )
+    }
+
+    fn has_focus(&self) -> bool {
+        self.properties.focused
+    }
+
+    fn input_binding(&self, pressed: &[Key]) -> BindingMatch<Self::Message> {
+        let message = match pressed {
+            [Key::Ctrl('n')] | [Key::Down] => Some(Message::ScrollBy(0, 1)),
+            [Key::Ctrl('p')] | [Key::Up] => Some(Message::ScrollBy(0, -1)),
+            [Key::Ctrl('f')] | [Key::Right] => Some(Message::ScrollBy(1, 0)),
+            [Key::Ctrl('b')] | [Key::Left] => Some(Message::ScrollBy(-1, 0)),
+            [Key::Ctrl('v')] | [Key::PageDown] => Some(Message::ScrollPageDown),
+            [Key::Alt('v')] | [Key::PageUp] => Some(Message::ScrollPageUp),
+            [Key::Alt('<')] | [Key::Home] => Some(Message::Top),
+            [Key::Alt('>')] | [Key::End] => Some(Message::Bottom),
+            _ => None,
+        };
+        BindingMatch {
+            transition: BindingTransition::Clear,
+            message,
+        }
+    }
+
+    fn mouse_binding(&self, event: &MouseEvent) -> Option<Self::Message> {
+        match event.kind {
+            MouseEventKind::ScrollUp => Some(Message::ScrollBy(0, -3)),
+            MouseEventKind::ScrollDown => Some(Message::ScrollBy(0, 3)),
+            MouseEventKind::ScrollLeft => Some(Message::ScrollBy(-3, 0)),
+            MouseEventKind::ScrollRight => Some(Message::ScrollBy(3, 0)),
+            _ => None,
+        }
+    }
+}
@@ -124,13 +124,20 @@ pub mod terminal;
 pub use app::App;
 pub use component::{
     layout::{
-        self, auto, column, component, container, fixed, row, ComponentExt, ComponentKey,
-        FlexBasis, FlexDirection, Item,
+        self, auto, column, component, container, content, fixed, overlay, percent, ratio, row,
+        Align, ComponentExt, ComponentKey, Constraints, FlexBasis, FlexDirection, Item, Justify,
+        Layer,
     },
     BindingMatch, BindingTransition, Callback, Component, ComponentLink, Layout, ShouldRender,
 };
 pub use error::{Error, Result};
-pub use terminal::{Background, Canvas, Colour, Foreground, Key, Position, Rect, Size, Style};
+pub use notification::{Toast, ToastLevel};
+pub use terminal::{
+    Background, Blink, Canvas, Colour, CursorShape, CursorStyle, Foreground, Key, Line, MediaKey,
+    Modifiers, MouseButton, MouseEvent, MouseEventKind, Position, Rect, Size, Span, Style,
+    Underline,
+};
+pub use theme::Theme;
 
 pub mod prelude {
     //! The Zi prelude.
@@ -139,11 +146,15 @@ pub mod prelude {
         layout, BindingMatch, BindingTransition, Component, ComponentExt, ComponentLink, Layout,
         ShouldRender,
     };
-    pub use super::{Background, Canvas, Colour, Foreground, Key, Position, Rect, Size, Style};
+    pub use super::{
+        Background, Canvas, Colour, Foreground, Key, MouseEvent, Position, Rect, Size, Style,
+    };
 }
 
 // Crate only modules
 pub(crate) mod app;
 pub(crate) mod component;
 pub(crate) mod error;
+pub(crate) mod notification;
 pub(crate) mod text;
+pub(crate) mod theme;
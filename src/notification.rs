@@ -0,0 +1,172 @@
+//! Toast notifications, posted by components with
+//! [`ComponentLink::notify`](struct.ComponentLink.html#method.notify) and
+//! drawn by the `App` over everything else.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    terminal::{Canvas, Colour, Position, Rect, Size, Style},
+    theme::Theme,
+};
+
+/// How important a [`Toast`](struct.Toast.html) is, it determines its colour
+/// in the current [`Theme`](struct.Theme.html).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ToastLevel {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+/// A short message shown in the top right corner of the screen, dismissed
+/// automatically after a timeout.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use zi::Toast;
+/// let toast = Toast::warning("Low disk space").timeout(Duration::from_secs(10));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Toast {
+    /// The text of the toast, it may contain newlines.
+    pub message: String,
+    pub level: ToastLevel,
+    pub timeout: Duration,
+}
+
+impl Toast {
+    pub fn new(level: ToastLevel, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Info, message)
+    }
+
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Success, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Warning, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Error, message)
+    }
+
+    /// Sets how long the toast is shown for.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl From<&str> for Toast {
+    fn from(message: &str) -> Self {
+        Self::info(message)
+    }
+}
+
+impl From<String> for Toast {
+    fn from(message: String) -> Self {
+        Self::info(message)
+    }
+}
+
+/// The toasts currently shown, with the time they expire at.
+#[derive(Default)]
+pub(crate) struct Notifications {
+    toasts: VecDeque<(Toast, Instant)>,
+}
+
+impl Notifications {
+    pub(crate) fn post(&mut self, toast: Toast) {
+        let expires = Instant::now() + toast.timeout;
+        self.toasts.push_front((toast, expires));
+        self.toasts.truncate(MAX_TOASTS);
+    }
+
+    /// Returns the time until the next toast expires, if any is shown.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.toasts
+            .iter()
+            .map(|(_, expires)| expires.saturating_duration_since(now))
+            .min()
+    }
+
+    /// Removes the expired toasts. Returns whether any was removed.
+    pub(crate) fn expire(&mut self) -> bool {
+        let now = Instant::now();
+        let num_toasts = self.toasts.len();
+        self.toasts.retain(|(_, expires)| *expires > now);
+        self.toasts.len() != num_toasts
+    }
+
+    /// Draws the toasts stacked in the top right corner of the screen, newest
+    /// first, on the surface style of the theme.
+    pub(crate) fn draw(&self, screen: &mut Canvas, theme: &Theme) {
+        let toast_style = theme.surface;
+        let screen_size = screen.size();
+        let max_width = MAX_WIDTH.min(screen_size.width.saturating_sub(2));
+        let mut y = 1;
+        for (toast, _) in self.toasts.iter() {
+            let lines: Vec<&str> = toast.message.lines().collect();
+            let width = lines
+                .iter()
+                .map(|line| line.width() + 4)
+                .max()
+                .unwrap_or(4)
+                .min(max_width);
+            let height = lines.len().max(1);
+            if y + height > screen_size.height {
+                break;
+            }
+
+            let mut canvas = Canvas::new(Size::new(width, height));
+            canvas.clear(toast_style);
+            let accent = Style {
+                foreground: level_colour(theme, toast.level),
+                ..toast_style
+            };
+            for (line_index, line) in lines.iter().enumerate() {
+                canvas.draw_str(0, line_index, accent, "▌");
+                canvas.draw_str(2, line_index, toast_style, line);
+            }
+            if lines.is_empty() {
+                canvas.draw_str(0, 0, accent, "▌");
+            }
+
+            let x = screen_size.width.saturating_sub(width + 1);
+            screen.copy_region(
+                &canvas,
+                Rect::new(Position::new(x, y), Size::new(width, height)),
+            );
+            y += height + 1;
+        }
+    }
+}
+
+fn level_colour(theme: &Theme, level: ToastLevel) -> Colour {
+    match level {
+        ToastLevel::Info => theme.accent.foreground,
+        ToastLevel::Success => theme.success.foreground,
+        ToastLevel::Warning => theme.warning.foreground,
+        ToastLevel::Error => theme.error.foreground,
+    }
+}
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(4);
+const MAX_TOASTS: usize = 5;
+const MAX_WIDTH: usize = 50;
@@ -1,20 +1,24 @@
 use smallstr::SmallString;
-use std::{self, cmp, iter};
+use std::{self, cmp, iter, sync::Arc};
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
-use super::{Position, Size};
+use super::{Image, ImagePlacement, Line, Position, Size};
 use crate::terminal::Rect;
 
 /// An extended grapheme cluster represented as a `SmallString`.
 pub type GraphemeCluster = SmallString<[u8; 16]>;
 
+/// The target URL of a hyperlink, shared by all the textels it covers.
+pub type Hyperlink = Arc<str>;
+
 /// A "text element", which consists of an extended grapheme cluster and
 /// associated styling.
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Textel {
     pub grapheme: GraphemeCluster,
     pub style: Style,
+    pub link: Option<Hyperlink>,
 }
 
 /// A lightweight abstract terminal. All components in Zi ultimately draw to a
@@ -25,6 +29,9 @@ pub struct Canvas {
     buffer: Vec<Option<Textel>>,
     size: Size,
     min_size: Size,
+    cursor: Option<Position>,
+    cursor_style: CursorStyle,
+    images: Vec<ImagePlacement>,
 }
 
 impl Canvas {
@@ -44,6 +51,9 @@ impl Canvas {
                 .collect(),
             size,
             min_size: Size::zero(),
+            cursor: None,
+            cursor_style: CursorStyle::default(),
+            images: Vec::new(),
         }
     }
 
@@ -57,6 +67,31 @@ impl Canvas {
         self.min_size
     }
 
+    /// Returns the position of the cursor, if it is visible.
+    #[inline]
+    pub fn cursor(&self) -> Option<Position> {
+        self.cursor
+    }
+
+    /// Sets the position of the cursor. Backends will hide the cursor if
+    /// `None`.
+    #[inline]
+    pub fn set_cursor(&mut self, cursor: Option<Position>) {
+        self.cursor = cursor;
+    }
+
+    /// Returns how the cursor should be drawn.
+    #[inline]
+    pub fn cursor_style(&self) -> CursorStyle {
+        self.cursor_style
+    }
+
+    /// Sets how the cursor should be drawn.
+    #[inline]
+    pub fn set_cursor_style(&mut self, cursor_style: CursorStyle) {
+        self.cursor_style = cursor_style;
+    }
+
     #[inline]
     pub fn buffer(&self) -> &[Option<Textel>] {
         self.buffer.as_slice()
@@ -67,11 +102,20 @@ impl Canvas {
         self.buffer.as_mut_slice()
     }
 
+    /// Returns the images drawn on the canvas, in the order they were drawn.
+    #[inline]
+    pub fn images(&self) -> &[ImagePlacement] {
+        &self.images
+    }
+
     #[inline]
     pub fn resize(&mut self, size: Size) {
         self.buffer.resize(size.area(), Default::default());
         self.size = size;
         self.min_size = size.min(self.min_size);
+        let bounds = Rect::from_size(size);
+        self.images
+            .retain(|placement| bounds.contains_rect(&placement.region));
     }
 
     #[inline]
@@ -85,20 +129,64 @@ impl Canvas {
                 .iter_mut()
                 .for_each(|textel| clear_textel(textel, style, " "));
         }
+        self.remove_images(region);
     }
 
     #[inline]
     pub fn clear(&mut self, style: Style) {
         self.buffer
             .iter_mut()
-            .for_each(|textel| clear_textel(textel, style, " "))
+            .for_each(|textel| clear_textel(textel, style, " "));
+        self.images.clear();
     }
 
     #[inline]
     pub fn clear_with(&mut self, style: Style, content: &str) {
         self.buffer
             .iter_mut()
-            .for_each(|textel| clear_textel(textel, style, content))
+            .for_each(|textel| clear_textel(textel, style, content));
+        self.images.clear();
+    }
+
+    /// Draws an image scaled to fill `region`.
+    ///
+    /// The cells are drawn with an approximation of the image using half
+    /// blocks, two pixels per cell. Backends whose terminal supports a graphics
+    /// protocol draw the image itself over them, see
+    /// [`Capabilities`](../backend/struct.Capabilities.html). Images outside of
+    /// the canvas or partially covered by content drawn later are only
+    /// approximated. Empty images aren't drawn.
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use zi::{terminal::Image, Canvas, Colour, Position, Rect, Size};
+    /// let image = Image::new(Size::new(1, 2), vec![Colour::black(), Colour::white()]);
+    /// let mut canvas = Canvas::new(Size::new(10, 10));
+    /// canvas.draw_image(Rect::new(Position::new(2, 2), Size::new(4, 4)), Arc::new(image));
+    /// assert_eq!(canvas.images().len(), 1);
+    /// ```
+    pub fn draw_image(&mut self, region: Rect, image: Arc<Image>) {
+        if region.is_empty() || image.size().is_empty() {
+            return;
+        }
+        let pixels = image.resized(Size::new(region.size.width, 2 * region.size.height));
+        let mut grid = SquarePixelGrid::new(pixels.size());
+        for y in 0..pixels.size().height {
+            for x in 0..pixels.size().width {
+                grid.draw(Position::new(x, y), pixels.pixel(x, y));
+            }
+        }
+        self.copy_region(&grid.into_canvas(), region);
+        if Rect::from_size(self.size).contains_rect(&region) {
+            self.images.push(ImagePlacement { region, image });
+        }
+    }
+
+    /// Removes the images intersecting `region`, the content drawn over them
+    /// takes precedence.
+    fn remove_images(&mut self, region: Rect) {
+        self.images
+            .retain(|placement| !placement.region.intersects(&region));
     }
 
     #[inline]
@@ -106,6 +194,52 @@ impl Canvas {
         self.draw_graphemes(x, y, style, UnicodeSegmentation::graphemes(text, true))
     }
 
+    /// Draws the spans of a line one after the other, each with its own
+    /// style. Returns the number of columns drawn.
+    ///
+    /// ```
+    /// # use zi::{Canvas, Colour, Line, Size, Style};
+    /// let mut canvas = Canvas::new(Size::new(20, 1));
+    /// let line = Line::new()
+    ///     .span("error: ", Style::bold(Colour::black(), Colour::rgb(251, 73, 52)))
+    ///     .span("file not found", Style::default());
+    /// assert_eq!(canvas.draw_line(0, 0, &line), 20);
+    /// ```
+    pub fn draw_line(&mut self, x: usize, y: usize, line: &Line) -> usize {
+        line.spans.iter().fold(0, |num_drawn, span| {
+            num_drawn + self.draw_str(x + num_drawn, y, span.style, &span.content)
+        })
+    }
+
+    /// Draws `text` like [`draw_str`](#method.draw_str), marking it as a
+    /// hyperlink to `url`. Backends that support it let the user open the
+    /// link, e.g. terminals supporting OSC 8 on click.
+    ///
+    /// ```
+    /// # use zi::{Canvas, Size, Style};
+    /// let mut canvas = Canvas::new(Size::new(20, 1));
+    /// canvas.draw_hyperlink(0, 0, Style::default(), "zi", "https://github.com/mcobzarenco/zi");
+    /// ```
+    pub fn draw_hyperlink(
+        &mut self,
+        x: usize,
+        y: usize,
+        style: Style,
+        text: &str,
+        url: &str,
+    ) -> usize {
+        let num_drawn = self.draw_str(x, y, style, text);
+        if num_drawn > 0 {
+            let link = Hyperlink::from(url);
+            let offset = y * self.size.width + x;
+            self.buffer[offset..offset + num_drawn]
+                .iter_mut()
+                .flatten()
+                .for_each(|textel| textel.link = Some(link.clone()));
+        }
+        num_drawn
+    }
+
     #[inline]
     pub fn draw_graphemes(
         &mut self,
@@ -118,10 +252,17 @@ impl Canvas {
             return 0;
         }
 
-        let initial_offset = y * self.size.width + x;
-        let max_offset = cmp::min((y + 1) * self.size.width + x, self.buffer.len());
+        let line_offset = y * self.size.width;
+        let initial_offset = line_offset + x;
+        let max_offset = cmp::min(line_offset + self.size.width, self.buffer.len());
         let mut current_offset = initial_offset;
 
+        // Drawing over the second half of a wide grapheme, replace it with a
+        // space to keep every cell covered by exactly one textel.
+        if current_offset > line_offset && self.buffer[current_offset].is_none() {
+            clear_wide_textel(&mut self.buffer[line_offset..current_offset]);
+        }
+
         for grapheme in graphemes {
             if current_offset >= max_offset {
                 break;
@@ -133,18 +274,47 @@ impl Canvas {
                 continue;
             }
 
-            self.buffer[current_offset] = Some(Textel { grapheme, style });
+            // A wide grapheme that doesn't fit at the end of the line would
+            // make the terminal wrap, pad the line with a space instead.
+            if grapheme_width > max_offset - current_offset {
+                self.buffer[current_offset] = Some(Textel {
+                    grapheme: " ".into(),
+                    style,
+                    link: None,
+                });
+                current_offset = max_offset;
+                break;
+            }
 
-            let num_modified = cmp::min(grapheme_width, max_offset - current_offset);
-            self.buffer[current_offset + 1..current_offset + num_modified]
+            self.buffer[current_offset] = Some(Textel {
+                grapheme,
+                style,
+                link: None,
+            });
+            self.buffer[current_offset + 1..current_offset + grapheme_width]
                 .iter_mut()
                 .for_each(|textel| *textel = None);
 
-            current_offset += num_modified;
+            current_offset += grapheme_width;
+        }
+
+        // Drawing over the first half of a wide grapheme, replace its now
+        // orphaned second half with a space.
+        if current_offset < max_offset && self.buffer[current_offset].is_none() {
+            self.buffer[current_offset..max_offset]
+                .iter_mut()
+                .take_while(|textel| textel.is_none())
+                .for_each(|textel| {
+                    *textel = Some(Textel {
+                        grapheme: " ".into(),
+                        style,
+                        link: None,
+                    })
+                });
         }
 
         // Update `min_size`
-        self.min_size.width = cmp::max(self.min_size.width, current_offset);
+        self.min_size.width = cmp::max(self.min_size.width, current_offset - line_offset);
         self.min_size.height = cmp::max(self.min_size.height, y);
 
         current_offset - initial_offset
@@ -168,6 +338,46 @@ impl Canvas {
                 )
                 .for_each(|(textel, other)| *textel = other.clone());
         }
+
+        self.remove_images(Rect::new(region.origin, source.size));
+        let bounds = Rect::from_size(self.size);
+        self.images.extend(
+            source
+                .images
+                .iter()
+                .map(|placement| ImagePlacement {
+                    region: placement.region.translate(region.origin.to_vector()),
+                    image: placement.image.clone(),
+                })
+                .filter(|placement| bounds.contains_rect(&placement.region)),
+        );
+    }
+
+    /// Returns a copy of a region of the canvas, e.g. the visible part of some
+    /// content larger than the screen. The region is clipped to the canvas.
+    pub fn crop(&self, region: Rect) -> Self {
+        let region = region
+            .intersection(&Rect::from_size(self.size))
+            .unwrap_or_default();
+        let mut cropped = Self::new(region.size);
+        for y in 0..region.size.height {
+            let start = (region.origin.y + y) * self.size.width + region.origin.x;
+            cropped.buffer[y * region.size.width..(y + 1) * region.size.width]
+                .clone_from_slice(&self.buffer[start..start + region.size.width]);
+        }
+        cropped.images.extend(
+            self.images
+                .iter()
+                .filter(|placement| region.contains_rect(&placement.region))
+                .map(|placement| ImagePlacement {
+                    region: Rect::new(
+                        (placement.region.origin - region.origin).to_point(),
+                        placement.region.size,
+                    ),
+                    image: placement.image.clone(),
+                }),
+        );
+        cropped
     }
 
     #[inline]
@@ -188,7 +398,15 @@ pub struct Style {
     pub background: Background,
     pub foreground: Foreground,
     pub bold: bool,
-    pub underline: bool,
+    /// Draw with decreased intensity, useful for secondary text.
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: Underline,
+    pub strikethrough: bool,
+    pub blink: Blink,
+    /// Swap the foreground and background colours when drawing. Unlike
+    /// [`Style::invert`], the colours stored in the style are left untouched.
+    pub reverse: bool,
 }
 
 impl Style {
@@ -204,47 +422,66 @@ impl Style {
             background,
             foreground,
             bold: false,
-            underline: false,
+            dim: false,
+            italic: false,
+            underline: Underline::None,
+            strikethrough: false,
+            blink: Blink::None,
+            reverse: false,
         }
     }
 
     #[inline]
     pub const fn bold(background: Background, foreground: Foreground) -> Self {
         Self {
-            background,
-            foreground,
             bold: true,
-            underline: false,
+            ..Self::normal(background, foreground)
+        }
+    }
+
+    #[inline]
+    pub const fn dim(background: Background, foreground: Foreground) -> Self {
+        Self {
+            dim: true,
+            ..Self::normal(background, foreground)
+        }
+    }
+
+    #[inline]
+    pub const fn italic(background: Background, foreground: Foreground) -> Self {
+        Self {
+            italic: true,
+            ..Self::normal(background, foreground)
         }
     }
 
     #[inline]
     pub const fn underline(background: Background, foreground: Foreground) -> Self {
         Self {
-            background,
-            foreground,
-            bold: false,
-            underline: true,
+            underline: Underline::Single,
+            ..Self::normal(background, foreground)
         }
     }
 
     #[inline]
-    pub const fn same_colour(colour: Colour) -> Self {
+    pub const fn strikethrough(background: Background, foreground: Foreground) -> Self {
         Self {
-            background: colour,
-            foreground: colour,
-            bold: false,
-            underline: false,
+            strikethrough: true,
+            ..Self::normal(background, foreground)
         }
     }
 
+    #[inline]
+    pub const fn same_colour(colour: Colour) -> Self {
+        Self::normal(colour, colour)
+    }
+
     #[inline]
     pub const fn invert(self) -> Self {
         Self {
             background: self.foreground,
             foreground: self.background,
-            bold: self.bold,
-            underline: self.underline,
+            ..self
         }
     }
 }
@@ -256,6 +493,61 @@ impl Default for Style {
     }
 }
 
+/// The line style used to underline content.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Underline {
+    #[default]
+    None,
+    Single,
+    Double,
+    /// A wavy line, commonly used by editors to mark diagnostics.
+    Curly,
+}
+
+/// The rate at which content should blink.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Blink {
+    #[default]
+    None,
+    /// Less than 150 times per minute.
+    Slow,
+    /// 150 times per minute or more.
+    Rapid,
+}
+
+/// Specifies how the text cursor should be drawn.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CursorStyle {
+    pub shape: CursorShape,
+    pub blinking: bool,
+}
+
+impl CursorStyle {
+    #[inline]
+    pub const fn new(shape: CursorShape, blinking: bool) -> Self {
+        Self { shape, blinking }
+    }
+}
+
+impl Default for CursorStyle {
+    /// A blinking block, the default cursor of most terminals.
+    #[inline]
+    fn default() -> Self {
+        Self::new(CursorShape::Block, true)
+    }
+}
+
+/// The shape of the text cursor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CursorShape {
+    /// A block covering the whole cell.
+    Block,
+    /// A vertical bar at the left edge of the cell.
+    Bar,
+    /// A horizontal line at the bottom of the cell.
+    Underline,
+}
+
 /// An RGB encoded colour, 1-byte per channel.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Colour {
@@ -304,20 +596,33 @@ fn clear_textel(textel: &mut Option<Textel>, style: Style, value: &str) {
         Some(Textel {
             style: ref mut textel_style,
             ref mut grapheme,
+            ref mut link,
         }) => {
             *textel_style = style;
             grapheme.clear();
             grapheme.push_str(value);
+            *link = None;
         }
         _ => {
             *textel = Some(Textel {
                 style,
                 grapheme: " ".into(),
+                link: None,
             });
         }
     }
 }
 
+/// Replaces the wide textel that covers the end of `line` with a space.
+#[inline]
+fn clear_wide_textel(line: &mut [Option<Textel>]) {
+    if let Some(textel) = line.iter_mut().rev().find_map(Option::as_mut) {
+        textel.grapheme.clear();
+        textel.grapheme.push_str(" ");
+        textel.link = None;
+    }
+}
+
 /// Wraps a [`Canvas`](terminal/struct.Canvas.html) and exposes a grid of square
 /// "pixels". The size of the grid `(2 * height, width)` of the dimensions of the
 /// wrapped canvas. This is implemented using Unicode's upper half block
@@ -328,7 +633,7 @@ pub struct SquarePixelGrid {
 
 impl SquarePixelGrid {
     pub fn new(size: Size) -> Self {
-        assert!(size.height % 2 == 0);
+        assert!(size.height.is_multiple_of(2));
         let mut canvas = Canvas::new(Size::new(size.width, size.height / 2));
         canvas.clear_with(Default::default(), UPPER_HALF_BLOCK);
         Self { canvas }
@@ -355,7 +660,7 @@ impl SquarePixelGrid {
             .textel_mut(position.x, position.y / 2)
             .as_mut()
             .expect("No textels should be uninitialised");
-        if position.y % 2 == 0 {
+        if position.y.is_multiple_of(2) {
             textel.style.foreground = colour;
         } else {
             textel.style.background = colour;
@@ -372,7 +677,42 @@ const UPPER_HALF_BLOCK: &str = "▀";
 
 #[cfg(test)]
 mod tests {
-    use super::{GraphemeCluster, Style, Textel};
+    use std::sync::Arc;
+
+    use super::{Canvas, GraphemeCluster, Image, Position, Rect, Size, Style, Textel};
+
+    fn line(canvas: &Canvas, y: usize) -> String {
+        let width = canvas.size().width;
+        canvas.buffer()[y * width..(y + 1) * width]
+            .iter()
+            .map(|textel| {
+                textel
+                    .as_ref()
+                    .map_or("_", |textel| textel.grapheme.as_str())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn draw_wide_graphemes() {
+        let mut canvas = Canvas::new(Size::new(5, 2));
+        canvas.clear(Style::default());
+        canvas.draw_str(0, 0, Style::default(), "a中b");
+        assert_eq!(line(&canvas, 0), "a中_b ");
+
+        // Wide graphemes that don't fit are replaced with a space and never
+        // overflow into the next line
+        canvas.draw_str(3, 0, Style::default(), "中中");
+        assert_eq!(line(&canvas, 0), "a中_中_");
+        canvas.draw_str(4, 1, Style::default(), "中");
+        assert_eq!(line(&canvas, 1), "     ");
+
+        // Overwriting half of a wide grapheme clears the other half
+        canvas.draw_str(2, 0, Style::default(), "x");
+        assert_eq!(line(&canvas, 0), "a x中_");
+        canvas.draw_str(3, 0, Style::default(), "y");
+        assert_eq!(line(&canvas, 0), "a xy ");
+    }
 
     #[test]
     fn size_of_style() {
@@ -393,4 +733,14 @@ mod tests {
             std::mem::size_of::<Option<GraphemeCluster>>()
         );
     }
+
+    #[test]
+    fn draw_empty_image() {
+        let mut canvas = Canvas::new(Size::new(2, 1));
+        canvas.clear(Style::default());
+        let image = Arc::new(Image::new(Size::zero(), Vec::new()));
+        canvas.draw_image(Rect::new(Position::new(0, 0), Size::new(2, 1)), image);
+        assert_eq!(line(&canvas, 0), "  ");
+        assert!(canvas.images().is_empty());
+    }
 }
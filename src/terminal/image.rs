@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use super::{Colour, Rect, Size};
+
+/// A raster image, a grid of RGB pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    size: Size,
+    pixels: Vec<Colour>,
+}
+
+impl Image {
+    /// Creates an image from its pixels, in row-major order.
+    ///
+    /// Panics if the number of pixels doesn't match the size.
+    ///
+    /// ```
+    /// # use zi::{terminal::Image, Colour, Size};
+    /// let image = Image::new(Size::new(2, 1), vec![Colour::black(), Colour::white()]);
+    /// assert_eq!(image.pixel(1, 0), Colour::white());
+    /// ```
+    pub fn new(size: Size, pixels: Vec<Colour>) -> Self {
+        assert_eq!(size.area(), pixels.len());
+        Self { size, pixels }
+    }
+
+    /// Creates an image from packed RGB data, 3 bytes per pixel in row-major
+    /// order, as produced by most image decoders.
+    ///
+    /// Panics if the length of the data doesn't match the size.
+    pub fn from_rgb(size: Size, data: &[u8]) -> Self {
+        assert_eq!(3 * size.area(), data.len());
+        Self::new(
+            size,
+            data.chunks(3)
+                .map(|rgb| Colour::rgb(rgb[0], rgb[1], rgb[2]))
+                .collect(),
+        )
+    }
+
+    #[inline]
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    #[inline]
+    pub fn pixels(&self) -> &[Colour] {
+        &self.pixels
+    }
+
+    #[inline]
+    pub fn pixel(&self, x: usize, y: usize) -> Colour {
+        self.pixels[y * self.size.width + x]
+    }
+
+    /// Returns a copy of the image scaled to the given size, using nearest
+    /// neighbour sampling. An empty image has no pixels to sample, it's
+    /// resized to a black one.
+    pub fn resized(&self, size: Size) -> Self {
+        if size == self.size {
+            return self.clone();
+        }
+        if self.size.is_empty() {
+            return Self::new(size, vec![Colour::black(); size.area()]);
+        }
+        let pixels = (0..size.height)
+            .flat_map(|y| (0..size.width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                self.pixel(
+                    x * self.size.width / size.width,
+                    y * self.size.height / size.height,
+                )
+            })
+            .collect();
+        Self::new(size, pixels)
+    }
+
+    /// Returns the image as packed RGB data, 3 bytes per pixel.
+    pub fn to_rgb(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|colour| [colour.red, colour.green, colour.blue])
+            .collect()
+    }
+}
+
+/// An image drawn over a region of a [`Canvas`](struct.Canvas.html), see
+/// [`Canvas::draw_image`](struct.Canvas.html#method.draw_image).
+#[derive(Debug, Clone)]
+pub struct ImagePlacement {
+    /// The cells covered by the image, it's scaled to fill them.
+    pub region: Rect,
+    pub image: Arc<Image>,
+}
+
+impl PartialEq for ImagePlacement {
+    fn eq(&self, other: &Self) -> bool {
+        // Images are compared by identity, they're potentially large
+        self.region == other.region && Arc::ptr_eq(&self.image, &other.image)
+    }
+}
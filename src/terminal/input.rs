@@ -1,3 +1,7 @@
+use std::fmt;
+
+use super::Position;
+
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
 pub enum Key {
     /// Backspace.
@@ -26,8 +30,13 @@ pub enum Key {
     Insert,
     /// Function keys.
     ///
-    /// Only function keys 1 through 12 are supported.
+    /// Function keys 13 through 24 are only reported by terminals supporting
+    /// the kitty keyboard protocol.
     F(u8),
+    /// Media keys.
+    ///
+    /// Only reported by terminals supporting the kitty keyboard protocol.
+    Media(MediaKey),
     /// Normal character.
     Char(char),
     /// Alt modified character.
@@ -41,3 +50,94 @@ pub enum Key {
     /// Esc key.
     Esc,
 }
+
+impl fmt::Display for Key {
+    /// Formats the key the way Emacs describes key sequences, e.g. `C-x`,
+    /// `A-f`, `RET` or `SPC`.
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Key::Char(' ') => write!(formatter, "SPC"),
+            Key::Char('\n') => write!(formatter, "RET"),
+            Key::Char('\t') => write!(formatter, "TAB"),
+            Key::Char(char) => write!(formatter, "{}", char),
+            Key::Ctrl(char) => write!(formatter, "C-{}", char),
+            Key::Alt(char) => write!(formatter, "A-{}", char),
+            Key::F(number) => write!(formatter, "F{}", number),
+            Key::Esc => write!(formatter, "ESC"),
+            key => write!(formatter, "{:?}", key),
+        }
+    }
+}
+
+/// A media key, see [`Key::Media`](enum.Key.html#variant.Media).
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
+pub enum MediaKey {
+    Play,
+    Pause,
+    PlayPause,
+    Reverse,
+    Stop,
+    FastForward,
+    Rewind,
+    TrackNext,
+    TrackPrevious,
+    Record,
+    LowerVolume,
+    RaiseVolume,
+    MuteVolume,
+}
+
+/// A mouse event.
+///
+/// When delivered to a component (see
+/// [`Component::mouse_binding`](../trait.Component.html#method.mouse_binding)),
+/// the position is relative to the component's frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MouseEvent {
+    /// The kind of mouse event.
+    pub kind: MouseEventKind,
+    /// The grid cell under the pointer.
+    pub position: Position,
+    /// The key modifiers active when the event occurred.
+    pub modifiers: Modifiers,
+}
+
+/// The kind of a mouse event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseEventKind {
+    /// A mouse button was pressed.
+    Press(MouseButton),
+    /// A mouse button was released.
+    Release(MouseButton),
+    /// The pointer moved while a mouse button was held down.
+    Drag(MouseButton),
+    /// The pointer moved while no mouse button was held down.
+    Move,
+    /// The mouse wheel was scrolled upwards (away from the user) by one line.
+    ScrollUp,
+    /// The mouse wheel was scrolled downwards (towards the user) by one line.
+    ScrollDown,
+    /// The mouse wheel was scrolled to the left by one column.
+    ScrollLeft,
+    /// The mouse wheel was scrolled to the right by one column.
+    ScrollRight,
+}
+
+/// A mouse button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    /// Left mouse button.
+    Left,
+    /// Right mouse button.
+    Right,
+    /// Middle mouse button.
+    Middle,
+}
+
+/// Key modifiers active during a mouse event.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+}
@@ -4,8 +4,13 @@
 //! via their child components and their descendants. At the bottom of the
 //! component hierarchy, low level components would draw directly on a canvas.
 
-pub use canvas::{Background, Canvas, Colour, Foreground, GraphemeCluster, SquarePixelGrid, Style};
-pub use input::Key;
+pub use canvas::{
+    Background, Blink, Canvas, Colour, CursorShape, CursorStyle, Foreground, GraphemeCluster,
+    Hyperlink, SquarePixelGrid, Style, Underline,
+};
+pub use image::{Image, ImagePlacement};
+pub use input::{Key, MediaKey, Modifiers, MouseButton, MouseEvent, MouseEventKind};
+pub use span::{Line, Span};
 
 /// A 2D rectangle with usize coordinates. Re-exported from
 /// [euclid](https://docs.rs/euclid).
@@ -20,4 +25,6 @@ pub type Position = euclid::default::Point2D<usize>;
 pub type Size = euclid::default::Size2D<usize>;
 
 pub(crate) mod canvas;
+pub(crate) mod image;
 pub(crate) mod input;
+pub(crate) mod span;
@@ -0,0 +1,91 @@
+use std::iter::FromIterator;
+use unicode_width::UnicodeWidthStr;
+
+use super::Style;
+
+/// A piece of text drawn with a single style.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub content: String,
+    pub style: Style,
+}
+
+impl Span {
+    pub fn new(content: impl Into<String>, style: Style) -> Self {
+        Self {
+            content: content.into(),
+            style,
+        }
+    }
+
+    /// Returns the number of columns the span takes when drawn.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.content.width()
+    }
+}
+
+/// A line of text made of spans with different styles, to mix colours and
+/// attributes without drawing every piece separately.
+///
+/// ```
+/// # use zi::{Colour, Line, Style};
+/// let normal = Style::normal(Colour::black(), Colour::white());
+/// let line = Line::new()
+///     .span("Press ", normal)
+///     .span("q", Style::bold(Colour::black(), Colour::rgb(250, 189, 47)))
+///     .span(" to quit", normal);
+/// assert_eq!(line.width(), 15);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Line {
+    pub spans: Vec<Span>,
+}
+
+impl Line {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a line with a single span.
+    pub fn styled(content: impl Into<String>, style: Style) -> Self {
+        Self::from(Span::new(content, style))
+    }
+
+    /// Appends a span to the line.
+    pub fn span(mut self, content: impl Into<String>, style: Style) -> Self {
+        self.push(Span::new(content, style));
+        self
+    }
+
+    #[inline]
+    pub fn push(&mut self, span: Span) {
+        self.spans.push(span);
+    }
+
+    /// Returns the number of columns the line takes when drawn.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.spans.iter().map(Span::width).sum()
+    }
+}
+
+impl From<Span> for Line {
+    fn from(span: Span) -> Self {
+        Self { spans: vec![span] }
+    }
+}
+
+impl From<Vec<Span>> for Line {
+    fn from(spans: Vec<Span>) -> Self {
+        Self { spans }
+    }
+}
+
+impl FromIterator<Span> for Line {
+    fn from_iter<IteratorT: IntoIterator<Item = Span>>(spans: IteratorT) -> Self {
+        Self {
+            spans: spans.into_iter().collect(),
+        }
+    }
+}
@@ -49,6 +49,11 @@ impl Cursor {
         }
     }
 
+    /// Whether a selection was started with `begin_selection`.
+    pub fn has_selection(&self) -> bool {
+        self.selection.is_some()
+    }
+
     pub fn begin_selection(&mut self) {
         self.selection = Some(self.range.start)
     }
@@ -134,6 +139,13 @@ impl Cursor {
         self.visual_horizontal_offset = None;
     }
 
+    /// Moves the cursor to the grapheme starting at `char_index`.
+    pub fn move_to_char<'a>(&mut self, text: &impl TextStorage<'a>, char_index: CharIndex) {
+        let char_index = cmp::min(char_index, text.len_chars().saturating_sub(1.into()));
+        self.range = char_index..text.next_grapheme_boundary(char_index);
+        self.visual_horizontal_offset = None;
+    }
+
     pub fn move_to_start_of_buffer<'a>(&mut self, text: &impl TextStorage<'a>) {
         self.range = CharIndex(0)..text.next_grapheme_boundary(CharIndex(0));
         self.visual_horizontal_offset = None;
@@ -215,25 +227,26 @@ impl Cursor {
         }
     }
 
-    //     pub fn delete_selection(&mut self, text: &mut impl TextStorage) {
-    //         // Delete selection
-    //         let selection = self.selection();
-    //         text.remove(selection.start.0..selection.end.0);
+    pub fn delete_selection<'a>(&mut self, text: &mut impl TextStorageMut<'a>) {
+        // Delete selection
+        let selection = self.selection();
+        text.remove(selection.start.0..selection.end.0);
+        ensure_trailing_newline_with_content(text);
 
-    //         // Update cursor position
-    //         let grapheme_start = cmp::min(
-    //             self.range.start,
-    //             prev_grapheme_boundary(&text.slice(..), CharIndex(text.len_chars())),
-    //         );
-    //         let grapheme_end = next_grapheme_boundary(&text.slice(..), grapheme_start);
-    //         if grapheme_start != grapheme_end {
-    //             self.range = grapheme_start..grapheme_end
-    //         } else {
-    //             self.range = CharIndex(0)..CharIndex(1)
-    //         }
-    //         self.clear_selection();
-    //         self.visual_horizontal_offset = None;
-    //     }
+        // Update cursor position
+        let grapheme_start = cmp::min(
+            selection.start,
+            text.prev_grapheme_boundary(text.len_chars()),
+        );
+        let grapheme_end = text.next_grapheme_boundary(grapheme_start);
+        if grapheme_start != grapheme_end {
+            self.range = grapheme_start..grapheme_end
+        } else {
+            self.range = CharIndex(0)..CharIndex(1)
+        }
+        self.clear_selection();
+        self.visual_horizontal_offset = None;
+    }
 
     pub fn backspace<'a>(&mut self, text: &mut impl TextStorageMut<'a>) {
         if self.range.start.0 > 0 {
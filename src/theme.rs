@@ -0,0 +1,207 @@
+//! Themes, mapping the semantic names of styles to colours and attributes.
+//! The current theme is shared by all components, which read it with
+//! [`ComponentLink::theme`](struct.ComponentLink.html#method.theme), and it
+//! can be switched at runtime with
+//! [`ComponentLink::set_theme`](struct.ComponentLink.html#method.set_theme).
+
+use std::sync::{Arc, RwLock};
+
+use crate::terminal::{Colour, Style};
+
+/// The styles of an application, by what they're used for rather than by
+/// colour, so that switching themes restyles everything consistently.
+///
+/// The styles of the built-in components can be created from a theme, e.g.
+/// `ProgressBarStyle::from(&theme)`. Their defaults don't depend on the
+/// current theme.
+///
+/// ```
+/// # use zi::prelude::*;
+/// # use zi::{
+/// #     components::{
+/// #         progress::{ProgressBar, ProgressBarProperties},
+/// #         text::{Text, TextProperties},
+/// #     },
+/// #     Theme,
+/// # };
+/// struct Status {
+///     link: ComponentLink<Self>,
+/// }
+///
+/// impl Component for Status {
+///     type Message = ();
+///     type Properties = ();
+///
+///     fn create(_: (), _frame: Rect, link: ComponentLink<Self>) -> Self {
+///         Self { link }
+///     }
+///
+///     fn update(&mut self, _: ()) -> ShouldRender {
+///         self.link.set_theme(Theme::light());
+///         ShouldRender::No
+///     }
+///
+///     fn view(&self) -> Layout {
+///         let theme = self.link.theme();
+///         layout::column([
+///             layout::fixed(
+///                 1,
+///                 Text::with(TextProperties::new().style(theme.success).content("Connected")),
+///             ),
+///             layout::fixed(
+///                 1,
+///                 ProgressBar::with(
+///                     ProgressBarProperties::new()
+///                         .style((&theme).into())
+///                         .progress(0.5),
+///                 ),
+///             ),
+///         ])
+///     }
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// The background of the application and the text drawn on it.
+    pub background: Style,
+    /// Panels, inputs, menus and other elements drawn over the background.
+    pub surface: Style,
+    /// Secondary text, e.g. hints, placeholders and disabled items.
+    pub muted: Style,
+    /// What should stand out, e.g. the focused element or a prompt.
+    pub accent: Style,
+    /// Selected items and text.
+    pub selection: Style,
+    pub border: Style,
+    pub success: Style,
+    pub warning: Style,
+    pub error: Style,
+}
+
+impl Theme {
+    /// A dark theme, with the gruvbox colours used by the components by
+    /// default.
+    pub fn dark() -> Self {
+        const DARK0: Colour = Colour::rgb(40, 40, 40);
+        const DARK1: Colour = Colour::rgb(60, 56, 54);
+        const DARK3: Colour = Colour::rgb(102, 92, 84);
+        const LIGHT1: Colour = Colour::rgb(235, 219, 178);
+        const GRAY_245: Colour = Colour::rgb(146, 131, 116);
+        const BRIGHT_BLUE: Colour = Colour::rgb(131, 165, 152);
+        const BRIGHT_GREEN: Colour = Colour::rgb(184, 187, 38);
+        const BRIGHT_YELLOW: Colour = Colour::rgb(250, 189, 47);
+        const BRIGHT_RED: Colour = Colour::rgb(251, 73, 52);
+
+        Self {
+            background: Style::normal(DARK0, LIGHT1),
+            surface: Style::normal(DARK1, LIGHT1),
+            muted: Style::normal(DARK0, GRAY_245),
+            accent: Style::bold(DARK0, BRIGHT_BLUE),
+            selection: Style::normal(DARK3, LIGHT1),
+            border: Style::normal(DARK0, DARK3),
+            success: Style::normal(DARK0, BRIGHT_GREEN),
+            warning: Style::normal(DARK0, BRIGHT_YELLOW),
+            error: Style::normal(DARK0, BRIGHT_RED),
+        }
+    }
+
+    /// A light theme, with gruvbox colours.
+    pub fn light() -> Self {
+        const LIGHT0: Colour = Colour::rgb(251, 241, 199);
+        const LIGHT1: Colour = Colour::rgb(235, 219, 178);
+        const LIGHT3: Colour = Colour::rgb(189, 174, 147);
+        const DARK1: Colour = Colour::rgb(60, 56, 54);
+        const GRAY_244: Colour = Colour::rgb(146, 131, 116);
+        const FADED_BLUE: Colour = Colour::rgb(7, 102, 120);
+        const FADED_GREEN: Colour = Colour::rgb(121, 116, 14);
+        const FADED_YELLOW: Colour = Colour::rgb(181, 118, 20);
+        const FADED_RED: Colour = Colour::rgb(157, 0, 6);
+
+        Self {
+            background: Style::normal(LIGHT0, DARK1),
+            surface: Style::normal(LIGHT1, DARK1),
+            muted: Style::normal(LIGHT0, GRAY_244),
+            accent: Style::bold(LIGHT0, FADED_BLUE),
+            selection: Style::normal(LIGHT3, DARK1),
+            border: Style::normal(LIGHT0, LIGHT3),
+            success: Style::normal(LIGHT0, FADED_GREEN),
+            warning: Style::normal(LIGHT0, FADED_YELLOW),
+            error: Style::normal(LIGHT0, FADED_RED),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// The current theme, shared by the `App` and the links of all components.
+pub(crate) type SharedTheme = Arc<RwLock<Theme>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{
+        calendar::{CalendarStyle, DateInputStyle},
+        chart::{BarChartStyle, GaugeStyle},
+        checkbox::ControlStyle,
+        code::CodeViewStyle,
+        dropdown::DropdownStyle,
+        input::InputStyle,
+        log::LogViewStyle,
+        menu::MenuStyle,
+        picker::PickerStyle,
+        progress::ProgressBarStyle,
+        scrollbar::ScrollbarStyle,
+        split::SplitPaneStyle,
+        table::TableStyle,
+        tabs::TabsStyle,
+        tree::TreeStyle,
+    };
+
+    /// Asserts the styles created from the dark and light themes differ.
+    fn assert_themed<StyleT>()
+    where
+        StyleT: for<'a> From<&'a Theme> + PartialEq + std::fmt::Debug,
+    {
+        assert_ne!(
+            StyleT::from(&Theme::dark()),
+            StyleT::from(&Theme::light()),
+            "{} ignores the theme",
+            std::any::type_name::<StyleT>()
+        );
+    }
+
+    #[test]
+    fn component_styles_follow_theme() {
+        assert_themed::<CalendarStyle>();
+        assert_themed::<DateInputStyle>();
+        assert_themed::<BarChartStyle>();
+        assert_themed::<GaugeStyle>();
+        assert_themed::<ControlStyle>();
+        assert_themed::<CodeViewStyle>();
+        assert_themed::<DropdownStyle>();
+        assert_themed::<InputStyle>();
+        assert_themed::<LogViewStyle>();
+        assert_themed::<MenuStyle>();
+        assert_themed::<PickerStyle>();
+        assert_themed::<ProgressBarStyle>();
+        assert_themed::<ScrollbarStyle>();
+        assert_themed::<SplitPaneStyle>();
+        assert_themed::<TableStyle>();
+        assert_themed::<TabsStyle>();
+        assert_themed::<TreeStyle>();
+
+        // Styles are picked by what they're used for
+        let theme = Theme::light();
+        let input = InputStyle::from(&theme);
+        assert_eq!(input.content, theme.background);
+        assert_eq!(input.selection, theme.selection);
+        assert_eq!(input.placeholder, theme.muted);
+        let table = TableStyle::from(&theme);
+        assert_eq!(table.row, theme.background);
+        assert_eq!(table.selected, theme.selection);
+    }
+}